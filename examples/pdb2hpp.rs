@@ -45,17 +45,17 @@ pub fn type_name(
 
         pdb::TypeData::Class(data) => {
             needed_types.insert(type_index);
-            data.name.to_string().into_owned()
+            data.name.clone()
         }
 
         pdb::TypeData::Enumeration(data) => {
             needed_types.insert(type_index);
-            data.name.to_string().into_owned()
+            data.name.clone()
         }
 
         pdb::TypeData::Union(data) => {
             needed_types.insert(type_index);
-            data.name.to_string().into_owned()
+            data.name.clone()
         }
 
         pdb::TypeData::Pointer(data) => format!(
@@ -81,11 +81,38 @@ pub fn type_name(
         }
 
         pdb::TypeData::Array(data) => {
-            let mut name = type_name(type_finder, data.element_type, needed_types)?;
-            for size in data.dimensions {
-                name = format!("{name}[{size}]");
+            let name = type_name(type_finder, data.element_type, needed_types)?;
+            match data.element_count(type_finder)? {
+                Some(count) => format!("{name}[{count}]"),
+                None => format!("{name}[/* {} bytes */]", data.len_bytes()),
             }
-            name
+        }
+
+        pdb::TypeData::Procedure(data) => {
+            let return_type = match data.return_type {
+                Some(return_type) => type_name(type_finder, return_type, needed_types)?,
+                None => "void".to_string(),
+            };
+            let arguments = argument_list(type_finder, data.argument_list, needed_types)?;
+            format!(
+                "{} ({})({})",
+                return_type,
+                data.calling_convention(),
+                arguments.join(", ")
+            )
+        }
+
+        pdb::TypeData::MemberFunction(data) => {
+            let return_type = type_name(type_finder, data.return_type, needed_types)?;
+            let class_name = type_name(type_finder, data.class_type, needed_types)?;
+            let arguments = argument_list(type_finder, data.argument_list, needed_types)?;
+            format!(
+                "{} ({} {}::)({})",
+                return_type,
+                data.calling_convention(),
+                class_name,
+                arguments.join(", ")
+            )
         }
 
         _ => format!("Type{type_index} /* TODO: figure out how to name it */"),
@@ -100,20 +127,20 @@ pub fn type_name(
 }
 
 #[derive(Debug, Clone, PartialEq, Eq)]
-struct Class<'p> {
+struct Class {
     kind: pdb::ClassKind,
-    name: pdb::RawString<'p>,
+    name: String,
     base_classes: Vec<BaseClass>,
-    fields: Vec<Field<'p>>,
-    instance_methods: Vec<Method<'p>>,
-    static_methods: Vec<Method<'p>>,
+    fields: Vec<Field>,
+    instance_methods: Vec<Method>,
+    static_methods: Vec<Method>,
 }
 
-impl<'p> Class<'p> {
+impl Class {
     #[allow(clippy::unnecessary_wraps)]
     fn add_derived_from(
         &mut self,
-        _: &pdb::TypeFinder<'p>,
+        _: &pdb::TypeFinder<'_>,
         _: pdb::TypeIndex,
         _: &mut TypeSet,
     ) -> pdb::Result<()> {
@@ -123,7 +150,7 @@ impl<'p> Class<'p> {
 
     fn add_fields(
         &mut self,
-        type_finder: &pdb::TypeFinder<'p>,
+        type_finder: &pdb::TypeFinder<'_>,
         type_index: pdb::TypeIndex,
         needed_types: &mut TypeSet,
     ) -> pdb::Result<()> {
@@ -149,8 +176,8 @@ impl<'p> Class<'p> {
 
     fn add_field(
         &mut self,
-        type_finder: &pdb::TypeFinder<'p>,
-        field: &pdb::TypeData<'p>,
+        type_finder: &pdb::TypeFinder<'_>,
+        field: &pdb::TypeData,
         needed_types: &mut TypeSet,
     ) -> pdb::Result<()> {
         match *field {
@@ -158,14 +185,14 @@ impl<'p> Class<'p> {
                 // TODO: attributes (static, virtual, etc.)
                 self.fields.push(Field {
                     type_name: type_name(type_finder, data.field_type, needed_types)?,
-                    name: data.name,
+                    name: data.name.clone(),
                     offset: data.offset,
                 });
             }
 
             pdb::TypeData::Method(ref data) => {
                 let method = Method::find(
-                    data.name,
+                    data.name.clone(),
                     data.attributes,
                     type_finder,
                     data.method_type,
@@ -191,7 +218,7 @@ impl<'p> Class<'p> {
                         {
                             // hooray
                             let method = Method::find(
-                                data.name,
+                                data.name.clone(),
                                 attributes,
                                 type_finder,
                                 method_type,
@@ -234,7 +261,7 @@ impl<'p> Class<'p> {
     }
 }
 
-impl fmt::Display for Class<'_> {
+impl fmt::Display for Class {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         write!(
             f,
@@ -244,7 +271,7 @@ impl fmt::Display for Class<'_> {
                 pdb::ClassKind::Struct => "struct",
                 pdb::ClassKind::Interface => "interface", // when can this happen?
             },
-            self.name.to_string()
+            self.name
         )?;
 
         if !self.base_classes.is_empty() {
@@ -271,9 +298,7 @@ impl fmt::Display for Class<'_> {
             writeln!(
                 f,
                 "\t/* offset {:3} */ {} {};",
-                field.offset,
-                field.type_name,
-                field.name.to_string()
+                field.offset, field.type_name, field.name
             )?;
         }
 
@@ -285,7 +310,7 @@ impl fmt::Display for Class<'_> {
                     "\t{}{} {}({});",
                     if method.is_virtual { "virtual " } else { "" },
                     method.return_type_name,
-                    method.name.to_string(),
+                    method.name,
                     method.arguments.join(", ")
                 )?;
             }
@@ -299,7 +324,7 @@ impl fmt::Display for Class<'_> {
                     "\t{}static {} {}({});",
                     if method.is_virtual { "virtual " } else { "" },
                     method.return_type_name,
-                    method.name.to_string(),
+                    method.name,
                     method.arguments.join(", ")
                 )?;
             }
@@ -318,28 +343,28 @@ struct BaseClass {
 }
 
 #[derive(Debug, Clone, PartialEq, Eq)]
-struct Field<'p> {
+struct Field {
     type_name: String,
-    name: pdb::RawString<'p>,
+    name: String,
     offset: u64,
 }
 
 #[derive(Debug, Clone, PartialEq, Eq)]
-struct Method<'p> {
-    name: pdb::RawString<'p>,
+struct Method {
+    name: String,
     return_type_name: String,
     arguments: Vec<String>,
     is_virtual: bool,
 }
 
-impl<'p> Method<'p> {
+impl Method {
     fn find(
-        name: pdb::RawString<'p>,
+        name: String,
         attributes: pdb::FieldAttributes,
-        type_finder: &pdb::TypeFinder<'p>,
+        type_finder: &pdb::TypeFinder<'_>,
         type_index: pdb::TypeIndex,
         needed_types: &mut TypeSet,
-    ) -> pdb::Result<Method<'p>> {
+    ) -> pdb::Result<Method> {
         match type_finder.find(type_index)?.parse()? {
             pdb::TypeData::MemberFunction(data) => Ok(Method {
                 name,
@@ -376,16 +401,16 @@ fn argument_list(
 }
 
 #[derive(Debug, Clone, PartialEq, Eq)]
-struct Enum<'p> {
-    name: pdb::RawString<'p>,
+struct Enum {
+    name: String,
     underlying_type_name: String,
-    values: Vec<EnumValue<'p>>,
+    values: Vec<EnumValue>,
 }
 
-impl<'p> Enum<'p> {
+impl Enum {
     fn add_fields(
         &mut self,
-        type_finder: &pdb::TypeFinder<'p>,
+        type_finder: &pdb::TypeFinder<'_>,
         type_index: pdb::TypeIndex,
         needed_types: &mut TypeSet,
     ) -> pdb::Result<()> {
@@ -409,40 +434,41 @@ impl<'p> Enum<'p> {
         Ok(())
     }
 
-    fn add_field(&mut self, _: &pdb::TypeFinder<'p>, field: &pdb::TypeData<'p>, _: &mut TypeSet) {
+    fn add_field(&mut self, _: &pdb::TypeFinder<'_>, field: &pdb::TypeData, _: &mut TypeSet) {
         // ignore everything else even though that's sad
         if let pdb::TypeData::Enumerate(ref data) = field {
             self.values.push(EnumValue {
-                name: data.name,
+                name: data.name.clone(),
                 value: data.value,
             });
         }
     }
 }
 
-impl fmt::Display for Enum<'_> {
+impl fmt::Display for Enum {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         writeln!(
             f,
             "enum {} /* stored as {} */ {{",
-            self.name.to_string(),
-            self.underlying_type_name
+            self.name, self.underlying_type_name
         )?;
 
         for value in &self.values {
             writeln!(
                 f,
                 "\t{} = {},",
-                value.name.to_string(),
+                value.name,
                 match value.value {
                     pdb::Variant::U8(v) => format!("0x{v:02x}"),
                     pdb::Variant::U16(v) => format!("0x{v:04x}"),
                     pdb::Variant::U32(v) => format!("0x{v:08x}"),
-                    pdb::Variant::U64(v) => format!("0x{v:16x}"),
+                    pdb::Variant::U64(v) => format!("0x{v:016x}"),
                     pdb::Variant::I8(v) => format!("{v}"),
                     pdb::Variant::I16(v) => format!("{v}"),
                     pdb::Variant::I32(v) => format!("{v}"),
                     pdb::Variant::I64(v) => format!("{v}"),
+                    pdb::Variant::U128(v) => format!("0x{v:032x}"),
+                    pdb::Variant::I128(v) => format!("{v}"),
                 }
             )?;
         }
@@ -453,18 +479,18 @@ impl fmt::Display for Enum<'_> {
 }
 
 #[derive(Debug, Clone, PartialEq, Eq)]
-struct EnumValue<'p> {
-    name: pdb::RawString<'p>,
+struct EnumValue {
+    name: String,
     value: pdb::Variant,
 }
 
 #[derive(Debug, Clone, PartialEq, Eq)]
-struct ForwardReference<'p> {
+struct ForwardReference {
     kind: pdb::ClassKind,
-    name: pdb::RawString<'p>,
+    name: String,
 }
 
-impl fmt::Display for ForwardReference<'_> {
+impl fmt::Display for ForwardReference {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         writeln!(
             f,
@@ -474,19 +500,19 @@ impl fmt::Display for ForwardReference<'_> {
                 pdb::ClassKind::Struct => "struct",
                 pdb::ClassKind::Interface => "interface", // when can this happen?
             },
-            self.name.to_string()
+            self.name
         )
     }
 }
 
 #[derive(Debug, Clone, PartialEq, Eq)]
-struct Data<'p> {
-    forward_references: Vec<ForwardReference<'p>>,
-    classes: Vec<Class<'p>>,
-    enums: Vec<Enum<'p>>,
+struct Data {
+    forward_references: Vec<ForwardReference>,
+    classes: Vec<Class>,
+    enums: Vec<Enum>,
 }
 
-impl fmt::Display for Data<'_> {
+impl fmt::Display for Data {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         writeln!(f, "// automatically generated by pdb2hpp\n// do not edit")?;
 
@@ -511,8 +537,8 @@ impl fmt::Display for Data<'_> {
     }
 }
 
-impl<'p> Data<'p> {
-    fn new() -> Data<'p> {
+impl Data {
+    fn new() -> Data {
         Data {
             forward_references: Vec::new(),
             classes: Vec::new(),
@@ -522,7 +548,7 @@ impl<'p> Data<'p> {
 
     fn add(
         &mut self,
-        type_finder: &pdb::TypeFinder<'p>,
+        type_finder: &pdb::TypeFinder<'_>,
         type_index: pdb::TypeIndex,
         needed_types: &mut TypeSet,
     ) -> pdb::Result<()> {
@@ -0,0 +1,88 @@
+use std::env;
+use std::time::Instant;
+
+use pdb2 as pdb;
+
+use getopts::Options;
+use pdb::FallibleIterator;
+
+fn print_usage(program: &str, opts: Options) {
+    let brief = format!("Usage: {program} input.pdb");
+    print!("{}", opts.usage(&brief));
+}
+
+fn walk_module(info: Option<pdb::ModuleInfo<'_>>) -> pdb::Result<usize> {
+    let info = match info {
+        Some(info) => info,
+        None => return Ok(0),
+    };
+
+    let mut count = 0;
+    let mut symbols = info.symbols()?;
+    while let Some(symbol) = symbols.next()? {
+        if symbol.parse().is_ok() {
+            count += 1;
+        }
+    }
+
+    let program = info.line_program()?;
+    count += program.lines().count()?;
+
+    Ok(count)
+}
+
+fn compare(filename: &str) -> pdb::Result<()> {
+    let file = std::fs::File::open(filename)?;
+    let mut pdb = pdb::PDB::open(file)?;
+
+    let start = Instant::now();
+    let dbi = pdb.debug_information()?;
+    let mut modules = dbi.modules()?;
+    let mut sequential = 0;
+    while let Some(module) = modules.next()? {
+        sequential += walk_module(pdb.module_info(&module)?)?;
+    }
+    let sequential_time = start.elapsed();
+
+    let start = Instant::now();
+    let mut parallel = 0;
+    for count in pdb.par_modules(|_module, info| walk_module(info))? {
+        parallel += count?;
+    }
+    let parallel_time = start.elapsed();
+
+    assert_eq!(sequential, parallel);
+    println!("{sequential} records in {} modules", dbi.module_count()?);
+    println!("sequential: {sequential_time:?}");
+    println!(
+        "parallel:   {parallel_time:?} ({} threads, {:.2}x)",
+        rayon::current_num_threads(),
+        sequential_time.as_secs_f64() / parallel_time.as_secs_f64()
+    );
+
+    Ok(())
+}
+
+fn main() {
+    let args: Vec<String> = env::args().collect();
+    let program = args[0].clone();
+
+    let mut opts = Options::new();
+    opts.optflag("h", "help", "print this help menu");
+    let matches = match opts.parse(&args[1..]) {
+        Ok(m) => m,
+        Err(f) => panic!("{}", f.to_string()),
+    };
+
+    let filename = if matches.free.len() == 1 {
+        &matches.free[0]
+    } else {
+        print_usage(&program, opts);
+        return;
+    };
+
+    match compare(filename) {
+        Ok(_) => (),
+        Err(e) => eprintln!("error walking PDB: {e}"),
+    }
+}
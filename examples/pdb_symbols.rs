@@ -3,14 +3,14 @@ use std::env;
 use pdb2 as pdb;
 
 use getopts::Options;
-use pdb::{FallibleIterator, PdbInternalSectionOffset, RawString};
+use pdb::{FallibleIterator, PdbInternalSectionOffset};
 
 fn print_usage(program: &str, opts: Options) {
     let brief = format!("Usage: {program} input.pdb");
     print!("{}", opts.usage(&brief));
 }
 
-fn print_row(offset: PdbInternalSectionOffset, kind: &str, name: pdb::RawString<'_>) {
+fn print_row(offset: PdbInternalSectionOffset, kind: &str, name: &str) {
     println!(
         "{:x}\t{:x}\t{}\t{}",
         offset.section, offset.offset, kind, name
@@ -20,20 +20,20 @@ fn print_row(offset: PdbInternalSectionOffset, kind: &str, name: pdb::RawString<
 fn print_symbol(symbol: &pdb::Symbol<'_>) -> pdb::Result<()> {
     match symbol.parse()? {
         pdb::SymbolData::Public(data) => {
-            print_row(data.offset, "function", data.name);
+            print_row(data.offset, "function", &data.name);
         }
         pdb::SymbolData::Data(data) => {
-            print_row(data.offset, "data", data.name);
+            print_row(data.offset, "data", &data.name);
         }
         pdb::SymbolData::Procedure(data) => {
-            print_row(data.offset, "function", data.name);
+            print_row(data.offset, "function", &data.name);
         }
         pdb::SymbolData::ManagedProcedure(data) => match data.name {
-            None => print_row(data.offset, "function", RawString::from(&b"<empty>"[..])),
-            Some(name) => print_row(data.offset, "function", name),
+            None => print_row(data.offset, "function", "<empty>"),
+            Some(name) => print_row(data.offset, "function", &name),
         },
         pdb::SymbolData::ManagedSlot(data) => {
-            print_row(data.offset, "data", data.name);
+            print_row(data.offset, "data", &data.name);
         }
         _ => {
             // ignore everything else
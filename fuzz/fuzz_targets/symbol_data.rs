@@ -0,0 +1,10 @@
+#![no_main]
+
+use libfuzzer_sys::fuzz_target;
+use scroll::Pread;
+
+// Parses the input as a single symbol record, starting with the symbol kind. Parsing may fail, but
+// it must never panic.
+fuzz_target!(|data: &[u8]| {
+    let _ = data.pread_with::<pdb2::SymbolData>(0, ());
+});
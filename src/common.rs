@@ -6,10 +6,11 @@
 // copied, modified, or distributed except according to those terms.
 
 use std::borrow::Cow;
+use std::convert::TryFrom;
 use std::fmt;
 use std::io;
 use std::mem;
-use std::ops::{Add, AddAssign, Sub};
+use std::ops::{Add, AddAssign, Range, Sub};
 use std::result;
 use std::slice;
 
@@ -25,6 +26,12 @@ pub enum Error {
     /// The input data was not recognized as a MSF (PDB) file.
     UnrecognizedFileFormat,
 
+    /// The data is compressed in a format that cannot be read.
+    ///
+    /// This is returned for PDBs stored in the compressed MSFZ container, and for compressed
+    /// injected sources.
+    UnsupportedCompression,
+
     /// The MSF header specifies an invalid page size.
     InvalidPageSize(u32),
 
@@ -57,6 +64,9 @@ pub enum Error {
     /// A symbol record's length value was impossibly small.
     SymbolTooShort,
 
+    /// Navigating the symbol stream reached the same symbol twice.
+    SymbolCycle(u32),
+
     /// Support for symbols of this kind is not implemented.
     UnimplementedSymbolKind(u16),
 
@@ -82,12 +92,21 @@ pub enum Error {
     /// Cross module reference not found in imports.
     CrossModuleRefNotFound(u32),
 
+    /// Type index does not refer to a function type.
+    NotAFunctionType(u32),
+
+    /// Type index does not refer to a field list.
+    NotAFieldList(u32),
+
     /// Variable-length numeric parsing encountered an unexpected prefix.
     UnexpectedNumericPrefix(u16),
 
     /// Required mapping for virtual addresses (OMAP) was not found.
     AddressMapNotFound,
 
+    /// A section offset (`.0:.1`) could not be translated to an address in the image.
+    UnmappedAddress(u16, u32),
+
     /// A parse error from scroll.
     ScrollError(scroll::Error),
 
@@ -111,12 +130,19 @@ pub enum Error {
 
     /// An unknown register index was encountered.
     UnknownRegister(u16),
+
+    /// An unknown `CV_CPU_TYPE_e` value was encountered.
+    UnknownCpuType(u16),
+
+    /// An unknown `CV_CFL_LANG` value was encountered.
+    UnknownSourceLanguage(u8),
 }
 
 impl std::error::Error for Error {
     fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
         match self {
             Self::IoError(error) => Some(error),
+            Self::ScrollError(error) => Some(error),
             _ => None,
         }
     }
@@ -128,6 +154,9 @@ impl fmt::Display for Error {
             Self::PageReferenceOutOfRange(p) => {
                 write!(f, "MSF referred to page number ({p}) out of range")
             }
+            Self::UnsupportedCompression => {
+                write!(f, "The data is compressed in an unsupported format")
+            }
             Self::InvalidPageSize(n) => write!(
                 f,
                 "The MSF header specifies an invalid page size ({n} bytes)"
@@ -147,6 +176,12 @@ impl fmt::Display for Error {
                 f,
                 "Support for symbols of kind {kind:#06x} is not implemented"
             ),
+            Self::SymbolCycle(index) => {
+                write!(
+                    f,
+                    "Symbol {index:#x} was reached twice while navigating symbols"
+                )
+            }
             Self::InvalidTypeInformationHeader(reason) => {
                 write!(f, "The type information header was invalid: {reason}")
             }
@@ -166,10 +201,20 @@ impl fmt::Display for Error {
                 f,
                 "Cross module reference {index:#06x} not found in imports"
             ),
+            Self::NotAFunctionType(index) => {
+                write!(f, "Type {index:#06x} is not a function type")
+            }
+            Self::NotAFieldList(index) => {
+                write!(f, "Type {index:#06x} is not a field list")
+            }
             Self::UnexpectedNumericPrefix(prefix) => write!(
                 f,
                 "Variable-length numeric parsing encountered an unexpected prefix ({prefix:#06x}"
             ),
+            Self::UnmappedAddress(section, offset) => write!(
+                f,
+                "Address {section:#06x}:{offset:#010x} is not mapped into the image"
+            ),
             Self::UnimplementedDebugSubsection(kind) => write!(
                 f,
                 "Debug module subsection of kind {kind:#06x} is not implemented"
@@ -181,6 +226,9 @@ impl fmt::Display for Error {
                 write!(f, "Invalid source file checksum offset {offset:#x}")
             }
             Self::UnknownBinaryAnnotation(num) => write!(f, "Unknown binary annotation {num}"),
+            Self::UnknownCpuType(value) => write!(f, "Unknown CPU type {value:#06x}"),
+            Self::UnknownSourceLanguage(value) => write!(f, "Unknown source language {value:#04x}"),
+            Self::ScrollError(ref e) => write!(f, "Error while parsing PDB data: {e}"),
             _ => fmt::Debug::fmt(self, f),
         }
     }
@@ -330,6 +378,24 @@ macro_rules! impl_va {
             pub fn wrapping_sub(self, other: Self) -> u32 {
                 self.0.wrapping_sub(other.0)
             }
+
+            /// Returns the range of `len` bytes starting at this address.
+            ///
+            /// The end of the range is clipped at the numeric bounds. Use `Range::contains` to check
+            /// whether an address lies within the range.
+            #[must_use]
+            pub fn range(self, len: u32) -> Range<Self> {
+                self..self.saturating_add(len)
+            }
+
+            /// Returns `true` if the two ranges share at least one address.
+            ///
+            /// Ranges are half-open, so ranges that merely touch do not overlap. Empty ranges never
+            /// overlap with any other range.
+            #[must_use]
+            pub fn ranges_overlap(a: &Range<Self>, b: &Range<Self>) -> bool {
+                a.start < b.end && b.start < a.end && !a.is_empty() && !b.is_empty()
+            }
         }
 
         impl Add<u32> for $type {
@@ -508,6 +574,7 @@ impl_section_offset!(SectionOffset);
 /// For binaries and their PDBs that have not been optimized, both address spaces are equal and the
 /// offsets are interchangeable. The conversion operations are cheap no-ops in this case.
 #[derive(Clone, Copy, Default, Eq, Hash, PartialEq)]
+#[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
 pub struct PdbInternalSectionOffset {
     /// The memory offset relative from the start of the section's memory.
     pub offset: u32,
@@ -604,6 +671,7 @@ pub trait ItemIndex:
 /// If this index is a [cross module reference](ItemIndex::is_cross_module), it must be resolved
 /// before lookup in the stream.
 #[derive(Clone, Copy, Default, Eq, Hash, Ord, PartialEq, PartialOrd)]
+#[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
 pub struct TypeIndex(pub u32);
 
 impl_convert!(TypeIndex, u32);
@@ -613,18 +681,83 @@ impl_pread!(TypeIndex);
 impl ItemIndex for TypeIndex {}
 
 /// COM+ metadata token for managed procedures (`CV_tkn_t`).
+///
+/// A token refers to a row in one of the .NET metadata tables. The high byte identifies the
+/// [table](Self::table), and the low 24 bits the 1-based [row](Self::rid) within that table.
 #[derive(Clone, Copy, Default, Eq, Hash, Ord, PartialEq, PartialOrd)]
+#[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
 pub struct COMToken(pub u32);
 
 impl_convert!(COMToken, u32);
 impl_hex_fmt!(COMToken);
 impl_pread!(COMToken);
 
+impl COMToken {
+    /// Returns the metadata table this token refers to.
+    #[must_use]
+    pub fn table(self) -> MetadataTable {
+        MetadataTable::from((self.0 >> 24) as u8)
+    }
+
+    /// Returns the 1-based row index (RID) of this token within its table.
+    #[must_use]
+    pub fn rid(self) -> u32 {
+        self.0 & 0x00ff_ffff
+    }
+}
+
+/// A .NET metadata table referenced by a [`COMToken`], as defined in ECMA-335, partition II.
+#[non_exhaustive]
+#[derive(Clone, Copy, Debug, Eq, Hash, Ord, PartialEq, PartialOrd)]
+pub enum MetadataTable {
+    /// The `Module` table.
+    Module,
+    /// The `TypeRef` table, for types defined in other modules.
+    TypeRef,
+    /// The `TypeDef` table, for types defined in this module.
+    TypeDef,
+    /// The `Field` table.
+    Field,
+    /// The `MethodDef` table, for methods defined in this module.
+    MethodDef,
+    /// The `Param` table.
+    Param,
+    /// The `MemberRef` table, for members of types defined in other modules.
+    MemberRef,
+    /// The `StandAloneSig` table, for local variable and call site signatures.
+    StandAloneSig,
+    /// The `TypeSpec` table, for constructed types such as generic instantiations.
+    TypeSpec,
+    /// The `MethodSpec` table, for generic method instantiations.
+    MethodSpec,
+    /// Any other table, by its table number.
+    Other(u8),
+}
+
+impl From<u8> for MetadataTable {
+    fn from(value: u8) -> Self {
+        match value {
+            0x00 => Self::Module,
+            0x01 => Self::TypeRef,
+            0x02 => Self::TypeDef,
+            0x04 => Self::Field,
+            0x06 => Self::MethodDef,
+            0x08 => Self::Param,
+            0x0a => Self::MemberRef,
+            0x11 => Self::StandAloneSig,
+            0x1b => Self::TypeSpec,
+            0x2b => Self::MethodSpec,
+            other => Self::Other(other),
+        }
+    }
+}
+
 /// Index of an [`Id`](crate::Id) in [`IdInformation`](crate::IdInformation) stream.
 ///
 /// If this index is a [cross module reference](ItemIndex::is_cross_module), it must be resolved
 /// before lookup in the stream.
 #[derive(Clone, Copy, Default, Eq, Hash, Ord, PartialEq, PartialOrd)]
+#[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
 pub struct IdIndex(pub u32);
 
 impl_convert!(IdIndex, u32);
@@ -682,6 +815,7 @@ impl_pread!(FileIndex);
 /// [`ModuleInfo::symbols_at`](crate::ModuleInfo::symbols_at). When iterating, use
 /// [`SymbolIter::seek`](crate::SymbolIter::seek) to jump between symbols.
 #[derive(Clone, Copy, Eq, Hash, Ord, PartialEq, PartialOrd)]
+#[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
 pub struct SymbolIndex(pub u32);
 
 impl_convert!(SymbolIndex, u32);
@@ -690,11 +824,74 @@ impl_pread!(SymbolIndex);
 
 /// A register referred to by its number.
 #[derive(Clone, Copy, Debug, Default, Eq, Hash, Ord, PartialEq, PartialOrd)]
+#[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
 pub struct Register(pub u16);
 
 impl_convert!(Register, u16);
 impl_pread!(Register);
 
+impl Register {
+    /// The x86 `eax` register.
+    pub const X86_EAX: Self = Self(17);
+    /// The x86 `ecx` register.
+    pub const X86_ECX: Self = Self(18);
+    /// The x86 `edx` register.
+    pub const X86_EDX: Self = Self(19);
+    /// The x86 `ebx` register.
+    pub const X86_EBX: Self = Self(20);
+    /// The x86 stack pointer `esp`.
+    pub const X86_ESP: Self = Self(21);
+    /// The x86 frame pointer `ebp`.
+    pub const X86_EBP: Self = Self(22);
+    /// The x86 `esi` register.
+    pub const X86_ESI: Self = Self(23);
+    /// The x86 `edi` register.
+    pub const X86_EDI: Self = Self(24);
+    /// The x86 instruction pointer `eip`.
+    pub const X86_EIP: Self = Self(33);
+
+    /// The x64 `rax` register.
+    pub const X64_RAX: Self = Self(328);
+    /// The x64 `rbx` register.
+    pub const X64_RBX: Self = Self(329);
+    /// The x64 `rcx` register.
+    pub const X64_RCX: Self = Self(330);
+    /// The x64 `rdx` register.
+    pub const X64_RDX: Self = Self(331);
+    /// The x64 `rsi` register.
+    pub const X64_RSI: Self = Self(332);
+    /// The x64 `rdi` register.
+    pub const X64_RDI: Self = Self(333);
+    /// The x64 frame pointer `rbp`.
+    pub const X64_RBP: Self = Self(334);
+    /// The x64 stack pointer `rsp`.
+    pub const X64_RSP: Self = Self(335);
+    /// The x64 `r13` register, used as alternate frame pointer.
+    pub const X64_R13: Self = Self(341);
+    /// The x64 instruction pointer `rip`.
+    pub const X64_RIP: Self = Self(33);
+
+    /// The ARM `r7` register, used as frame pointer in Thumb code.
+    pub const ARM_R7: Self = Self(17);
+    /// The ARM `r11` register, used as frame pointer in ARM code.
+    pub const ARM_R11: Self = Self(21);
+    /// The ARM stack pointer `sp`.
+    pub const ARM_SP: Self = Self(23);
+    /// The ARM link register `lr`.
+    pub const ARM_LR: Self = Self(24);
+    /// The ARM program counter `pc`.
+    pub const ARM_PC: Self = Self(25);
+
+    /// The ARM64 `x19` register, used as alternate frame pointer.
+    pub const ARM64_X19: Self = Self(69);
+    /// The ARM64 frame pointer `fp`.
+    pub const ARM64_FP: Self = Self(79);
+    /// The ARM64 link register `lr`.
+    pub const ARM64_LR: Self = Self(80);
+    /// The ARM64 stack pointer `sp`.
+    pub const ARM64_SP: Self = Self(81);
+}
+
 /// Provides little-endian access to a &[u8].
 #[derive(Debug, Default, Clone)]
 pub(crate) struct ParseBuffer<'b>(&'b [u8], usize);
@@ -853,7 +1050,8 @@ impl fmt::LowerHex for ParseBuffer<'_> {
 }
 
 /// Value of an enumerate type.
-#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
 #[allow(missing_docs)]
 pub enum Variant {
     U8(u8),
@@ -864,19 +1062,98 @@ pub enum Variant {
     I16(i16),
     I32(i32),
     I64(i64),
+    U128(u128),
+    I128(i128),
 }
 
+impl Variant {
+    /// Returns the value as `u64` if it is non-negative and fits.
+    #[must_use]
+    pub fn as_u64(&self) -> Option<u64> {
+        match *self {
+            Self::U8(value) => Some(value.into()),
+            Self::U16(value) => Some(value.into()),
+            Self::U32(value) => Some(value.into()),
+            Self::U64(value) => Some(value),
+            Self::I8(value) => u64::try_from(value).ok(),
+            Self::I16(value) => u64::try_from(value).ok(),
+            Self::I32(value) => u64::try_from(value).ok(),
+            Self::I64(value) => u64::try_from(value).ok(),
+            Self::U128(value) => u64::try_from(value).ok(),
+            Self::I128(value) => u64::try_from(value).ok(),
+        }
+    }
+
+    /// Returns the value as `i64` if it fits.
+    #[must_use]
+    pub fn as_i64(&self) -> Option<i64> {
+        match *self {
+            Self::U8(value) => Some(value.into()),
+            Self::U16(value) => Some(value.into()),
+            Self::U32(value) => Some(value.into()),
+            Self::U64(value) => i64::try_from(value).ok(),
+            Self::I8(value) => Some(value.into()),
+            Self::I16(value) => Some(value.into()),
+            Self::I32(value) => Some(value.into()),
+            Self::I64(value) => Some(value),
+            Self::U128(value) => i64::try_from(value).ok(),
+            Self::I128(value) => i64::try_from(value).ok(),
+        }
+    }
+
+    /// Returns the value as `f64` if it can be represented exactly.
+    ///
+    /// Floating point leaves (`LF_REAL32` and friends) are not parsed into a `Variant`, so this
+    /// only converts integers whose magnitude does not exceed 2<sup>53</sup>.
+    #[must_use]
+    pub fn as_f64(&self) -> Option<f64> {
+        const MAX_EXACT: i64 = 1 << f64::MANTISSA_DIGITS;
+
+        let value = self.as_i64()?;
+        if (-MAX_EXACT..=MAX_EXACT).contains(&value) {
+            Some(value as f64)
+        } else {
+            None
+        }
+    }
+
+    /// Returns the value as `bool` if it is `0` or `1`.
+    #[must_use]
+    pub fn as_bool(&self) -> Option<bool> {
+        match self.as_u64()? {
+            0 => Some(false),
+            1 => Some(true),
+            _ => None,
+        }
+    }
+}
+
+/// Formats the value in decimal, or in hexadecimal with the alternate flag (`{:#}`).
+///
+/// Hexadecimal output of negative values shows their two's complement in the width of the type.
 impl fmt::Display for Variant {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        macro_rules! display {
+            ($value:expr) => {
+                if f.alternate() {
+                    write!(f, "{:#x}", $value)
+                } else {
+                    write!(f, "{}", $value)
+                }
+            };
+        }
+
         match self {
-            Self::U8(value) => write!(f, "{value}"),
-            Self::U16(value) => write!(f, "{value}"),
-            Self::U32(value) => write!(f, "{value}"),
-            Self::U64(value) => write!(f, "{value}"),
-            Self::I8(value) => write!(f, "{value}"),
-            Self::I16(value) => write!(f, "{value}"),
-            Self::I32(value) => write!(f, "{value}"),
-            Self::I64(value) => write!(f, "{value}"),
+            Self::U8(value) => display!(value),
+            Self::U16(value) => display!(value),
+            Self::U32(value) => display!(value),
+            Self::U64(value) => display!(value),
+            Self::I8(value) => display!(value),
+            Self::I16(value) => display!(value),
+            Self::I32(value) => display!(value),
+            Self::I64(value) => display!(value),
+            Self::U128(value) => display!(value),
+            Self::I128(value) => display!(value),
         }
     }
 }
@@ -896,7 +1173,8 @@ impl<'a> TryFromCtx<'a, Endian> for Variant {
             constants::LF_USHORT => Self::U16(this.gread_with(&mut offset, le)?),
             constants::LF_ULONG => Self::U32(this.gread_with(&mut offset, le)?),
             constants::LF_UQUADWORD => Self::U64(this.gread_with(&mut offset, le)?),
-            _ if cfg!(debug_assertions) => unreachable!(),
+            constants::LF_OCTWORD => Self::I128(this.gread_with(&mut offset, le)?),
+            constants::LF_UOCTWORD => Self::U128(this.gread_with(&mut offset, le)?),
             other => return Err(Error::UnexpectedNumericPrefix(other)),
         };
 
@@ -997,8 +1275,47 @@ pub(crate) fn cast_aligned<T>(data: &[u8]) -> Option<&[T]> {
     }
 }
 
+/// Computes the 64-bit FNV-1a hash over a sequence of byte slices.
+///
+/// Unlike `std::collections::hash_map::DefaultHasher`, the result is specified and therefore stable
+/// across Rust versions and platforms, which makes it suitable for persisted cache keys.
+pub(crate) fn fnv1a_64<'a>(chunks: impl IntoIterator<Item = &'a [u8]>) -> u64 {
+    const OFFSET_BASIS: u64 = 0xcbf2_9ce4_8422_2325;
+    const PRIME: u64 = 0x0000_0100_0000_01b3;
+
+    let mut hash = OFFSET_BASIS;
+    for byte in chunks.into_iter().flatten() {
+        hash ^= u64::from(*byte);
+        hash = hash.wrapping_mul(PRIME);
+    }
+    hash
+}
+
 #[cfg(test)]
 mod tests {
+    mod error {
+        use std::error::Error as _;
+
+        use crate::common::*;
+
+        #[test]
+        fn test_source() {
+            let error = Error::from(scroll::Error::BadInput {
+                size: 4,
+                msg: "invalid",
+            });
+            assert!(matches!(error, Error::ScrollError(_)));
+            let source = error.source().expect("source");
+            assert!(source.downcast_ref::<scroll::Error>().is_some());
+
+            let error = Error::from(io::Error::other("read failed"));
+            let source = error.source().expect("source");
+            assert!(source.downcast_ref::<io::Error>().is_some());
+
+            assert!(Error::UnexpectedEof.source().is_none());
+        }
+    }
+
     mod parse_buffer {
         use crate::common::*;
 
@@ -1261,6 +1578,54 @@ mod tests {
             assert_eq!(val, SymbolIndex(0x42));
             assert!(buf.is_empty());
         }
+
+        #[test]
+        fn test_com_token() {
+            let token = COMToken(0x0600_0012);
+            assert_eq!(token.table(), MetadataTable::MethodDef);
+            assert_eq!(token.rid(), 0x12);
+
+            assert_eq!(COMToken(0x0200_0003).table(), MetadataTable::TypeDef);
+            assert_eq!(COMToken(0x7000_0001).table(), MetadataTable::Other(0x70));
+        }
+    }
+
+    mod va {
+        use crate::common::*;
+
+        #[test]
+        fn test_add() {
+            assert_eq!(Rva(0x1000) + 0x10, Rva(0x1010));
+            assert_eq!(Rva(0x1000).checked_add(0x10), Some(Rva(0x1010)));
+            assert_eq!(Rva(u32::MAX).checked_add(1), None);
+            assert_eq!(Rva(u32::MAX - 1).saturating_add(4), Rva(u32::MAX));
+        }
+
+        #[test]
+        fn test_sub() {
+            assert_eq!(Rva(0x1010) - Rva(0x1000), 0x10);
+            assert_eq!(Rva(0x1000).checked_sub(Rva(0x1010)), None);
+            assert_eq!(Rva(0x1000).saturating_sub(Rva(0x1010)), 0);
+        }
+
+        #[test]
+        fn test_range() {
+            let range = Rva(0x1000).range(0x10);
+            assert_eq!(range, Rva(0x1000)..Rva(0x1010));
+            assert!(range.contains(&Rva(0x100f)));
+            assert!(!range.contains(&Rva(0x1010)));
+            assert_eq!(Rva(u32::MAX - 1).range(4).end, Rva(u32::MAX));
+        }
+
+        #[test]
+        fn test_ranges_overlap() {
+            let a = Rva(0x1000)..Rva(0x1010);
+            assert!(Rva::ranges_overlap(&a, &(Rva(0x100f)..Rva(0x1020))));
+            assert!(Rva::ranges_overlap(&a, &(Rva(0x1004)..Rva(0x1008))));
+            assert!(!Rva::ranges_overlap(&a, &(Rva(0x1010)..Rva(0x1020))));
+            assert!(!Rva::ranges_overlap(&a, &(Rva(0x0ff0)..Rva(0x1000))));
+            assert!(!Rva::ranges_overlap(&a, &(Rva(0x1008)..Rva(0x1008))));
+        }
     }
 
     mod cast_aligned {
@@ -1307,4 +1672,97 @@ mod tests {
             assert_eq!(cast_aligned::<u32>(bin), None);
         }
     }
+
+    mod variant {
+        use crate::common::*;
+
+        #[test]
+        fn test_display() {
+            let cases = [
+                (Variant::U8(200), "200", "0xc8"),
+                (Variant::U16(0x1234), "4660", "0x1234"),
+                (Variant::U32(7), "7", "0x7"),
+                (
+                    Variant::U64(u64::MAX),
+                    "18446744073709551615",
+                    "0xffffffffffffffff",
+                ),
+                (Variant::I8(-1), "-1", "0xff"),
+                (Variant::I16(-2), "-2", "0xfffe"),
+                (Variant::I32(42), "42", "0x2a"),
+                (Variant::I64(-16), "-16", "0xfffffffffffffff0"),
+                (
+                    Variant::U128(1 << 64),
+                    "18446744073709551616",
+                    "0x10000000000000000",
+                ),
+                (
+                    Variant::I128(-1),
+                    "-1",
+                    "0xffffffffffffffffffffffffffffffff",
+                ),
+            ];
+
+            for (variant, decimal, hex) in cases.iter() {
+                assert_eq!(variant.to_string(), *decimal);
+                assert_eq!(format!("{:#}", variant), *hex);
+            }
+        }
+
+        #[test]
+        fn test_getters() {
+            assert_eq!(Variant::U8(1).as_u64(), Some(1));
+            assert_eq!(Variant::I32(-1).as_u64(), None);
+            assert_eq!(Variant::U128(u128::MAX).as_u64(), None);
+            assert_eq!(Variant::I128(-5).as_i64(), Some(-5));
+            assert_eq!(Variant::U64(u64::MAX).as_i64(), None);
+            assert_eq!(Variant::I16(i16::MIN).as_i64(), Some(-32768));
+
+            assert_eq!(Variant::I64(-3).as_f64(), Some(-3.0));
+            assert_eq!(
+                Variant::U64(1 << 53).as_f64(),
+                Some(9_007_199_254_740_992.0)
+            );
+            assert_eq!(Variant::U64((1 << 53) + 1).as_f64(), None);
+
+            assert_eq!(Variant::U16(0).as_bool(), Some(false));
+            assert_eq!(Variant::I8(1).as_bool(), Some(true));
+            assert_eq!(Variant::U32(2).as_bool(), None);
+            assert_eq!(Variant::I32(-1).as_bool(), None);
+        }
+    }
+
+    mod register {
+        use crate::common::*;
+        use crate::register::{AMD64Register, ARM64Register, ARMRegister, X86Register};
+
+        #[test]
+        fn test_constants() {
+            let x86 = [
+                (Register::X86_EAX, X86Register::EAX),
+                (Register::X86_ESP, X86Register::ESP),
+                (Register::X86_EBP, X86Register::EBP),
+                (Register::X86_EIP, X86Register::EIP),
+            ];
+            for (register, expected) in x86.iter() {
+                assert_eq!(register.0, *expected as u16);
+            }
+
+            let x64 = [
+                (Register::X64_RAX, AMD64Register::RAX),
+                (Register::X64_RBP, AMD64Register::RBP),
+                (Register::X64_RSP, AMD64Register::RSP),
+                (Register::X64_RIP, AMD64Register::RIP),
+            ];
+            for (register, expected) in x64.iter() {
+                assert_eq!(register.0, *expected as u16);
+            }
+
+            assert_eq!(Register::ARM_R7.0, ARMRegister::R7 as u16);
+            assert_eq!(Register::ARM_R11.0, ARMRegister::R11 as u16);
+            assert_eq!(Register::ARM_SP.0, ARMRegister::SP as u16);
+            assert_eq!(Register::ARM64_FP.0, ARM64Register::FP as u16);
+            assert_eq!(Register::ARM64_SP.0, ARM64Register::SP as u16);
+        }
+    }
 }
@@ -111,6 +111,26 @@ impl<'s> DebugInformation<'s> {
         })
     }
 
+    /// Returns the number of modules in the DBI stream.
+    ///
+    /// This reads the module count recorded at the start of the file info substream, which avoids
+    /// parsing every entry of the modules list. The count is stored as a 16-bit number, so PDBs with
+    /// 65536 or more modules will report a truncated value; iterate [`modules`](Self::modules) if
+    /// an exact count is required for such files.
+    pub fn module_count(&self) -> Result<usize> {
+        let mut buf = self.stream.parse_buffer();
+        // drop the header, modules list, section contributions list, and section map
+        let offset = self.header_len
+            + self.header.module_list_size as usize
+            + self.header.section_contribution_size as usize
+            + self.header.section_map_size as usize;
+
+        buf.take(offset)?;
+        let mut file_info_buf: ParseBuffer<'_> =
+            buf.take(self.header.file_info_size as usize)?.into();
+        Ok(file_info_buf.parse_u16()? as usize)
+    }
+
     /// Returns an iterator that can traverse the section contributions list in sequential order.
     pub fn section_contributions(&self) -> Result<DBISectionContributionIter<'_>> {
         let mut buf = self.stream.parse_buffer();
@@ -531,7 +551,10 @@ impl<'m> Module<'m> {
 }
 
 /// A `ModuleIter` iterates over the modules in the DBI section, producing `Module`s.
-#[derive(Debug)]
+///
+/// The iterator can be cloned to save its position, which allows resuming iteration later or
+/// splitting the modules list into chunks with `skip` and `take`.
+#[derive(Clone, Debug)]
 pub struct ModuleIter<'m> {
     buf: ParseBuffer<'m>,
 }
@@ -771,7 +794,7 @@ impl DBIExtraStreams {
     pub(crate) fn parse(buf: &mut ParseBuffer<'_>) -> Result<Self> {
         // short reads are okay, as are long reads -- this struct is actually an array
         // what's _not_ okay are
-        if buf.len() % 2 != 0 {
+        if !buf.len().is_multiple_of(2) {
             return Err(Error::InvalidStreamLength("DbgDataHdr"));
         }
 
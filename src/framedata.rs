@@ -9,9 +9,13 @@
 
 use std::cmp::Ordering;
 use std::fmt;
+use std::mem;
+use std::ptr;
 
 use crate::common::*;
+use crate::modi::{DebugSubsectionIter, DebugSubsectionKind};
 use crate::msf::Stream;
+use crate::strings::StringTable;
 use crate::FallibleIterator;
 
 /// A compiler specific frame type.
@@ -143,6 +147,13 @@ impl NewFrameData {
     fn flags(&self) -> u32 {
         u32::from_le(self.flags)
     }
+
+    /// Reads a record from a buffer without requiring alignment.
+    fn read(buf: &mut ParseBuffer<'_>) -> Result<Self> {
+        let bytes = buf.take(mem::size_of::<Self>())?;
+        // The record consists of plain integers, so any bit pattern is valid.
+        Ok(unsafe { ptr::read_unaligned(bytes.as_ptr().cast::<Self>()) })
+    }
 }
 
 impl fmt::Debug for NewFrameData {
@@ -358,6 +369,70 @@ impl From<&'_ NewFrameData> for FrameData {
     }
 }
 
+impl FrameData {
+    /// Resolves the [`program`](Self::program) string against the PDB's string table.
+    ///
+    /// Returns `None` for frame data in the old FPO format, which has no program string.
+    ///
+    /// # Errors
+    ///
+    /// * `Error::UnexpectedEof` if the program string reference is out of bounds
+    pub fn program_string<'t>(
+        &self,
+        strings: &'t StringTable<'_>,
+    ) -> Result<Option<RawString<'t>>> {
+        self.program.map(|program| strings.get(program)).transpose()
+    }
+}
+
+/// Iterator over the frame data in the `DEBUG_S_FRAMEDATA` subsections of a module.
+///
+/// Created by [`ModuleInfo::frame_data`](crate::ModuleInfo::frame_data). Entries are returned in
+/// the order they are stored, which is not necessarily sorted by address.
+#[derive(Clone, Debug, Default)]
+pub struct ModuleFrameDataIter<'a> {
+    subsections: DebugSubsectionIter<'a>,
+    frames: ParseBuffer<'a>,
+}
+
+impl<'a> ModuleFrameDataIter<'a> {
+    pub(crate) fn new(subsections: DebugSubsectionIter<'a>) -> Self {
+        Self {
+            subsections,
+            frames: ParseBuffer::default(),
+        }
+    }
+}
+
+impl FallibleIterator for ModuleFrameDataIter<'_> {
+    type Item = FrameData;
+    type Error = Error;
+
+    fn next(&mut self) -> Result<Option<Self::Item>> {
+        while self.frames.is_empty() {
+            let data = match self.subsections.next()? {
+                Some((DebugSubsectionKind::FrameData, data)) => data,
+                Some(_) => continue,
+                None => return Ok(None),
+            };
+
+            // The records may be preceded by the address of the relocation applied to them.
+            let record_size = mem::size_of::<NewFrameData>();
+            let mut frames = ParseBuffer::from(data);
+            if data.len() % record_size == mem::size_of::<u32>() {
+                frames.parse_u32()?;
+            }
+            if frames.len() % record_size != 0 {
+                return Err(Error::InvalidStreamLength("DEBUG_S_FRAMEDATA"));
+            }
+
+            self.frames = frames;
+        }
+
+        Ok(Some((&NewFrameData::read(&mut self.frames)?).into()))
+    }
+}
+
 /// Iterator over entries in a [`FrameTable`].
 #[derive(Debug, Default)]
 pub struct FrameDataIter<'t> {
@@ -599,4 +674,61 @@ mod tests {
         assert_eq!(mem::size_of::<OldFrameData>(), 16);
         assert_eq!(mem::align_of::<OldFrameData>(), 4);
     }
+
+    fn frame_data_subsection(reloc_ptr: bool) -> Vec<u8> {
+        let mut payload = Vec::new();
+        if reloc_ptr {
+            payload.extend_from_slice(&0x1000u32.to_le_bytes());
+        }
+        for value in [0x1010u32, 0x20, 8, 4, 0x30, 0x7] {
+            payload.extend_from_slice(&value.to_le_bytes());
+        }
+        payload.extend_from_slice(&3u16.to_le_bytes()); // prolog size
+        payload.extend_from_slice(&12u16.to_le_bytes()); // saved regs size
+        payload.extend_from_slice(&0b101u32.to_le_bytes()); // has seh, is function start
+
+        let mut data = 0xf5u32.to_le_bytes().to_vec();
+        data.extend_from_slice(&(payload.len() as u32).to_le_bytes());
+        data.extend(payload);
+        data
+    }
+
+    #[test]
+    fn test_module_frame_data() {
+        // a lines subsection that must be skipped, followed by frame data with and without the
+        // leading relocation pointer
+        let mut data = vec![0xf2, 0, 0, 0, 4, 0, 0, 0, 0, 0, 0, 0];
+        data.extend(frame_data_subsection(true));
+        data.extend(frame_data_subsection(false));
+
+        let frames: Vec<_> = ModuleFrameDataIter::new(DebugSubsectionIter::new(&data))
+            .collect()
+            .expect("frame data");
+        assert_eq!(frames.len(), 2);
+
+        for frame in frames {
+            assert_eq!(frame.ty, FrameType::FrameData);
+            assert_eq!(frame.code_start, PdbInternalRva(0x1010));
+            assert_eq!(frame.code_size, 0x20);
+            assert_eq!(frame.locals_size, 8);
+            assert_eq!(frame.params_size, 4);
+            assert_eq!(frame.max_stack_size, Some(0x30));
+            assert_eq!(frame.program, Some(StringRef(7)));
+            assert_eq!(frame.prolog_size, 3);
+            assert_eq!(frame.saved_regs_size, 12);
+            assert!(frame.has_structured_eh);
+            assert!(!frame.has_cpp_eh);
+            assert!(frame.is_function_start);
+        }
+    }
+
+    #[test]
+    fn test_module_frame_data_invalid_length() {
+        let data = [0xf5, 0, 0, 0, 6, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0];
+        let result = ModuleFrameDataIter::new(DebugSubsectionIter::new(&data)).next();
+        assert!(matches!(
+            result,
+            Err(Error::InvalidStreamLength("DEBUG_S_FRAMEDATA"))
+        ));
+    }
 }
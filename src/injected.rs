@@ -0,0 +1,236 @@
+// Copyright 2017 pdb Developers
+//
+// Licensed under the Apache License, Version 2.0, <LICENSE-APACHE or
+// http://apache.org/licenses/LICENSE-2.0> or the MIT license <LICENSE-MIT or
+// http://opensource.org/licenses/MIT>, at your option. This file may not be
+// copied, modified, or distributed except according to those terms.
+
+//! Facilities for reading source files injected into the PDB.
+//!
+//! Injected sources are listed in the `/src/headerblock` stream, which is laid out as
+//! [documented by LLVM](https://llvm.org/docs/PDB/PdbStream.html). The contents of every file are
+//! stored in a separate named stream `/src/files/<virtual path>`.
+
+use std::borrow::Cow;
+use std::mem;
+
+use crate::common::*;
+use crate::msf::Stream;
+
+/// Size of the header at the start of the `/src/headerblock` stream.
+const HEADER_BLOCK_HEADER_SIZE: usize = 64;
+
+/// Size of a single entry in the `/src/headerblock` hash table.
+const HEADER_BLOCK_ENTRY_SIZE: usize = 40;
+
+/// Compression applied to an injected source file.
+#[non_exhaustive]
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+pub enum SourceCompression {
+    /// The contents are stored uncompressed.
+    None,
+    /// Run-length encoding.
+    RunLengthEncoded,
+    /// Huffman encoding.
+    Huffman,
+    /// LZ compression.
+    LZ,
+    /// Compression used by the .NET toolchain.
+    DotNet,
+    /// An unknown compression scheme.
+    Unknown(u8),
+}
+
+impl From<u8> for SourceCompression {
+    fn from(value: u8) -> Self {
+        match value {
+            0 => Self::None,
+            1 => Self::RunLengthEncoded,
+            2 => Self::Huffman,
+            3 => Self::LZ,
+            101 => Self::DotNet,
+            _ => Self::Unknown(value),
+        }
+    }
+}
+
+/// An entry of the `/src/headerblock` stream, with names as references into the string table.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub(crate) struct SourceHeaderEntry {
+    pub crc: u32,
+    pub file_size: u32,
+    pub file_name: StringRef,
+    pub object_name: StringRef,
+    pub virtual_name: StringRef,
+    pub compression: SourceCompression,
+    pub is_virtual: bool,
+}
+
+/// Parses the entries of the `/src/headerblock` stream.
+///
+/// The header is followed by a hash table in the same format as the named stream map, see
+/// [`PDBInformation::stream_names`](crate::PDBInformation::stream_names). Its values are the
+/// fixed-size entries describing each file.
+pub(crate) fn parse_header_block(data: &[u8]) -> Result<Vec<SourceHeaderEntry>> {
+    let mut buf = ParseBuffer::from(data);
+    if buf.is_empty() {
+        return Ok(Vec::new());
+    }
+
+    buf.take(HEADER_BLOCK_HEADER_SIZE)?;
+    let count = buf.parse_u32()?;
+    let _capacity = buf.parse_u32()?;
+    let present_words = buf.parse_u32()?;
+    buf.take(present_words as usize * mem::size_of::<u32>())?;
+    let deleted_words = buf.parse_u32()?;
+    buf.take(deleted_words as usize * mem::size_of::<u32>())?;
+
+    let mut entries = Vec::with_capacity(count.min(1024) as usize);
+    for _ in 0..count {
+        let _key = buf.parse_u32()?;
+        let mut entry = ParseBuffer::from(buf.take(HEADER_BLOCK_ENTRY_SIZE)?);
+        let _size = entry.parse_u32()?;
+        let _version = entry.parse_u32()?;
+        entries.push(SourceHeaderEntry {
+            crc: entry.parse_u32()?,
+            file_size: entry.parse_u32()?,
+            file_name: StringRef(entry.parse_u32()?),
+            object_name: StringRef(entry.parse_u32()?),
+            virtual_name: StringRef(entry.parse_u32()?),
+            compression: SourceCompression::from(entry.parse_u8()?),
+            is_virtual: entry.parse_u8()? != 0,
+        });
+    }
+
+    Ok(entries)
+}
+
+/// A source file injected into the PDB.
+///
+/// Obtained via [`PDB::injected_sources`](crate::PDB::injected_sources).
+#[derive(Debug)]
+pub struct InjectedSource<'s> {
+    /// The virtual path under which the file is stored in the PDB.
+    pub virtual_path: String,
+    /// The original path of the file.
+    pub file_name: String,
+    /// The object file that the source belongs to.
+    pub object_name: String,
+    /// The compression applied to the stored contents.
+    pub compression: SourceCompression,
+    /// CRC of the original file contents.
+    pub crc: u32,
+    /// Size of the original file in bytes.
+    pub file_size: u32,
+    /// Whether this is a virtual file that was never stored on disk.
+    pub is_virtual: bool,
+    /// The raw, possibly compressed contents.
+    pub data: Stream<'s>,
+}
+
+impl InjectedSource<'_> {
+    /// Returns the decompressed contents of the file.
+    ///
+    /// # Errors
+    ///
+    /// * `Error::UnsupportedCompression` if the contents are compressed. None of the compression
+    ///   schemes used by injected sources are supported at this time.
+    pub fn contents(&self) -> Result<Cow<'_, [u8]>> {
+        match self.compression {
+            SourceCompression::None => Ok(Cow::Borrowed(self.data.as_slice())),
+            _ => Err(Error::UnsupportedCompression),
+        }
+    }
+}
+
+/// An iterator over [`InjectedSource`]s.
+///
+/// Obtained via [`PDB::injected_sources`](crate::PDB::injected_sources).
+#[derive(Debug)]
+pub struct InjectedSourceIter<'s> {
+    sources: std::vec::IntoIter<InjectedSource<'s>>,
+}
+
+impl<'s> InjectedSourceIter<'s> {
+    pub(crate) fn new(sources: Vec<InjectedSource<'s>>) -> Self {
+        InjectedSourceIter {
+            sources: sources.into_iter(),
+        }
+    }
+}
+
+impl<'s> Iterator for InjectedSourceIter<'s> {
+    type Item = InjectedSource<'s>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.sources.next()
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.sources.size_hint()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn entry(crc: u32, names: [u32; 3], compression: u8) -> Vec<u8> {
+        let mut data = Vec::new();
+        for value in [HEADER_BLOCK_ENTRY_SIZE as u32, 19_980_827, crc, 42] {
+            data.extend_from_slice(&value.to_le_bytes());
+        }
+        for name in &names {
+            data.extend_from_slice(&name.to_le_bytes());
+        }
+        data.extend_from_slice(&[compression, 1, 0, 0]);
+        data.extend_from_slice(&[0; 8]);
+        data
+    }
+
+    #[test]
+    fn test_parse_header_block() {
+        let mut data = vec![0; HEADER_BLOCK_HEADER_SIZE];
+        for value in [2u32, 4, 1, 0b11, 0] {
+            data.extend_from_slice(&value.to_le_bytes());
+        }
+        data.extend_from_slice(&10u32.to_le_bytes());
+        data.extend(entry(0xdead, [1, 2, 3], 0));
+        data.extend_from_slice(&20u32.to_le_bytes());
+        data.extend(entry(0xbeef, [4, 5, 6], 101));
+
+        let entries = parse_header_block(&data).expect("parse");
+        assert_eq!(
+            entries,
+            vec![
+                SourceHeaderEntry {
+                    crc: 0xdead,
+                    file_size: 42,
+                    file_name: StringRef(1),
+                    object_name: StringRef(2),
+                    virtual_name: StringRef(3),
+                    compression: SourceCompression::None,
+                    is_virtual: true,
+                },
+                SourceHeaderEntry {
+                    crc: 0xbeef,
+                    file_size: 42,
+                    file_name: StringRef(4),
+                    object_name: StringRef(5),
+                    virtual_name: StringRef(6),
+                    compression: SourceCompression::DotNet,
+                    is_virtual: true,
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn test_empty_header_block() {
+        assert_eq!(parse_header_block(&[]).expect("parse"), vec![]);
+        assert!(matches!(
+            parse_header_block(&[0; 16]),
+            Err(Error::UnexpectedEof)
+        ));
+    }
+}
@@ -50,7 +50,10 @@
 mod common;
 mod dbi;
 mod framedata;
+mod injected;
+mod linkinfo;
 mod modi;
+mod modulemap;
 mod msf;
 mod omap;
 mod pdb;
@@ -59,6 +62,7 @@ mod pe;
 pub mod register;
 mod source;
 mod strings;
+mod summary;
 mod symbol;
 mod tpi;
 
@@ -66,13 +70,18 @@ mod tpi;
 pub use crate::common::*;
 pub use crate::dbi::*;
 pub use crate::framedata::*;
+pub use crate::injected::{InjectedSource, InjectedSourceIter, SourceCompression};
+pub use crate::linkinfo::*;
 pub use crate::modi::*;
+pub use crate::modulemap::*;
+pub use crate::msf::{StreamInfo, VerifyProblem, VerifyReport};
 pub use crate::omap::*;
 pub use crate::pdb::*;
 pub use crate::pdbi::*;
 pub use crate::pe::*;
 pub use crate::source::*;
 pub use crate::strings::*;
+pub use crate::summary::*;
 pub use crate::symbol::*;
 pub use crate::tpi::*;
 
@@ -0,0 +1,160 @@
+// Copyright 2017 pdb Developers
+//
+// Licensed under the Apache License, Version 2.0, <LICENSE-APACHE or
+// http://apache.org/licenses/LICENSE-2.0> or the MIT license <LICENSE-MIT or
+// http://opensource.org/licenses/MIT>, at your option. This file may not be
+// copied, modified, or distributed except according to those terms.
+
+//! Facilities for parsing the `/LinkInfo` stream.
+
+use crate::common::*;
+
+/// Size of the fixed `LinkInfo` header preceding the strings.
+const LINK_INFO_HEADER_SIZE: usize = 24;
+
+/// Information about the link that produced the PDB, read from the `/LinkInfo` stream.
+///
+/// This records the linker invocation, and complements the compiler environment recorded in
+/// [`EnvBlockSymbol`](crate::EnvBlockSymbol) and [`BuildInfoId`](crate::BuildInfoId) records. The
+/// path of the linker itself is at the start of [`command`](Self::command).
+///
+/// Obtained via [`PDB::link_info`](crate::PDB::link_info).
+#[non_exhaustive]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct LinkInfo {
+    /// Version of the `LinkInfo` structure.
+    pub version: u32,
+    /// The current working directory of the linker.
+    pub cwd: String,
+    /// The linker command line.
+    pub command: String,
+    /// The name of the output file, as passed to `/OUT` on the command line.
+    pub output_file: String,
+    /// Libraries passed to the linker.
+    pub libraries: Vec<String>,
+}
+
+impl LinkInfo {
+    /// Parses link information from the contents of the `/LinkInfo` stream.
+    pub(crate) fn parse(data: &[u8]) -> Result<Self> {
+        let mut buf = ParseBuffer::from(data);
+        let size = buf.parse_u32()? as usize;
+        let version = buf.parse_u32()?;
+        let cwd_offset = buf.parse_u32()? as usize;
+        let command_offset = buf.parse_u32()? as usize;
+        let output_file_index = buf.parse_u32()? as usize;
+        let libraries_offset = buf.parse_u32()? as usize;
+
+        if size < LINK_INFO_HEADER_SIZE || size > data.len() {
+            return Err(Error::InvalidStreamLength("LinkInfo"));
+        }
+
+        let mut buf = ParseBuffer::from(&data[..size]);
+        let mut string_at = |offset: usize| -> Result<String> {
+            buf.seek(offset);
+            Ok(buf.parse_cstring()?.to_string().into_owned())
+        };
+
+        let cwd = string_at(cwd_offset)?;
+        let command = string_at(command_offset)?;
+        let output_file = command_argument(&string_at(command_offset + output_file_index)?);
+
+        let mut libraries = Vec::new();
+        if libraries_offset != 0 {
+            buf.seek(libraries_offset);
+            while !buf.is_empty() {
+                let library = buf.parse_cstring()?;
+                if library.is_empty() {
+                    break;
+                }
+                libraries.push(library.to_string().into_owned());
+            }
+        }
+
+        Ok(LinkInfo {
+            version,
+            cwd,
+            command,
+            output_file,
+            libraries,
+        })
+    }
+}
+
+/// Returns the first argument of a command line, up to the next whitespace outside of quotes.
+///
+/// Quotes are removed from the returned argument.
+fn command_argument(command: &str) -> String {
+    let mut argument = String::new();
+    let mut quoted = false;
+
+    for c in command.chars() {
+        match c {
+            '"' => quoted = !quoted,
+            c if c.is_whitespace() && !quoted => break,
+            c => argument.push(c),
+        }
+    }
+
+    argument
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn link_info(strings: &[u8], offsets: [u32; 4]) -> Vec<u8> {
+        let size = (LINK_INFO_HEADER_SIZE + strings.len()) as u32;
+        let mut data = Vec::new();
+        data.extend_from_slice(&size.to_le_bytes());
+        data.extend_from_slice(&1u32.to_le_bytes());
+        for offset in &offsets {
+            data.extend_from_slice(&offset.to_le_bytes());
+        }
+        data.extend_from_slice(strings);
+        data
+    }
+
+    #[test]
+    fn test_parse() {
+        let strings = b"C:\\build\0link.exe /OUT:foo.exe foo.obj\0kernel32.lib\0user32.lib\0\0";
+        let data = link_info(strings, [24, 33, 14, 63]);
+
+        let info = LinkInfo::parse(&data).expect("parse");
+        assert_eq!(info.version, 1);
+        assert_eq!(info.cwd, "C:\\build");
+        assert_eq!(info.command, "link.exe /OUT:foo.exe foo.obj");
+        assert_eq!(info.output_file, "foo.exe");
+        assert_eq!(info.libraries, vec!["kernel32.lib", "user32.lib"]);
+    }
+
+    #[test]
+    fn test_quoted_output_file() {
+        let strings = b"C:\\\0link.exe /OUT:\"C:\\out dir\\foo.exe\" foo.obj\0";
+        let data = link_info(strings, [24, 28, 14, 0]);
+
+        let info = LinkInfo::parse(&data).expect("parse");
+        assert_eq!(info.output_file, "C:\\out dir\\foo.exe");
+    }
+
+    #[test]
+    fn test_without_libraries() {
+        let data = link_info(b"C:\\\0link.exe\0", [24, 28, 0, 0]);
+
+        let info = LinkInfo::parse(&data).expect("parse");
+        assert_eq!(info.cwd, "C:\\");
+        assert_eq!(info.command, "link.exe");
+        assert!(info.libraries.is_empty());
+    }
+
+    #[test]
+    fn test_truncated() {
+        let mut data = link_info(b"C:\\\0link.exe\0", [24, 28, 0, 0]);
+        data.truncate(30);
+
+        assert!(matches!(
+            LinkInfo::parse(&data),
+            Err(Error::InvalidStreamLength("LinkInfo"))
+        ));
+    }
+}
@@ -12,39 +12,81 @@ use crate::modi::{
 use crate::symbol::{BinaryAnnotation, BinaryAnnotationsIter, InlineSiteSymbol};
 use crate::FallibleIterator;
 
-#[derive(Clone, Copy, Debug, Eq, PartialEq)]
-#[repr(u32)]
-#[allow(unused)]
-enum DebugSubsectionKind {
-    // Native
-    Symbols = 0xf1,
-    Lines = 0xf2,
-    StringTable = 0xf3,
-    FileChecksums = 0xf4,
-    FrameData = 0xf5,
-    InlineeLines = 0xf6,
-    CrossScopeImports = 0xf7,
-    CrossScopeExports = 0xf8,
-
-    // .NET
-    ILLines = 0xf9,
-    FuncMDTokenMap = 0xfa,
-    TypeMDTokenMap = 0xfb,
-    MergedAssemblyInput = 0xfc,
-
-    CoffSymbolRva = 0xfd,
-}
-
-impl DebugSubsectionKind {
-    fn parse(value: u32) -> Result<Option<Self>> {
-        if (0xf1..=0xfd).contains(&value) {
-            Ok(Some(unsafe {
-                std::mem::transmute::<u32, DebugSubsectionKind>(value)
-            }))
-        } else if value == constants::DEBUG_S_IGNORE {
-            Ok(None)
-        } else {
-            Err(Error::UnimplementedDebugSubsection(value))
+/// The kind of a C13 debug subsection.
+///
+/// Modules store their line information and related tables as a sequence of subsections, each
+/// tagged with one of these kinds. See [`ModuleInfo::debug_subsections`](crate::ModuleInfo::debug_subsections).
+#[non_exhaustive]
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+pub enum DebugSubsectionKind {
+    /// Symbol records, `DEBUG_S_SYMBOLS`.
+    Symbols,
+    /// Line numbers of a contribution, `DEBUG_S_LINES`.
+    Lines,
+    /// A string table referenced by other subsections, `DEBUG_S_STRINGTABLE`.
+    StringTable,
+    /// Checksums of the source files, `DEBUG_S_FILECHKSMS`.
+    FileChecksums,
+    /// Frame data for stack unwinding, `DEBUG_S_FRAMEDATA`.
+    FrameData,
+    /// Source lines of inlined functions, `DEBUG_S_INLINEELINES`.
+    InlineeLines,
+    /// Items imported from other modules, `DEBUG_S_CROSSSCOPEIMPORTS`.
+    CrossScopeImports,
+    /// Items exported to other modules, `DEBUG_S_CROSSSCOPEEXPORTS`.
+    CrossScopeExports,
+    /// IL line numbers of .NET methods, `DEBUG_S_IL_LINES`.
+    ILLines,
+    /// Map from functions to .NET metadata tokens, `DEBUG_S_FUNC_MDTOKEN_MAP`.
+    FuncMDTokenMap,
+    /// Map from types to .NET metadata tokens, `DEBUG_S_TYPE_MDTOKEN_MAP`.
+    TypeMDTokenMap,
+    /// Merged .NET assembly inputs, `DEBUG_S_MERGED_ASSEMBLYINPUT`.
+    MergedAssemblyInput,
+    /// RVAs of COFF symbols, `DEBUG_S_COFF_SYMBOL_RVA`.
+    CoffSymbolRva,
+    /// A subsection kind that is not known to this crate.
+    Unknown(u32),
+}
+
+impl From<u32> for DebugSubsectionKind {
+    fn from(value: u32) -> Self {
+        match value {
+            0xf1 => Self::Symbols,
+            0xf2 => Self::Lines,
+            0xf3 => Self::StringTable,
+            0xf4 => Self::FileChecksums,
+            0xf5 => Self::FrameData,
+            0xf6 => Self::InlineeLines,
+            0xf7 => Self::CrossScopeImports,
+            0xf8 => Self::CrossScopeExports,
+            0xf9 => Self::ILLines,
+            0xfa => Self::FuncMDTokenMap,
+            0xfb => Self::TypeMDTokenMap,
+            0xfc => Self::MergedAssemblyInput,
+            0xfd => Self::CoffSymbolRva,
+            other => Self::Unknown(other),
+        }
+    }
+}
+
+impl From<DebugSubsectionKind> for u32 {
+    fn from(kind: DebugSubsectionKind) -> Self {
+        match kind {
+            DebugSubsectionKind::Symbols => 0xf1,
+            DebugSubsectionKind::Lines => 0xf2,
+            DebugSubsectionKind::StringTable => 0xf3,
+            DebugSubsectionKind::FileChecksums => 0xf4,
+            DebugSubsectionKind::FrameData => 0xf5,
+            DebugSubsectionKind::InlineeLines => 0xf6,
+            DebugSubsectionKind::CrossScopeImports => 0xf7,
+            DebugSubsectionKind::CrossScopeExports => 0xf8,
+            DebugSubsectionKind::ILLines => 0xf9,
+            DebugSubsectionKind::FuncMDTokenMap => 0xfa,
+            DebugSubsectionKind::TypeMDTokenMap => 0xfb,
+            DebugSubsectionKind::MergedAssemblyInput => 0xfc,
+            DebugSubsectionKind::CoffSymbolRva => 0xfd,
+            DebugSubsectionKind::Unknown(value) => value,
         }
     }
 }
@@ -71,30 +113,71 @@ impl<'t> TryFromCtx<'t, Endian> for DebugSubsectionHeader {
 }
 
 impl DebugSubsectionHeader {
-    fn kind(self) -> Result<Option<DebugSubsectionKind>> {
-        DebugSubsectionKind::parse(self.kind)
-    }
-
     fn len(self) -> usize {
         self.len as usize
     }
 }
 
+/// An iterator over the raw C13 debug subsections of a module.
+///
+/// Each item is the kind of a subsection and its data, excluding the header and the padding that
+/// aligns the following subsection to 4 bytes. Subsections flagged with `DEBUG_S_IGNORE` are
+/// skipped. Kinds not modeled by this crate are yielded as [`DebugSubsectionKind::Unknown`].
+///
+/// Created by [`ModuleInfo::debug_subsections`](crate::ModuleInfo::debug_subsections).
+#[derive(Clone, Debug, Default)]
+pub struct DebugSubsectionIter<'a> {
+    buf: ParseBuffer<'a>,
+}
+
+impl<'a> DebugSubsectionIter<'a> {
+    pub(crate) fn new(data: &'a [u8]) -> Self {
+        Self {
+            buf: ParseBuffer::from(data),
+        }
+    }
+}
+
+impl<'a> FallibleIterator for DebugSubsectionIter<'a> {
+    type Item = (DebugSubsectionKind, &'a [u8]);
+    type Error = Error;
+
+    fn next(&mut self) -> Result<Option<Self::Item>> {
+        while !self.buf.is_empty() {
+            let header = self.buf.parse::<DebugSubsectionHeader>()?;
+            let data = self.buf.take(header.len())?;
+
+            // Subsections start at 4-byte boundaries. Trailing padding may be cut off.
+            let aligned = (self.buf.pos() + 3) & !3;
+            self.buf.seek(aligned);
+
+            if header.kind & constants::DEBUG_S_IGNORE != 0 {
+                continue;
+            }
+
+            return Ok(Some((DebugSubsectionKind::from(header.kind), data)));
+        }
+
+        Ok(None)
+    }
+}
+
 #[derive(Clone, Copy, Debug)]
 struct DebugSubsection<'a> {
     pub kind: DebugSubsectionKind,
     pub data: &'a [u8],
 }
 
+/// Iterates the subsections known to this crate, failing on unknown kinds.
 #[derive(Clone, Debug, Default)]
 struct DebugSubsectionIterator<'a> {
-    buf: ParseBuffer<'a>,
+    inner: DebugSubsectionIter<'a>,
 }
 
 impl<'a> DebugSubsectionIterator<'a> {
     fn new(data: &'a [u8]) -> Self {
         Self {
-            buf: ParseBuffer::from(data),
+            inner: DebugSubsectionIter::new(data),
         }
     }
 }
@@ -104,21 +187,23 @@ impl<'a> FallibleIterator for DebugSubsectionIterator<'a> {
     type Error = Error;
 
     fn next(&mut self) -> Result<Option<Self::Item>> {
-        while !self.buf.is_empty() {
-            let header = self.buf.parse::<DebugSubsectionHeader>()?;
-            let data = self.buf.take(header.len())?;
-            let kind = match header.kind()? {
-                Some(kind) => kind,
-                None => continue,
-            };
-
-            return Ok(Some(DebugSubsection { kind, data }));
+        match self.inner.next()? {
+            Some((DebugSubsectionKind::Unknown(kind), _)) => {
+                Err(Error::UnimplementedDebugSubsection(kind))
+            }
+            Some((kind, data)) => Ok(Some(DebugSubsection { kind, data })),
+            None => Ok(None),
         }
-
-        Ok(None)
     }
 }
 
+/// Returns whether the C13 line data contains at least one `DEBUG_S_LINES` subsection.
+pub(crate) fn has_lines(data: &[u8]) -> bool {
+    DebugSubsectionIterator::new(data)
+        .any(|sec| Ok(sec.kind == DebugSubsectionKind::Lines))
+        .unwrap_or(false)
+}
+
 #[derive(Clone, Copy, Debug, Default)]
 struct DebugInlineeLinesHeader {
     /// The signature of the inlinees
@@ -1038,7 +1123,7 @@ impl FallibleIterator for LineIterator<'_> {
                     kind: line_entry.kind,
                 };
 
-                let mut last_info = match std::mem::replace(&mut self.last_info, Some(line_info)) {
+                let mut last_info = match self.last_info.replace(line_info) {
                     Some(last_info) => last_info,
                     None => continue,
                 };
@@ -1228,7 +1313,7 @@ impl FallibleIterator for InlineeLineIterator<'_> {
 
             // Finish the previous record and emit it. The current record is stored so that the
             // length can be inferred from subsequent operators or the next line info.
-            if let Some(last_info) = std::mem::replace(&mut self.last_info, Some(line_info)) {
+            if let Some(last_info) = self.last_info.replace(line_info) {
                 return Ok(Some(last_info));
             }
         }
@@ -1452,6 +1537,34 @@ mod tests {
         assert_eq!(mem::align_of::<RawCrossScopeExport>(), 4);
     }
 
+    #[test]
+    fn test_debug_subsection_iter() {
+        let mut data = Vec::new();
+        // string table with 5 bytes of data, padded to 8
+        data.extend_from_slice(&[0xf3, 0, 0, 0, 5, 0, 0, 0]);
+        data.extend_from_slice(b"\0abc\0\0\0\0");
+        // ignored subsection
+        data.extend_from_slice(&[0, 0, 0, 0x80, 4, 0, 0, 0, 1, 2, 3, 4]);
+        // unknown subsection without trailing padding
+        data.extend_from_slice(&[0x42, 0, 0, 0, 2, 0, 0, 0, 9, 9]);
+
+        let subsections: Vec<_> = DebugSubsectionIter::new(&data).collect().expect("collect");
+        assert_eq!(
+            subsections,
+            vec![
+                (DebugSubsectionKind::StringTable, &b"\0abc\0"[..]),
+                (DebugSubsectionKind::Unknown(0x42), &[9, 9][..]),
+            ]
+        );
+        assert_eq!(u32::from(DebugSubsectionKind::StringTable), 0xf3);
+
+        let result = DebugSubsectionIterator::new(&data).count();
+        assert!(matches!(
+            result,
+            Err(Error::UnimplementedDebugSubsection(0x42))
+        ));
+    }
+
     #[test]
     fn test_iter_lines() {
         let data = &[
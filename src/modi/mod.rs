@@ -1,17 +1,18 @@
 use std::fmt;
 
 use crate::common::*;
-use crate::dbi::Module;
+use crate::dbi::DBIModuleInfo;
+use crate::framedata::ModuleFrameDataIter;
 use crate::msf::Stream;
-use crate::symbol::SymbolIter;
+use crate::symbol::{ObjNameSymbol, SymbolData, SymbolIter};
 use crate::FallibleIterator;
 
 mod c13;
 mod constants;
 
 pub use c13::{
-    CrossModuleExportIter, CrossModuleExports, CrossModuleImports, Inlinee, InlineeIterator,
-    InlineeLineIterator,
+    CrossModuleExportIter, CrossModuleExports, CrossModuleImports, DebugSubsectionIter,
+    DebugSubsectionKind, Inlinee, InlineeIterator, InlineeLineIterator,
 };
 
 #[derive(Clone, Copy, Debug)]
@@ -27,13 +28,12 @@ pub struct ModuleInfo<'s> {
     stream: Stream<'s>,
     symbols_size: usize,
     lines_size: LinesSize,
+    global_refs_offset: usize,
 }
 
 impl<'s> ModuleInfo<'s> {
     /// Parses a `ModuleInfo` from it's Module info stream data.
-    pub(crate) fn parse(stream: Stream<'s>, module: &Module<'_>) -> Self {
-        let info = module.info();
-
+    pub(crate) fn parse(stream: Stream<'s>, info: &DBIModuleInfo) -> Self {
         let lines_size = if info.lines_size > 0 {
             LinesSize::C11(info.lines_size as usize)
         } else {
@@ -41,10 +41,13 @@ impl<'s> ModuleInfo<'s> {
         };
 
         let symbols_size = info.symbols_size as usize;
+        let global_refs_offset =
+            symbols_size + info.lines_size as usize + info.c13_lines_size as usize;
         ModuleInfo {
             stream,
             symbols_size,
             lines_size,
+            global_refs_offset,
         }
     }
 
@@ -53,7 +56,26 @@ impl<'s> ModuleInfo<'s> {
         &self.stream[start..start + size]
     }
 
+    /// Returns the size of the symbol region of the module info stream in bytes.
+    ///
+    /// This includes the 4-byte signature preceding the symbol records.
+    #[must_use]
+    pub fn symbols_size(&self) -> usize {
+        self.symbols_size
+    }
+
     /// Get an iterator over the all symbols in this module.
+    ///
+    /// A module info stream is laid out as follows, with the sizes of each region stored in the
+    /// module's record in the debug information stream:
+    ///
+    /// ```text
+    /// [signature][symbols][C11 lines][C13 subsections][global refs]
+    /// ```
+    ///
+    /// The iterator covers exactly the symbol records between the signature and
+    /// [`symbols_size`](Self::symbols_size), so it stops at the end of the symbol region instead of
+    /// running into line information.
     pub fn symbols(&self) -> Result<SymbolIter<'_>> {
         let mut buf = self.stream.parse_buffer();
         buf.truncate(self.symbols_size)?;
@@ -68,6 +90,21 @@ impl<'s> ModuleInfo<'s> {
         Ok(SymbolIter::new(buf))
     }
 
+    /// Returns the object file name record of this module.
+    ///
+    /// Compilers emit an `S_OBJNAME` record as the first symbol of every module. This reads only
+    /// that record instead of iterating all symbols. Returns `None` if the module has no symbols,
+    /// or the first symbol is not a valid [`ObjNameSymbol`].
+    pub fn obj_name(&self) -> Result<Option<ObjNameSymbol>> {
+        Ok(match self.symbols()?.next()? {
+            Some(symbol) => match symbol.parse_lenient() {
+                SymbolData::ObjName(obj_name) => Some(obj_name),
+                _ => None,
+            },
+            None => None,
+        })
+    }
+
     /// Get an iterator over symbols starting at the given index.
     pub fn symbols_at(&self, index: SymbolIndex) -> Result<SymbolIter<'_>> {
         let mut iter = self.symbols()?;
@@ -75,6 +112,72 @@ impl<'s> ModuleInfo<'s> {
         Ok(iter)
     }
 
+    /// Returns the global symbols referenced by this module.
+    ///
+    /// The global refs region at the end of the module info stream starts with its size in bytes,
+    /// followed by the offsets of the referenced symbols in the global symbol stream. Each returned
+    /// index can be passed to [`SymbolTable::iter_at`](crate::SymbolTable::iter_at). Modules
+    /// without this region return an empty list.
+    ///
+    /// # Errors
+    ///
+    /// * `Error::UnexpectedEof` if the region exceeds the module info stream
+    pub fn global_refs(&self) -> Result<Vec<SymbolIndex>> {
+        let mut buf = self.stream.parse_buffer();
+        buf.take(self.global_refs_offset)?;
+        if buf.is_empty() {
+            return Ok(Vec::new());
+        }
+
+        let size = buf.parse_u32()? as usize;
+        let mut buf = ParseBuffer::from(buf.take(size)?);
+
+        let mut refs = Vec::with_capacity(size / 4);
+        while !buf.is_empty() {
+            refs.push(SymbolIndex(buf.parse_u32()?));
+        }
+
+        Ok(refs)
+    }
+
+    /// Returns whether this module contains line information that [`line_program`] can read.
+    ///
+    /// This only scans the headers of the C13 subsections for a `DEBUG_S_LINES` subsection, which
+    /// is much cheaper than building the line program. Modules with C11 line information, which is
+    /// not supported, return `false`.
+    ///
+    /// [`line_program`]: Self::line_program
+    #[must_use]
+    pub fn has_line_info(&self) -> bool {
+        match self.lines_size {
+            LinesSize::C11(_size) => false,
+            LinesSize::C13(size) => c13::has_lines(self.lines_data(size)),
+        }
+    }
+
+    /// Returns an iterator over the raw C13 debug subsections of this module.
+    ///
+    /// This gives access to every subsection, including kinds that have no dedicated accessor
+    /// such as [`line_program`](Self::line_program) or [`inlinees`](Self::inlinees). Subsections
+    /// of unknown kinds are yielded as [`DebugSubsectionKind::Unknown`] rather than failing.
+    /// Modules with C11 line information have no C13 subsections and yield an empty iterator.
+    pub fn debug_subsections(&self) -> Result<DebugSubsectionIter<'_>> {
+        Ok(match self.lines_size {
+            LinesSize::C11(_size) => Default::default(),
+            LinesSize::C13(size) => DebugSubsectionIter::new(self.lines_data(size)),
+        })
+    }
+
+    /// Returns an iterator over the frame data stored in this module.
+    ///
+    /// Modules compiled for x86 describe the stack frames of their functions in
+    /// `DEBUG_S_FRAMEDATA` subsections. The [`program`](crate::FrameData::program) of each entry refers to
+    /// the PDB's string table and can be resolved with [`FrameData::program_string`](crate::FrameData::program_string). Most PDBs
+    /// also contain a merged copy of this data in the [`FrameTable`](crate::FrameTable).
+    pub fn frame_data(&self) -> Result<ModuleFrameDataIter<'_>> {
+        Ok(ModuleFrameDataIter::new(self.debug_subsections()?))
+    }
+
     /// Returns a line program that gives access to file and line information in this module.
     pub fn line_program(&self) -> Result<LineProgram<'_>> {
         let inner = match self.lines_size {
@@ -151,20 +254,15 @@ pub struct FileInfo<'a> {
 }
 
 /// The kind of source construct a line info is referring to.
-#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq)]
 pub enum LineInfoKind {
     /// A source code expression.
     Expression,
     /// A source code statement.
+    #[default]
     Statement,
 }
 
-impl Default for LineInfoKind {
-    fn default() -> Self {
-        Self::Statement
-    }
-}
-
 /// Mapping of a source code offset to a source file location.
 ///
 /// A line entry is always valid up to the subsequent entry.
@@ -201,7 +299,7 @@ impl LineInfo {
 
         if self.offset <= end_offset {
             let length = end_offset.offset - self.offset.offset;
-            if self.length.map_or(true, |l| l > length) {
+            if self.length.is_none_or(|l| l > length) {
                 self.length = Some(length);
             }
         }
@@ -267,6 +365,56 @@ impl<'a> LineProgram<'a> {
             LineProgramInner::C13(ref inner) => inner.get_file_info(offset),
         }
     }
+
+    /// Returns the source file and line number of the code at the given section offset.
+    ///
+    /// This picks the line record with the highest start offset that still covers `offset`.
+    /// Returns `None` if no line record covers the offset.
+    pub fn location_of(
+        &self,
+        offset: PdbInternalSectionOffset,
+    ) -> Result<Option<(FileInfo<'a>, u32)>> {
+        let mut best: Option<LineInfo> = None;
+
+        let mut lines = self.lines_for_symbol(offset);
+        while let Some(line) = lines.next()? {
+            if line.offset.section != offset.section || line.offset.offset > offset.offset {
+                continue;
+            }
+
+            let end = line
+                .length
+                .map(|length| line.offset.offset.saturating_add(length));
+            if end.is_some_and(|end| offset.offset >= end) {
+                continue;
+            }
+
+            if best
+                .as_ref()
+                .is_none_or(|best| best.offset.offset < line.offset.offset)
+            {
+                best = Some(line);
+            }
+        }
+
+        match best {
+            Some(line) => Ok(Some((
+                self.get_file_info(line.file_index)?,
+                line.line_start,
+            ))),
+            None => Ok(None),
+        }
+    }
+}
+
+/// A resolved source location, returned by [`PDB::symbol_location`](crate::PDB::symbol_location)
+/// and [`PDB::symbol_locations`](crate::PDB::symbol_locations).
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct SourceLocation {
+    /// Path of the source file, as recorded in the string table.
+    pub file: String,
+    /// Line number in the source file.
+    pub line: u32,
 }
 
 #[derive(Clone, Debug)]
@@ -329,10 +477,10 @@ impl<'a> FallibleIterator for FileIterator<'a> {
     }
 }
 
-/// Named reference to a [`Module`].
+/// Named reference to a [`Module`](crate::Module).
 ///
 /// The name stored in the [`StringTable`](crate::StringTable) corresponds to the name of the module
-/// as returned by [`Module::module_name`].
+/// as returned by [`Module::module_name`](crate::Module::module_name).
 #[derive(Clone, Copy, Debug, Eq, Hash, Ord, PartialEq, PartialOrd)]
 pub struct ModuleRef(pub StringRef);
 
@@ -0,0 +1,145 @@
+// Copyright 2017 pdb Developers
+//
+// Licensed under the Apache License, Version 2.0, <LICENSE-APACHE or
+// http://apache.org/licenses/LICENSE-2.0> or the MIT license <LICENSE-MIT or
+// http://opensource.org/licenses/MIT>, at your option. This file may not be
+// copied, modified, or distributed except according to those terms.
+
+//! Facilities for attributing addresses to the modules that contributed them.
+
+use fallible_iterator::FallibleIterator;
+
+use crate::common::*;
+use crate::dbi::DebugInformation;
+use crate::omap::AddressMap;
+
+/// A module listed in a [`ModuleMap`].
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct ModuleDescriptor {
+    /// Index of the module in [`DebugInformation::modules`].
+    pub index: usize,
+    /// The module name, see [`Module::module_name`](crate::Module::module_name).
+    pub module_name: String,
+    /// The object file name, see [`Module::object_file_name`](crate::Module::object_file_name).
+    pub object_file_name: String,
+}
+
+/// A section contribution, reduced to the fields needed for lookups.
+#[derive(Clone, Copy, Debug)]
+struct Contribution {
+    section: u16,
+    start: u32,
+    end: u32,
+    module: usize,
+}
+
+/// An index from addresses to the modules that contributed them.
+///
+/// This combines the section contributions of the DBI stream with the list of modules and the
+/// [`AddressMap`] of the PDB. Lookups perform a binary search over the contributions, sorted by
+/// address. The map is built once via [`PDB::module_map`](crate::PDB::module_map).
+///
+/// # Example
+///
+/// ```
+/// # fn test() -> pdb2::Result<()> {
+/// let file = std::fs::File::open("fixtures/self/foo.pdb")?;
+/// let mut pdb = pdb2::PDB::open(file)?;
+/// let module_map = pdb.module_map()?;
+///
+/// let offset = pdb2::PdbInternalSectionOffset::new(1, 0x5540);
+/// if let Some(module) = module_map.module_for_internal_offset(offset) {
+///     println!("{} contributed {:?}", module.object_file_name, offset);
+/// }
+/// # Ok(())
+/// # }
+/// # test().unwrap()
+/// ```
+#[derive(Debug)]
+pub struct ModuleMap<'s> {
+    address_map: AddressMap<'s>,
+    contributions: Vec<Contribution>,
+    modules: Vec<ModuleDescriptor>,
+}
+
+impl<'s> ModuleMap<'s> {
+    pub(crate) fn new(
+        debug_information: &DebugInformation<'_>,
+        address_map: AddressMap<'s>,
+    ) -> Result<Self> {
+        let modules = debug_information
+            .modules()?
+            .enumerate()
+            .map(|(index, module)| {
+                Ok(ModuleDescriptor {
+                    index,
+                    module_name: module.module_name().into_owned(),
+                    object_file_name: module.object_file_name().into_owned(),
+                })
+            })
+            .collect()?;
+
+        let mut contributions: Vec<_> = debug_information
+            .section_contributions()?
+            .filter(|contribution| Ok(contribution.size > 0))
+            .map(|contribution| {
+                Ok(Contribution {
+                    section: contribution.offset.section,
+                    start: contribution.offset.offset,
+                    end: contribution.offset.offset.saturating_add(contribution.size),
+                    module: contribution.module,
+                })
+            })
+            .collect()?;
+        contributions.sort_by_key(|contribution| (contribution.section, contribution.start));
+
+        Ok(ModuleMap {
+            address_map,
+            contributions,
+            modules,
+        })
+    }
+
+    /// Returns all modules of the PDB, in the order of [`DebugInformation::modules`].
+    #[must_use]
+    pub fn modules(&self) -> &[ModuleDescriptor] {
+        &self.modules
+    }
+
+    /// Returns the address map used to translate RVAs.
+    #[must_use]
+    pub fn address_map(&self) -> &AddressMap<'s> {
+        &self.address_map
+    }
+
+    /// Returns the module that contributed the code or data at the given offset.
+    ///
+    /// Returns `None` if the offset is not covered by any section contribution.
+    #[must_use]
+    pub fn module_for_internal_offset(
+        &self,
+        offset: PdbInternalSectionOffset,
+    ) -> Option<&ModuleDescriptor> {
+        let key = (offset.section, offset.offset);
+        let index = self
+            .contributions
+            .partition_point(|contribution| (contribution.section, contribution.start) <= key);
+
+        let contribution = self.contributions.get(index.checked_sub(1)?)?;
+        if contribution.section != offset.section || offset.offset >= contribution.end {
+            return None;
+        }
+
+        self.modules.get(contribution.module)
+    }
+
+    /// Returns the module that contributed the code or data at the given RVA.
+    ///
+    /// The RVA is translated to a PDB-internal offset through the [`AddressMap`] first. Returns
+    /// `None` if the address cannot be translated, or is not covered by any section contribution.
+    #[must_use]
+    pub fn module_for_rva(&self, rva: Rva) -> Option<&ModuleDescriptor> {
+        let offset = rva.to_internal_offset(&self.address_map)?;
+        self.module_for_internal_offset(offset)
+    }
+}
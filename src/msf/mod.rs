@@ -75,6 +75,125 @@ fn view<'s>(
     Ok(view)
 }
 
+/// A problem found while verifying the structure of an MSF file.
+///
+/// See [`PDB::verify`](crate::PDB::verify).
+#[non_exhaustive]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum VerifyProblem {
+    /// The MSF header refers to an invalid free page map.
+    InvalidFreePageMap(u32),
+    /// The file is shorter than the number of pages declared in the MSF header.
+    Truncated {
+        /// The number of pages declared in the MSF header.
+        pages_used: u32,
+    },
+    /// The stream directory could not be read at all.
+    UnreadableStreamDirectory,
+    /// The stream directory ends before all declared streams are described.
+    TruncatedStreamDirectory,
+    /// A stream refers to a page outside of the file.
+    PageOutOfRange {
+        /// The number of the stream referring to the page.
+        stream: u32,
+        /// The invalid page number.
+        page: u32,
+    },
+}
+
+/// The result of verifying the structure of an MSF file.
+///
+/// See [`PDB::verify`](crate::PDB::verify).
+#[derive(Clone, Debug, Default, Eq, PartialEq)]
+pub struct VerifyReport {
+    /// All problems found, in the order they were encountered.
+    pub problems: Vec<VerifyProblem>,
+}
+
+impl VerifyReport {
+    /// Returns `true` if no problems were found.
+    #[must_use]
+    pub fn is_ok(&self) -> bool {
+        self.problems.is_empty()
+    }
+}
+
+/// An entry of the MSF stream directory.
+///
+/// See [`PDB::stream_directory`](crate::PDB::stream_directory).
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub struct StreamInfo {
+    /// The index of the stream, which can be passed to
+    /// [`PDB::raw_stream`](crate::PDB::raw_stream).
+    pub index: StreamIndex,
+    /// Whether the stream exists. Deleted or unused stream numbers are marked as absent.
+    pub present: bool,
+    /// The size of the stream in bytes, or `0` if the stream is absent.
+    pub byte_size: u32,
+    /// The number of pages occupied by the stream.
+    pub page_count: usize,
+}
+
+/// Describes all streams given their byte sizes in the stream directory.
+fn stream_infos(header: &Header, sizes: &[u32]) -> Vec<StreamInfo> {
+    sizes
+        .iter()
+        .enumerate()
+        .map(|(number, &bytes)| {
+            let present = bytes != u32::MAX;
+            let byte_size = if present { bytes } else { 0 };
+            StreamInfo {
+                index: StreamIndex(number as u16),
+                present,
+                byte_size,
+                page_count: header.pages_needed_to_store(byte_size as usize),
+            }
+        })
+        .collect()
+}
+
+/// Checks that the last page declared in the header can be read from the source.
+fn verify_page_count<'s>(source: &mut dyn Source<'s>, header: &Header, report: &mut VerifyReport) {
+    let pages_used = header.maximum_valid_page_number;
+    let mut last_page = PageList::new(header.page_size);
+    last_page.push(pages_used.saturating_sub(1));
+
+    if pages_used == 0 || source.view(last_page.source_slices()).is_err() {
+        report
+            .problems
+            .push(VerifyProblem::Truncated { pages_used });
+    }
+}
+
+/// Checks the page numbers of all streams in a stream directory.
+///
+/// `sizes` are the byte sizes of all streams in the directory, and `next_page` reads the next page
+/// number from the directory.
+fn verify_stream_pages(
+    header: &Header,
+    sizes: &[u32],
+    mut next_page: impl FnMut() -> Result<u32>,
+    report: &mut VerifyReport,
+) -> Result<()> {
+    for (stream, &bytes) in sizes.iter().enumerate() {
+        if bytes == u32::MAX {
+            continue;
+        }
+
+        for _ in 0..header.pages_needed_to_store(bytes as usize) {
+            let page = next_page()?;
+            if page == 0 || page >= header.maximum_valid_page_number {
+                report.problems.push(VerifyProblem::PageOutOfRange {
+                    stream: stream as u32,
+                    page,
+                });
+            }
+        }
+    }
+
+    Ok(())
+}
+
 mod big {
     use super::*;
 
@@ -118,6 +237,7 @@ mod big {
     #[derive(Debug)]
     pub struct BigMSF<'s, S> {
         header: Header,
+        free_page_map: u32,
         source: S,
         stream_table: StreamTable<'s>,
     }
@@ -168,6 +288,7 @@ mod big {
 
             Ok(BigMSF {
                 header: header_object,
+                free_page_map: header.free_page_map,
                 source,
                 stream_table: StreamTable::HeaderOnly {
                     size_in_bytes: header.directory_size as usize,
@@ -323,6 +444,75 @@ mod big {
     }
 
     impl<'s, S: Source<'s>> Msf<'s, S> for BigMSF<'s, S> {
+        fn verify(&mut self) -> VerifyReport {
+            let mut report = VerifyReport::default();
+
+            // the free page map is always stored in page 1 or 2
+            if self.free_page_map != 1 && self.free_page_map != 2 {
+                report
+                    .problems
+                    .push(VerifyProblem::InvalidFreePageMap(self.free_page_map));
+            }
+
+            verify_page_count(&mut self.source, &self.header, &mut report);
+
+            if self.make_stream_table_available().is_err() {
+                report
+                    .problems
+                    .push(VerifyProblem::UnreadableStreamDirectory);
+                return report;
+            }
+
+            let StreamTable::Available {
+                ref stream_table_view,
+            } = self.stream_table
+            else {
+                unreachable!()
+            };
+
+            let mut stream_table = ParseBuffer::from(stream_table_view.as_slice());
+            let result = (|| {
+                let stream_count = stream_table.parse_u32()?;
+                let sizes = (0..stream_count)
+                    .map(|_| stream_table.parse_u32())
+                    .collect::<Result<Vec<_>>>()?;
+
+                verify_stream_pages(
+                    &self.header,
+                    &sizes,
+                    || stream_table.parse_u32(),
+                    &mut report,
+                )
+            })();
+
+            if result.is_err() {
+                report
+                    .problems
+                    .push(VerifyProblem::TruncatedStreamDirectory);
+            }
+
+            report
+        }
+
+        fn stream_directory(&mut self) -> Result<Vec<StreamInfo>> {
+            self.make_stream_table_available()?;
+
+            let StreamTable::Available {
+                ref stream_table_view,
+            } = self.stream_table
+            else {
+                unreachable!()
+            };
+
+            let mut stream_table = ParseBuffer::from(stream_table_view.as_slice());
+            let stream_count = stream_table.parse_u32()?;
+            let sizes = (0..stream_count)
+                .map(|_| stream_table.parse_u32())
+                .collect::<Result<Vec<_>>>()?;
+
+            Ok(stream_infos(&self.header, &sizes))
+        }
+
         fn get(&mut self, stream_number: u32, limit: Option<usize>) -> Result<Stream<'s>> {
             // look up the stream
             let mut page_list = self.look_up_stream(stream_number)?;
@@ -389,6 +579,7 @@ mod small {
     #[derive(Debug)]
     pub struct SmallMSF<'s, S> {
         header: Header,
+        free_page_map: u32,
         source: S,
         stream_table: StreamTable<'s>,
     }
@@ -433,6 +624,7 @@ mod small {
 
             Ok(SmallMSF {
                 header: header_object,
+                free_page_map: header.free_page_map as _,
                 source,
                 stream_table: StreamTable::Available { stream_table_view },
             })
@@ -522,6 +714,76 @@ mod small {
     }
 
     impl<'s, S: Source<'s>> Msf<'s, S> for SmallMSF<'s, S> {
+        fn verify(&mut self) -> VerifyReport {
+            let mut report = VerifyReport::default();
+
+            if self.free_page_map == 0
+                || self.free_page_map >= self.header.maximum_valid_page_number
+            {
+                report
+                    .problems
+                    .push(VerifyProblem::InvalidFreePageMap(self.free_page_map));
+            }
+
+            verify_page_count(&mut self.source, &self.header, &mut report);
+
+            // the stream table is read eagerly when opening a small MSF
+            let StreamTable::Available {
+                ref stream_table_view,
+            } = self.stream_table
+            else {
+                unreachable!()
+            };
+
+            let mut stream_table = ParseBuffer::from(stream_table_view.as_slice());
+            let result = (|| {
+                let stream_count = stream_table.parse_u16()?;
+                let _reserved = stream_table.parse_u16()?;
+
+                let mut sizes = Vec::with_capacity(stream_count as usize);
+                for _ in 0..stream_count {
+                    sizes.push(stream_table.parse_u32()?);
+                    let _reserved = stream_table.parse_u32()?;
+                }
+
+                verify_stream_pages(
+                    &self.header,
+                    &sizes,
+                    || Ok(u32::from(stream_table.parse_u16()?)),
+                    &mut report,
+                )
+            })();
+
+            if result.is_err() {
+                report
+                    .problems
+                    .push(VerifyProblem::TruncatedStreamDirectory);
+            }
+
+            report
+        }
+
+        fn stream_directory(&mut self) -> Result<Vec<StreamInfo>> {
+            let StreamTable::Available {
+                ref stream_table_view,
+            } = self.stream_table
+            else {
+                unreachable!()
+            };
+
+            let mut stream_table = ParseBuffer::from(stream_table_view.as_slice());
+            let stream_count = stream_table.parse_u16()?;
+            let _reserved = stream_table.parse_u16()?;
+
+            let mut sizes = Vec::with_capacity(stream_count as usize);
+            for _ in 0..stream_count {
+                sizes.push(stream_table.parse_u32()?);
+                let _reserved = stream_table.parse_u32()?;
+            }
+
+            Ok(stream_infos(&self.header, &sizes))
+        }
+
         fn get(&mut self, stream_number: u32, limit: Option<usize>) -> Result<Stream<'s>> {
             // look up the stream
             let mut page_list = self.look_up_stream(stream_number)?;
@@ -549,6 +811,13 @@ pub struct Stream<'s> {
 }
 
 impl Stream<'_> {
+    /// Creates a stream without any data, standing in for a stream that is not present.
+    pub(crate) fn empty() -> Self {
+        Stream {
+            source_view: Box::new(EmptyView),
+        }
+    }
+
     #[inline]
     pub(crate) fn parse_buffer(&self) -> ParseBuffer<'_> {
         let slice = self.source_view.as_slice();
@@ -559,6 +828,23 @@ impl Stream<'_> {
     pub fn as_slice(&self) -> &[u8] {
         self.source_view.as_slice()
     }
+
+    /// Returns the length of this stream in bytes.
+    #[inline]
+    #[must_use]
+    pub fn byte_len(&self) -> usize {
+        self.as_slice().len()
+    }
+
+    /// Returns a stable 64-bit fingerprint of the stream contents.
+    ///
+    /// The fingerprint is the FNV-1a hash of the stream bytes. It does not change between runs or
+    /// crate versions and can be used as a cache key. Streams with equal contents always produce
+    /// equal fingerprints, regardless of how their pages are laid out in the file.
+    #[must_use]
+    pub fn fingerprint(&self) -> u64 {
+        fnv1a_64(Some(self.as_slice()))
+    }
 }
 
 impl Deref for Stream<'_> {
@@ -570,12 +856,51 @@ impl Deref for Stream<'_> {
     }
 }
 
+#[cfg(test)]
+impl Stream<'static> {
+    /// Creates a stream holding the given bytes.
+    pub(crate) fn from_bytes(bytes: Vec<u8>) -> Self {
+        Stream {
+            source_view: Box::new(OwnedView(bytes)),
+        }
+    }
+}
+
+#[cfg(test)]
+#[derive(Debug)]
+struct OwnedView(Vec<u8>);
+
+#[cfg(test)]
+impl SourceView<'_> for OwnedView {
+    fn as_slice(&self) -> &[u8] {
+        &self.0
+    }
+}
+
+#[derive(Debug)]
+struct EmptyView;
+
+impl SourceView<'_> for EmptyView {
+    fn as_slice(&self) -> &[u8] {
+        &[]
+    }
+}
+
 /// Provides access to a "multi-stream file", which is the container format used by PDBs.
 pub trait Msf<'s, S>: fmt::Debug {
     /// Accesses a stream by stream number, optionally restricted by a byte limit.
     fn get(&mut self, stream_number: u32, limit: Option<usize>) -> Result<Stream<'s>>;
+
+    /// Checks the structure of the file, collecting all problems found.
+    fn verify(&mut self) -> VerifyReport;
+
+    /// Lists all streams in the stream directory.
+    fn stream_directory(&mut self) -> Result<Vec<StreamInfo>>;
 }
 
+/// Signature of the compressed MSFZ container format.
+const MSFZ_MAGIC: &[u8] = b"Microsoft MSFZ Container\r\n\x1aALD\x00\x00";
+
 fn header_matches(actual: &[u8], expected: &[u8]) -> bool {
     actual.len() >= expected.len() && &actual[0..expected.len()] == expected
 }
@@ -613,6 +938,12 @@ pub fn open_msf<'s, S: Source<'s> + Send + 's>(
         return Ok(Box::new(smallmsf));
     }
 
+    // MSFZ stores streams in compressed chunks rather than pages, so none of the MSF structures
+    // above apply. Report it explicitly rather than claiming this is not a PDB at all.
+    if header_matches(header_view.as_slice(), MSFZ_MAGIC) {
+        return Err(Error::UnsupportedCompression);
+    }
+
     Err(Error::UnrecognizedFileFormat)
 }
 
@@ -674,5 +1005,50 @@ mod tests {
                 },
             };
         }
+
+        #[test]
+        fn test_compressed_container() {
+            let mut data = b"Microsoft MSFZ Container\r\n\x1aALD\x00\x00".to_vec();
+            data.resize(4096, 0);
+
+            match open_msf(std::io::Cursor::new(data)) {
+                Ok(_) => panic!("MSFZ container should not parse as msf"),
+                Err(e) => match e {
+                    Error::UnsupportedCompression => (),
+                    _ => panic!("MSFZ container should parse as unsupported compression"),
+                },
+            };
+        }
+    }
+
+    mod verify {
+        use crate::msf::*;
+
+        #[test]
+        fn test_verify_stream_pages() {
+            let h = Header {
+                page_size: 4096,
+                maximum_valid_page_number: 16,
+            };
+
+            // stream 0 spans two pages, stream 1 is missing, stream 2 is empty and stream 3 has one
+            let sizes = [4097, u32::MAX, 0, 1];
+            let mut pages = vec![3, 0, 16].into_iter();
+
+            let mut report = VerifyReport::default();
+            verify_stream_pages(&h, &sizes, || Ok(pages.next().unwrap()), &mut report)
+                .expect("verify");
+
+            assert_eq!(
+                report.problems,
+                [
+                    VerifyProblem::PageOutOfRange { stream: 0, page: 0 },
+                    VerifyProblem::PageOutOfRange {
+                        stream: 3,
+                        page: 16
+                    },
+                ]
+            );
+        }
     }
 }
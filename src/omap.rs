@@ -16,6 +16,7 @@ use std::ops::Range;
 use crate::common::*;
 use crate::msf::Stream;
 use crate::pe::ImageSectionHeader;
+use crate::symbol::SectionSymbol;
 
 /// A address translation record from an `OMAPTable`.
 ///
@@ -184,6 +185,47 @@ impl<'s> OMAPTable<'s> {
     }
 }
 
+/// Looks up ascending source addresses in OMAP records with a single forward walk.
+///
+/// Lookups for addresses lower than the previous one fall back to a binary search, so results are
+/// the same as [`OMAPTable::lookup`] regardless of the input order.
+struct OMAPCursor<'t> {
+    records: &'t [OMAPRecord],
+    index: usize,
+}
+
+impl<'t> OMAPCursor<'t> {
+    fn new(records: &'t [OMAPRecord]) -> Self {
+        Self { records, index: 0 }
+    }
+
+    fn lookup(&mut self, source_address: u32) -> Option<u32> {
+        let records = self.records;
+
+        match records.get(self.index) {
+            Some(record) if record.source_address() <= source_address => {
+                while records
+                    .get(self.index + 1)
+                    .is_some_and(|next| next.source_address() <= source_address)
+                {
+                    self.index += 1;
+                }
+            }
+            _ => {
+                let end = records.partition_point(|r| r.source_address() <= source_address);
+                self.index = end.checked_sub(1)?;
+            }
+        }
+
+        let record = records[self.index];
+        if record.target_address() == 0 {
+            return None;
+        }
+
+        Some(record.translate(source_address))
+    }
+}
+
 impl fmt::Debug for OMAPTable<'_> {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         f.debug_tuple("OMAPTable").field(&self.records()).finish()
@@ -352,6 +394,18 @@ impl FusedIterator for PdbInternalRvaRangeIter<'_> {}
 /// translation still has to assume different address spaces, which is why there is no direct
 /// conversion without an `AddressMap`.
 ///
+/// # Performance
+///
+/// `PDB::address_map` does all the one-time work: it reads the section headers and both OMAP
+/// tables into memory. Translations never touch the PDB again. Section offsets are converted to
+/// RVAs by indexing into the section headers, and OMAP lookups are binary searches over the sorted
+/// OMAP records, so converting a [`PdbInternalSectionOffset`] to an [`Rva`] costs `O(log n)` in
+/// the size of the OMAP and `O(1)` without one. Converting RVAs back to section offsets scans the
+/// section headers, of which there are usually only a handful.
+///
+/// Build the `AddressMap` once and share it across all translations rather than requesting a new
+/// one per lookup.
+///
 /// # Example
 ///
 /// ```rust
@@ -423,6 +477,68 @@ impl AddressMap<'_> {
             None => RangeIter::identity(range.start.0..range.end.0),
         })
     }
+
+    /// Resolves the PDB internal section offsets of a batch of RVAs.
+    ///
+    /// This is equivalent to calling [`Rva::to_internal_offset`] for every element, but walks the
+    /// OMAP table only once if `rvas` is sorted in ascending order. Unsorted input is still
+    /// translated correctly, only more slowly.
+    #[must_use]
+    pub fn rva_to_internal_batch(&self, rvas: &[Rva]) -> Vec<Option<PdbInternalSectionOffset>> {
+        let mut cursor = self
+            .transformed_to_original
+            .as_ref()
+            .map(|omap| OMAPCursor::new(omap.records()));
+
+        rvas.iter()
+            .map(|&rva| {
+                let internal_rva = match cursor {
+                    Some(ref mut cursor) => cursor.lookup(rva.0)?,
+                    None => rva.0,
+                };
+
+                let (section, offset) = get_section_offset(&self.original_sections, internal_rva)?;
+                Some(PdbInternalSectionOffset { section, offset })
+            })
+            .collect()
+    }
+
+    /// Returns the section headers of the executable.
+    ///
+    /// If the executable was transformed after linking, these are the transformed sections.
+    fn sections(&self) -> &[ImageSectionHeader] {
+        match self.transformed_sections {
+            Some(ref sections) => sections,
+            None => &self.original_sections,
+        }
+    }
+
+    /// Looks up a section of the executable by name, such as `".text"`.
+    ///
+    /// Returns the 1-based section index along with its header. If multiple sections share the
+    /// same name, the first one is returned.
+    #[must_use]
+    pub fn section_by_name(&self, name: &str) -> Option<(u16, &ImageSectionHeader)> {
+        let (index, section) = self
+            .sections()
+            .iter()
+            .enumerate()
+            .find(|(_, section)| section.name() == name)?;
+
+        Some((index as u16 + 1, section))
+    }
+
+    /// Returns the range of RVAs covered by the section with the given 1-based index.
+    ///
+    /// The range spans the larger of the section's virtual size and its size on disk. Returns
+    /// `None` if there is no such section, or its end lies beyond the 32-bit address space.
+    #[must_use]
+    pub fn section_range(&self, section: u16) -> Option<Range<Rva>> {
+        let header = self.sections().get(usize::from(section).checked_sub(1)?)?;
+        let size = header.virtual_size.max(header.size_of_raw_data);
+        let end = header.virtual_address.checked_add(size)?;
+        Some(Rva(header.virtual_address)..Rva(end))
+    }
 }
 
 fn get_section_offset(sections: &[ImageSectionHeader], address: u32) -> Option<(u16, u32)> {
@@ -571,6 +687,25 @@ impl PdbInternalSectionOffset {
             .map(PdbInternalRva)
     }
 
+    /// Resolves a PDB-internal Relative Virtual Address using `S_SECTION` records.
+    ///
+    /// This is an alternative to [`to_internal_rva`](Self::to_internal_rva) for when the section
+    /// headers are not available, for instance when the PDB is inspected without its executable
+    /// and lacks a section headers stream. The `sections` are the records in the symbols of the
+    /// `* Linker *` module, see [`section_symbols`](crate::section_symbols).
+    ///
+    /// `S_SECTION` records describe the executable as it was emitted by the linker. If the
+    /// executable was later rewritten and the PDB contains OMAP tables, the result may therefore
+    /// differ from the RVA obtained through an [`AddressMap`], and no OMAP translation is applied.
+    #[must_use]
+    pub fn to_internal_rva_from_sections(
+        self,
+        sections: &[SectionSymbol],
+    ) -> Option<PdbInternalRva> {
+        let section = sections.iter().find(|s| s.isec == self.section)?;
+        section.rva.checked_add(self.offset).map(PdbInternalRva)
+    }
+
     /// Resolves the section offset in the PE headers.
     #[must_use]
     pub fn to_section_offset(self, translator: &AddressMap<'_>) -> Option<SectionOffset> {
@@ -607,4 +742,63 @@ mod tests {
         // https://github.com/willglynn/pdb/issues/87
         assert_eq!(get_virtual_address(&sections, 0, 0x1234), None);
     }
+
+    #[test]
+    fn test_section_range_overflow() {
+        let address_map = AddressMap {
+            original_sections: vec![
+                ImageSectionHeader {
+                    virtual_address: 0x1000,
+                    virtual_size: 0x200,
+                    size_of_raw_data: 0x100,
+                    ..Default::default()
+                },
+                ImageSectionHeader {
+                    virtual_address: 0xffff_f000,
+                    virtual_size: 0x2000,
+                    ..Default::default()
+                },
+            ],
+            ..Default::default()
+        };
+
+        assert_eq!(address_map.section_range(1), Some(Rva(0x1000)..Rva(0x1200)));
+        assert_eq!(address_map.section_range(2), None);
+    }
+
+    #[test]
+    fn test_omap_cursor() {
+        let records = [
+            OMAPRecord::new(0x1000, 0x5000),
+            OMAPRecord::new(0x1010, 0),
+            OMAPRecord::new(0x1020, 0x2000),
+            OMAPRecord::new(0x1100, 0x3000),
+        ];
+
+        let lookup = |address: u32| {
+            let index = match records.binary_search_by_key(&address, |r| r.source_address()) {
+                Ok(i) => i,
+                Err(0) => return None,
+                Err(i) => i - 1,
+            };
+            let record = records[index];
+            (record.target_address() != 0).then(|| record.translate(address))
+        };
+
+        let sorted = [
+            0x0, 0xfff, 0x1000, 0x1008, 0x1010, 0x1020, 0x1024, 0x10ff, 0x1100, 0x9000,
+        ];
+        let mut cursor = OMAPCursor::new(&records);
+        for address in sorted {
+            assert_eq!(cursor.lookup(address), lookup(address), "{:#x}", address);
+        }
+
+        let unsorted = [0x1100, 0x1004, 0x0, 0x1028, 0x1014, 0x1000, 0x9000, 0x1020];
+        let mut cursor = OMAPCursor::new(&records);
+        for address in unsorted {
+            assert_eq!(cursor.lookup(address), lookup(address), "{:#x}", address);
+        }
+
+        assert_eq!(OMAPCursor::new(&[]).lookup(0x1000), None);
+    }
 }
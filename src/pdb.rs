@@ -5,21 +5,32 @@
 // http://opensource.org/licenses/MIT>, at your option. This file may not be
 // copied, modified, or distributed except according to those terms.
 
+use std::collections::btree_map::Entry;
+use std::collections::{BTreeMap, HashSet};
+use std::path::PathBuf;
+
 use fallible_iterator::FallibleIterator;
 
 use crate::dbi::{
-    DBIExtraStreams, DBIHeader, DBISectionMapItemFlag, DBISectionMapItemSectionType,
+    DBIExtraStreams, DBIHeader, DBIModuleInfo, DBISectionMapItemFlag, DBISectionMapItemSectionType,
     DebugInformation, Module,
 };
 use crate::framedata::FrameTable;
-use crate::modi::ModuleInfo;
-use crate::msf::{self, Msf, Stream};
+use crate::injected::{self, InjectedSource, InjectedSourceIter};
+use crate::linkinfo::LinkInfo;
+use crate::modi::{ModuleInfo, SourceLocation};
+use crate::modulemap::ModuleMap;
+use crate::msf::{self, Msf, Stream, StreamInfo, VerifyReport};
 use crate::omap::{AddressMap, OMAPTable};
 use crate::pdbi::PDBInformation;
 use crate::pe::{self, ImageSectionHeader};
 use crate::source::Source;
 use crate::strings::StringTable;
-use crate::symbol::SymbolTable;
+use crate::summary::BuildSummary;
+use crate::symbol::{
+    DataReferenceSymbol, DataSymbol, ManagedProcedureSymbol, ProcedureSymbol, SymbolData,
+    SymbolIter, SymbolTable, TokenReferenceSymbol, UserDefinedTypeSymbol,
+};
 use crate::tpi::{IdInformation, TypeInformation};
 use crate::{common::*, SectionCharacteristics};
 
@@ -67,6 +78,7 @@ impl<'s, S: Source<'s> + 's> PDB<'s, S> {
     ///
     /// * `Error::UnimplementedFeature` if the PDB file predates ~2002
     /// * `Error::UnrecognizedFileFormat` if the `Source` does not appear to be a PDB file
+    /// * `Error::UnsupportedCompression` if the PDB is stored in a compressed container
     /// * `Error::IoError` if returned by the `Source`
     /// * `Error::PageReferenceOutOfRange`, `Error::InvalidPageSize` if the PDB file seems corrupt
     pub fn open(source: S) -> Result<PDB<'s, S>>
@@ -80,6 +92,62 @@ impl<'s, S: Source<'s> + 's> PDB<'s, S> {
         })
     }
 
+    /// Checks the structure of the underlying MSF file.
+    ///
+    /// This verifies the fields of the MSF header, that the file is large enough to hold all pages
+    /// declared in the header, and that every stream only refers to pages within the file. Instead
+    /// of failing on the first problem, all problems found are collected into a [`VerifyReport`].
+    /// The contents of the streams are not validated.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # fn test() -> pdb2::Result<()> {
+    /// let file = std::fs::File::open("fixtures/self/foo.pdb")?;
+    /// let mut pdb = pdb2::PDB::open(file)?;
+    ///
+    /// let report = pdb.verify();
+    /// assert!(report.is_ok(), "{:?}", report.problems);
+    /// # Ok(())
+    /// # }
+    /// # test().unwrap()
+    /// ```
+    pub fn verify(&mut self) -> VerifyReport {
+        self.msf.verify()
+    }
+
+    /// Lists every stream of the MSF container with its size.
+    ///
+    /// The result contains one [`StreamInfo`] per stream number declared in the stream directory,
+    /// in order, including streams that are absent. This shows where the bulk of a PDB lives
+    /// without reading any of the streams.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # fn test() -> pdb2::Result<()> {
+    /// let file = std::fs::File::open("fixtures/self/foo.pdb")?;
+    /// let mut pdb = pdb2::PDB::open(file)?;
+    ///
+    /// let mut streams = pdb.stream_directory()?;
+    /// streams.sort_by_key(|stream| std::cmp::Reverse(stream.byte_size));
+    /// for stream in streams.iter().take(5) {
+    ///     println!("stream {}: {} bytes", stream.index, stream.byte_size);
+    /// }
+    /// # Ok(())
+    /// # }
+    /// # test().unwrap()
+    /// ```
+    ///
+    /// # Errors
+    ///
+    /// * `Error::IoError` if returned by the `Source`
+    /// * `Error::PageReferenceOutOfRange` if the PDB file seems corrupt
+    /// * `Error::UnexpectedEof` if the stream directory is truncated
+    pub fn stream_directory(&mut self) -> Result<Vec<StreamInfo>> {
+        self.msf.stream_directory()
+    }
+
     /// Retrieve the `PDBInformation` for this PDB.
     ///
     /// The `PDBInformation` object contains the GUID and age fields that can be used to verify
@@ -95,20 +163,41 @@ impl<'s, S: Source<'s> + 's> PDB<'s, S> {
         PDBInformation::parse(stream)
     }
 
+    /// Returns a stable 64-bit hash identifying this PDB.
+    ///
+    /// The hash combines the GUID and age from the [`PDBInformation`], which together form the
+    /// canonical identity of a PDB and are what a binary references in its debug directory. Two
+    /// PDBs produced by the same link therefore hash equally. To tell apart rebuilt PDBs that
+    /// reuse the same GUID, compare the fingerprints of the relevant streams instead, obtained via
+    /// [`raw_stream`](Self::raw_stream) and `Stream::fingerprint`.
+    ///
+    /// # Errors
+    ///
+    /// * `Error::StreamNotFound` if the PDB somehow does not contain the PDB information stream
+    /// * `Error::IoError` if returned by the `Source`
+    /// * `Error::PageReferenceOutOfRange` if the PDB file seems corrupt
+    pub fn content_hash(&mut self) -> Result<u64> {
+        let info = self.pdb_information()?;
+        let age = info.age.to_le_bytes();
+        Ok(fnv1a_64([&info.guid.as_bytes()[..], &age[..]]))
+    }
+
     /// Retrieve the `TypeInformation` for this PDB.
     ///
     /// The `TypeInformation` object owns a `SourceView` for the type information ("TPI") stream.
     /// This is usually the single largest stream of the PDB file.
     ///
+    /// If the PDB does not contain the type information stream, an empty `TypeInformation` is
+    /// returned. Use `is_empty()` to detect this case.
+    ///
     /// # Errors
     ///
-    /// * `Error::StreamNotFound` if the PDB does not contain the type information stream
     /// * `Error::IoError` if returned by the `Source`
     /// * `Error::PageReferenceOutOfRange` if the PDB file seems corrupt
     /// * `Error::InvalidTypeInformationHeader` if the type information stream header was not
     ///   understood
     pub fn type_information(&mut self) -> Result<TypeInformation<'s>> {
-        let stream = self.msf.get(TPI_STREAM, None)?;
+        let stream = self.optional_stream(TPI_STREAM)?;
         TypeInformation::parse(stream)
     }
 
@@ -116,15 +205,17 @@ impl<'s, S: Source<'s> + 's> PDB<'s, S> {
     ///
     /// The `IdInformation` object owns a `SourceView` for the type information ("IPI") stream.
     ///
+    /// If the PDB does not contain the id information stream, an empty `IdInformation` is
+    /// returned. Use `is_empty()` to detect this case.
+    ///
     /// # Errors
     ///
-    /// * `Error::StreamNotFound` if the PDB does not contain the id information stream
     /// * `Error::IoError` if returned by the `Source`
     /// * `Error::PageReferenceOutOfRange` if the PDB file seems corrupt
     /// * `Error::InvalidTypeInformationHeader` if the id information stream header was not
     ///   understood
     pub fn id_information(&mut self) -> Result<IdInformation<'s>> {
-        let stream = self.msf.get(IPI_STREAM, None)?;
+        let stream = self.optional_stream(IPI_STREAM)?;
         IdInformation::parse(stream)
     }
 
@@ -147,6 +238,21 @@ impl<'s, S: Source<'s> + 's> PDB<'s, S> {
         Ok(debug_info)
     }
 
+    /// Returns the number of modules in this PDB.
+    ///
+    /// This is a shortcut for [`DebugInformation::module_count`] on the result of
+    /// [`debug_information`](Self::debug_information).
+    ///
+    /// # Errors
+    ///
+    /// * `Error::StreamNotFound` if the PDB somehow does not contain the debug information stream
+    /// * `Error::IoError` if returned by the `Source`
+    /// * `Error::PageReferenceOutOfRange` if the PDB file seems corrupt
+    /// * `Error::UnexpectedEof` if the file info substream is truncated
+    pub fn debug_module_count(&mut self) -> Result<usize> {
+        self.debug_information()?.module_count()
+    }
+
     fn dbi_header(&mut self) -> Result<DBIHeader> {
         // see if we've already got a header
         if let Some(ref h) = self.dbi_header {
@@ -161,6 +267,150 @@ impl<'s, S: Source<'s> + 's> PDB<'s, S> {
         Ok(header)
     }
 
+    /// Maps `f` over all modules in parallel.
+    ///
+    /// The module info streams are read from the `Source` up front, one after another, and then
+    /// each module is handed to `f` on the rayon thread pool along with its [`ModuleInfo`], or
+    /// `None` if the module has no debug information. Every invocation of `f` owns its
+    /// `ModuleInfo`, so it can freely create a `SymbolIter` or `LineProgram` from it.
+    ///
+    /// Results are returned in the same order as [`DebugInformation::modules`].
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use pdb2::FallibleIterator;
+    /// #
+    /// # fn test() -> pdb2::Result<()> {
+    /// let file = std::fs::File::open("fixtures/self/foo.pdb")?;
+    /// let mut pdb = pdb2::PDB::open(file)?;
+    ///
+    /// let counts = pdb.par_modules(|_module, info| match info {
+    ///     Some(info) => info.symbols()?.count(),
+    ///     None => Ok(0),
+    /// })?;
+    ///
+    /// let total: usize = counts.into_iter().collect::<pdb2::Result<Vec<_>>>()?.iter().sum();
+    /// assert!(total > 0);
+    /// # Ok(())
+    /// # }
+    /// # test().expect("test");
+    /// ```
+    ///
+    /// # Errors
+    ///
+    /// * `Error::StreamNotFound` if the PDB does not contain the debug information stream
+    /// * `Error::IoError` if returned by the `Source`
+    /// * `Error::PageReferenceOutOfRange` if the PDB file seems corrupt
+    #[cfg(feature = "rayon")]
+    pub fn par_modules<F, R>(&mut self, f: F) -> Result<Vec<R>>
+    where
+        F: Fn(&Module<'_>, Option<ModuleInfo<'s>>) -> R + Sync,
+        R: Send,
+    {
+        use rayon::prelude::*;
+
+        let dbi = self.debug_information()?;
+        let mut modules = Vec::new();
+        let mut iter = dbi.modules()?;
+        while let Some(module) = iter.next()? {
+            let info = self.module_info(&module)?;
+            modules.push((module, info));
+        }
+
+        Ok(modules
+            .into_par_iter()
+            .map(|(module, info)| f(&module, info))
+            .collect())
+    }
+
+    /// Returns an iterator over the procedures of all modules.
+    ///
+    /// This walks the symbol stream of every module in the order of
+    /// [`DebugInformation::modules`] and yields each [`ProcedureSymbol`] together with the index of
+    /// the module it was found in. Module streams are loaded lazily as the iterator advances.
+    ///
+    /// Procedures are usually also referenced from the global symbol table through
+    /// `S_PROCREF` records. Deduplicating against those is left to the caller.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use pdb2::FallibleIterator;
+    /// #
+    /// # fn test() -> pdb2::Result<usize> {
+    /// let file = std::fs::File::open("fixtures/self/foo.pdb")?;
+    /// let mut pdb = pdb2::PDB::open(file)?;
+    ///
+    /// let mut procedures = pdb.all_procedures()?;
+    /// while let Some((module_index, procedure)) = procedures.next()? {
+    ///     println!("{} in module {}", procedure.name, module_index);
+    /// }
+    /// # Ok(procedures.count()?)
+    /// # }
+    /// # test().expect("test");
+    /// ```
+    ///
+    /// # Errors
+    ///
+    /// * `Error::StreamNotFound` if the PDB does not contain the debug information stream
+    /// * `Error::IoError` if returned by the `Source`
+    /// * `Error::PageReferenceOutOfRange` if the PDB file seems corrupt
+    pub fn all_procedures(&mut self) -> Result<ProcedureIter<'_, 's, S>> {
+        let debug_info = self.debug_information()?;
+        let modules = debug_info
+            .modules()?
+            .map(|module| Ok(*module.info()))
+            .collect()?;
+
+        Ok(ProcedureIter {
+            pdb: self,
+            modules,
+            next_module: 0,
+            current: None,
+        })
+    }
+
+    /// Lists the object and library files holding the debug information of a mini PDB.
+    ///
+    /// PDBs linked with `/DEBUG:FASTLINK` only contain minimal debug information and refer to the
+    /// original `.obj` and `.lib` files for the rest, so reading symbols and types from the PDB
+    /// alone yields incomplete results. This returns the distinct object file names of all modules
+    /// in the order they first appear, skipping linker-synthesized modules and import stubs.
+    ///
+    /// If the PDB does not declare minimal debug information in its [`PdbFeatures`], it is
+    /// self-contained and the returned list is empty.
+    ///
+    /// # Errors
+    ///
+    /// * `Error::StreamNotFound` if the PDB does not contain the debug information stream
+    /// * `Error::IoError` if returned by the `Source`
+    /// * `Error::PageReferenceOutOfRange` if the PDB file seems corrupt
+    ///
+    /// [`PdbFeatures`]: crate::PdbFeatures
+    pub fn external_debug_sources(&mut self) -> Result<Vec<PathBuf>> {
+        if !self.pdb_information()?.features()?.minimal_debug_info {
+            return Ok(Vec::new());
+        }
+
+        let debug_info = self.debug_information()?;
+        let mut sources = Vec::new();
+        let mut modules = debug_info.modules()?;
+        while let Some(module) = modules.next()? {
+            let object_file = module.object_file_name();
+            if object_file.is_empty() || module.module_name().starts_with("Import:") {
+                continue;
+            }
+
+            let path = PathBuf::from(object_file.as_ref());
+            if !sources.contains(&path) {
+                sources.push(path);
+            }
+        }
+
+        Ok(sources)
+    }
+
     /// Retrieve the global symbol table for this PDB.
     ///
     /// The `SymbolTable` object owns a `SourceView` for the symbol records stream. This is usually
@@ -233,7 +483,147 @@ impl<'s, S: Source<'s> + 's> PDB<'s, S> {
     pub fn module_info<'m>(&mut self, module: &Module<'m>) -> Result<Option<ModuleInfo<'s>>> {
         Ok(self
             .raw_stream(module.info().stream)?
-            .map(|stream| ModuleInfo::parse(stream, module)))
+            .map(|stream| ModuleInfo::parse(stream, module.info())))
+    }
+
+    /// Resolves a token reference to the managed procedure it refers to.
+    ///
+    /// [`TokenReferenceSymbol`]s in the global symbol table point at a [`ManagedProcedureSymbol`]
+    /// in the symbol stream of a module. This opens the referenced module and parses the procedure.
+    ///
+    /// Returns `None` if the reference does not name a module, the module has no symbols, or the
+    /// referenced symbol is not a managed procedure.
+    ///
+    /// # Errors
+    ///
+    /// * `Error::StreamNotFound` if the PDB does not contain the debug information stream
+    /// * `Error::IoError` if returned by the `Source`
+    /// * `Error::PageReferenceOutOfRange` if the PDB file seems corrupt
+    /// * `Error::UnexpectedEof` if the symbol index lies outside of the module's symbols
+    pub fn resolve_token_reference(
+        &mut self,
+        reference: &TokenReferenceSymbol,
+    ) -> Result<Option<ManagedProcedureSymbol>> {
+        match self.referenced_symbol(reference.module, reference.symbol_index)? {
+            Some(SymbolData::ManagedProcedure(procedure)) => Ok(Some(procedure)),
+            _ => Ok(None),
+        }
+    }
+
+    /// Resolves a data reference to the data symbol it refers to.
+    ///
+    /// [`DataReferenceSymbol`]s in the global symbol table point at a [`DataSymbol`] in the symbol
+    /// stream of a module. This opens the referenced module and parses the data symbol.
+    ///
+    /// Returns `None` if the reference does not name a module, the module has no symbols, or the
+    /// referenced symbol is not a data symbol.
+    ///
+    /// # Errors
+    ///
+    /// * `Error::StreamNotFound` if the PDB does not contain the debug information stream
+    /// * `Error::IoError` if returned by the `Source`
+    /// * `Error::PageReferenceOutOfRange` if the PDB file seems corrupt
+    /// * `Error::UnexpectedEof` if the symbol index lies outside of the module's symbols
+    pub fn resolve_data_reference(
+        &mut self,
+        reference: &DataReferenceSymbol,
+    ) -> Result<Option<DataSymbol>> {
+        match self.referenced_symbol(reference.module, reference.symbol_index)? {
+            Some(SymbolData::Data(data)) => Ok(Some(data)),
+            _ => Ok(None),
+        }
+    }
+
+    /// Collects the code and data symbols of the global symbol table into a map keyed by their
+    /// address.
+    ///
+    /// This works like [`SymbolTable::to_rva_map`], but also resolves the procedure references in
+    /// the global symbol table to the procedures in the module symbol streams. Procedures thus take
+    /// precedence over the public symbols at the same address. References that cannot be resolved
+    /// are skipped.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # fn test() -> pdb2::Result<()> {
+    /// let file = std::fs::File::open("fixtures/self/foo.pdb")?;
+    /// let mut pdb = pdb2::PDB::open(file)?;
+    ///
+    /// let symbols = pdb.global_symbol_rva_map()?;
+    /// if let Some((rva, symbol)) = symbols.range(..=pdb2::Rva(0x64f4)).next_back() {
+    ///     println!("{} at {}", symbol.name().unwrap_or_default(), rva);
+    /// }
+    /// # Ok(())
+    /// # }
+    /// # test().expect("test");
+    /// ```
+    ///
+    /// # Errors
+    ///
+    /// * `Error::StreamNotFound` if the PDB does not contain the debug information stream
+    /// * `Error::IoError` if returned by the `Source`
+    /// * `Error::PageReferenceOutOfRange` if the PDB file seems corrupt
+    /// * `Error::UnexpectedEof` if a symbol stream is truncated mid-record
+    pub fn global_symbol_rva_map(&mut self) -> Result<BTreeMap<Rva, SymbolData>> {
+        let address_map = self.address_map()?;
+        let global_symbols = self.global_symbols()?;
+        let debug_info = self.debug_information()?;
+        let modules: Vec<_> = debug_info.modules()?.collect()?;
+
+        // load every module referenced from the global symbol table once
+        let mut module_infos = BTreeMap::new();
+        global_symbols.to_rva_map_with(&address_map, |reference| {
+            let module_index = match reference.module {
+                Some(module_index) => module_index,
+                None => return Ok(None),
+            };
+
+            let module_info = match module_infos.entry(module_index) {
+                Entry::Occupied(entry) => entry.into_mut(),
+                Entry::Vacant(entry) => entry.insert(match modules.get(module_index) {
+                    Some(module) => self.module_info(module)?,
+                    None => None,
+                }),
+            };
+
+            let module_info = match module_info {
+                Some(module_info) => module_info,
+                None => return Ok(None),
+            };
+
+            match module_info.symbols_at(reference.symbol_index)?.next()? {
+                Some(symbol) => Ok(Some(symbol.parse_lenient())),
+                None => Ok(None),
+            }
+        })
+    }
+
+    /// Parses the symbol at `index` in the symbol stream of the module at index `module`.
+    fn referenced_symbol(
+        &mut self,
+        module: Option<usize>,
+        index: SymbolIndex,
+    ) -> Result<Option<SymbolData>> {
+        let module = match module {
+            Some(module) => module,
+            None => return Ok(None),
+        };
+
+        let debug_info = self.debug_information()?;
+        let module = match debug_info.modules()?.nth(module)? {
+            Some(module) => module,
+            None => return Ok(None),
+        };
+
+        let module_info = match self.module_info(&module)? {
+            Some(module_info) => module_info,
+            None => return Ok(None),
+        };
+
+        match module_info.symbols_at(index)?.next()? {
+            Some(symbol) => symbol.parse().map(Some),
+            None => Ok(None),
+        }
     }
 
     /// Retrieve the executable's section headers, as stored inside this PDB.
@@ -270,6 +660,15 @@ impl<'s, S: Source<'s> + 's> PDB<'s, S> {
         Ok(Some(headers))
     }
 
+    // Minimal or stripped PDBs may declare a fixed stream as the nil stream. Substitute an empty
+    // stream, which the stream parsers treat the same as a missing stream.
+    fn optional_stream(&mut self, stream_number: u32) -> Result<Stream<'s>> {
+        match self.msf.get(stream_number, None) {
+            Err(Error::StreamNotFound(_)) => Ok(Stream::empty()),
+            result => result,
+        }
+    }
+
     // If there are no section_headers in the file, attempt to synthesize sections
     // based on the section map. This seems to be necessary to handle NGEN-generated PDB
     // files (.ni.pdb from Crossgen2).
@@ -482,6 +881,253 @@ impl<'s, S: Source<'s> + 's> PDB<'s, S> {
         })
     }
 
+    /// Build an index that attributes addresses to the modules that contributed them.
+    ///
+    /// The [`ModuleMap`] combines the section contributions and module list of the DBI stream with
+    /// the [`AddressMap`]. It is built once and answers lookups by binary search, so it should be
+    /// reused rather than rebuilt for every address.
+    ///
+    /// # Errors
+    ///
+    /// * `Error::StreamNotFound` if the PDB somehow does not contain a DBI stream
+    /// * `Error::AddressMapNotFound` if the PDB has OMAP sections but no OMAP tables
+    /// * `Error::UnexpectedEof` if the DBI stream is truncated
+    pub fn module_map(&mut self) -> Result<ModuleMap<'s>> {
+        let debug_information = self.debug_information()?;
+        let address_map = self.address_map()?;
+        ModuleMap::new(&debug_information, address_map)
+    }
+
+    /// Returns the source file and line at which a procedure starts.
+    ///
+    /// The module containing the procedure is looked up through the section contributions, see
+    /// [`module_map`](Self::module_map). Its line program is then searched for the line record
+    /// covering the procedure's start offset.
+    ///
+    /// Returns `None` if no module contributed the procedure's code, or the module has no line
+    /// information for it.
+    ///
+    /// This rebuilds the module map and reloads the string table on every call. To look up the
+    /// locations of many procedures, use [`symbol_locations`](Self::symbol_locations) instead.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use pdb2::FallibleIterator;
+    /// # fn test() -> pdb2::Result<()> {
+    /// let file = std::fs::File::open("fixtures/self/foo.pdb")?;
+    /// let mut pdb = pdb2::PDB::open(file)?;
+    ///
+    /// let main = pdb
+    ///     .all_procedures()?
+    ///     .find(|(_, procedure)| Ok(procedure.name == "main"))?;
+    /// if let Some((_, main)) = main {
+    ///     if let Some(location) = pdb.symbol_location(&main)? {
+    ///         println!("main() at {}:{}", location.file, location.line);
+    ///     }
+    /// }
+    /// # Ok(())
+    /// # }
+    /// # test().unwrap()
+    /// ```
+    ///
+    /// # Errors
+    ///
+    /// * `Error::StreamNotFound` if the PDB somehow does not contain a DBI stream or string table
+    /// * `Error::IoError` if returned by the `Source`
+    /// * `Error::UnexpectedEof` if a stream is truncated
+    pub fn symbol_location(
+        &mut self,
+        procedure: &ProcedureSymbol,
+    ) -> Result<Option<SourceLocation>> {
+        let mut locations = self.symbol_locations(std::iter::once(procedure))?;
+        Ok(locations.pop().flatten())
+    }
+
+    /// Returns the source file and line at which each of the given procedures starts.
+    ///
+    /// This works like [`symbol_location`](Self::symbol_location), but builds the module map and
+    /// loads the string table only once, and reads the line program of every module only once.
+    /// The returned locations are in the same order as the procedures.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use pdb2::FallibleIterator;
+    /// # fn test() -> pdb2::Result<()> {
+    /// let file = std::fs::File::open("fixtures/self/foo.pdb")?;
+    /// let mut pdb = pdb2::PDB::open(file)?;
+    ///
+    /// let procedures: Vec<_> = pdb.all_procedures()?.collect()?;
+    /// let locations = pdb.symbol_locations(procedures.iter().map(|(_, procedure)| procedure))?;
+    /// for ((_, procedure), location) in procedures.iter().zip(locations) {
+    ///     if let Some(location) = location {
+    ///         println!("{}() at {}:{}", procedure.name, location.file, location.line);
+    ///     }
+    /// }
+    /// # Ok(())
+    /// # }
+    /// # test().unwrap()
+    /// ```
+    ///
+    /// # Errors
+    ///
+    /// * `Error::StreamNotFound` if the PDB somehow does not contain a DBI stream or string table
+    /// * `Error::IoError` if returned by the `Source`
+    /// * `Error::UnexpectedEof` if a stream is truncated
+    pub fn symbol_locations<'p, I>(&mut self, procedures: I) -> Result<Vec<Option<SourceLocation>>>
+    where
+        I: IntoIterator<Item = &'p ProcedureSymbol>,
+    {
+        let module_map = self.module_map()?;
+        let debug_info = self.debug_information()?;
+        let modules: Vec<_> = debug_info.modules()?.collect()?;
+        let string_table = self.string_table()?;
+
+        let procedures: Vec<_> = procedures
+            .into_iter()
+            .map(|procedure| {
+                let module = module_map.module_for_internal_offset(procedure.offset);
+                (procedure.offset, module.map(|module| module.index))
+            })
+            .collect();
+
+        // load every module that contributed one of the procedures once
+        let mut module_infos = BTreeMap::new();
+        for &(_, module_index) in &procedures {
+            let module_index = match module_index {
+                Some(module_index) if !module_infos.contains_key(&module_index) => module_index,
+                _ => continue,
+            };
+
+            let module_info = match modules.get(module_index) {
+                Some(module) => self.module_info(module)?,
+                None => None,
+            };
+            module_infos.insert(module_index, module_info);
+        }
+
+        let mut line_programs = BTreeMap::new();
+        for (module_index, module_info) in &module_infos {
+            if let Some(module_info) = module_info {
+                line_programs.insert(*module_index, module_info.line_program()?);
+            }
+        }
+
+        procedures
+            .into_iter()
+            .map(|(offset, module_index)| {
+                let line_program = match module_index.and_then(|index| line_programs.get(&index)) {
+                    Some(line_program) => line_program,
+                    None => return Ok(None),
+                };
+
+                let (file_info, line) = match line_program.location_of(offset)? {
+                    Some(location) => location,
+                    None => return Ok(None),
+                };
+
+                let file = file_info.name.to_string_lossy(&string_table)?.into_owned();
+                Ok(Some(SourceLocation { file, line }))
+            })
+            .collect()
+    }
+
+    /// Collects the distinct user defined types declared in this PDB.
+    ///
+    /// `S_UDT` records are repeated in the global symbol table and in the symbol stream of every
+    /// module that uses a type. This reads all of them and deduplicates records with the same name
+    /// and type index, so that each typedef or type name is listed once.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # fn test() -> pdb2::Result<()> {
+    /// let file = std::fs::File::open("fixtures/self/foo.pdb")?;
+    /// let mut pdb = pdb2::PDB::open(file)?;
+    ///
+    /// let mut udts: Vec<_> = pdb.unique_udts()?.into_iter().collect();
+    /// udts.sort_by(|a, b| a.name.cmp(&b.name));
+    /// for udt in &udts {
+    ///     println!("{} = {}", udt.name, udt.type_index);
+    /// }
+    /// # Ok(())
+    /// # }
+    /// # test().unwrap()
+    /// ```
+    ///
+    /// # Errors
+    ///
+    /// * `Error::StreamNotFound` if the PDB somehow does not contain a DBI stream
+    /// * `Error::IoError` if returned by the `Source`
+    /// * `Error::UnexpectedEof` if a stream is truncated
+    pub fn unique_udts(&mut self) -> Result<HashSet<UserDefinedTypeSymbol>> {
+        fn collect(
+            mut symbols: SymbolIter<'_>,
+            udts: &mut HashSet<UserDefinedTypeSymbol>,
+        ) -> Result<()> {
+            while let Some(symbol) = symbols.next()? {
+                if let SymbolData::UserDefinedType(udt) = symbol.parse_lenient() {
+                    udts.insert(udt);
+                }
+            }
+            Ok(())
+        }
+
+        let mut udts = HashSet::new();
+        collect(self.global_symbols()?.iter(), &mut udts)?;
+
+        let debug_information = self.debug_information()?;
+        let mut modules = debug_information.modules()?;
+        while let Some(module) = modules.next()? {
+            if let Some(info) = self.module_info(&module)? {
+                collect(info.symbols()?, &mut udts)?;
+            }
+        }
+
+        Ok(udts)
+    }
+
+    /// Summarizes how the modules of this PDB were built.
+    ///
+    /// This reads the symbols of every module and aggregates their source languages, compilers
+    /// and optimization settings into a [`BuildSummary`].
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # fn test() -> pdb2::Result<()> {
+    /// let file = std::fs::File::open("fixtures/self/foo.pdb")?;
+    /// let mut pdb = pdb2::PDB::open(file)?;
+    ///
+    /// let summary = pdb.build_summary()?;
+    /// for (compiler, count) in &summary.compilers {
+    ///     println!("{}: {} modules", compiler, count);
+    /// }
+    /// # Ok(())
+    /// # }
+    /// # test().unwrap()
+    /// ```
+    ///
+    /// # Errors
+    ///
+    /// * `Error::StreamNotFound` if the PDB somehow does not contain a DBI stream
+    /// * `Error::IoError` if returned by the `Source`
+    /// * `Error::UnexpectedEof` if a stream is truncated
+    pub fn build_summary(&mut self) -> Result<BuildSummary> {
+        let debug_information = self.debug_information()?;
+        let mut summary = BuildSummary::default();
+
+        let mut modules = debug_information.modules()?;
+        while let Some(module) = modules.next()? {
+            if let Some(info) = self.module_info(&module)? {
+                summary.add_module(info.symbols()?)?;
+            }
+        }
+
+        Ok(summary)
+    }
+
     /// Retrieve the global string table of this PDB.
     ///
     /// Long strings, such as file names, are stored in a global deduplicated string table. They are
@@ -525,6 +1171,87 @@ impl<'s, S: Source<'s> + 's> PDB<'s, S> {
         StringTable::parse(stream)
     }
 
+    /// Returns the source files injected into this PDB.
+    ///
+    /// Injected sources are listed in the `/src/headerblock` stream, and the contents of each file
+    /// are stored in the named stream `/src/files/<virtual path>`. Use
+    /// [`InjectedSource::contents`] to read the contents of a file. If the PDB does not contain any
+    /// injected sources, the iterator is empty.
+    ///
+    /// # Errors
+    ///
+    /// * `Error::StreamNameNotFound` if the stream of an injected source is missing
+    /// * `Error::IoError` if returned by the `Source`
+    /// * `Error::PageReferenceOutOfRange` if the PDB file seems corrupt
+    /// * `Error::UnexpectedEof` if the header block or a file name is malformed
+    pub fn injected_sources(&mut self) -> Result<InjectedSourceIter<'s>> {
+        let header_block = match self.named_stream(b"/src/headerblock") {
+            Ok(stream) => stream,
+            Err(Error::StreamNameNotFound) => return Ok(InjectedSourceIter::new(Vec::new())),
+            Err(e) => return Err(e),
+        };
+
+        let entries = injected::parse_header_block(header_block.as_slice())?;
+        if entries.is_empty() {
+            return Ok(InjectedSourceIter::new(Vec::new()));
+        }
+
+        let strings = self.string_table()?;
+        let mut sources = Vec::with_capacity(entries.len());
+        for entry in entries {
+            let virtual_path = strings.get(entry.virtual_name)?.to_string().into_owned();
+            let file_name = strings.get(entry.file_name)?.to_string().into_owned();
+            let object_name = strings.get(entry.object_name)?.to_string().into_owned();
+
+            // Writers differ in whether they lowercase the virtual path in the stream name.
+            let stream_name = format!("/src/files/{virtual_path}");
+            let data = match self.named_stream(stream_name.as_bytes()) {
+                Err(Error::StreamNameNotFound) => {
+                    self.named_stream(stream_name.to_lowercase().as_bytes())?
+                }
+                result => result?,
+            };
+
+            sources.push(InjectedSource {
+                virtual_path,
+                file_name,
+                object_name,
+                compression: entry.compression,
+                crc: entry.crc,
+                file_size: entry.file_size,
+                is_virtual: entry.is_virtual,
+                data,
+            });
+        }
+
+        Ok(InjectedSourceIter::new(sources))
+    }
+
+    /// Retrieve the link information from the `/LinkInfo` stream.
+    ///
+    /// Returns `None` if the PDB does not contain this stream or if it is empty, which is the case
+    /// for PDBs written by most recent linkers.
+    ///
+    /// # Errors
+    ///
+    /// * `Error::StreamNotFound` if the PDB does not contain the stream referred to
+    /// * `Error::IoError` if returned by the `Source`
+    /// * `Error::PageReferenceOutOfRange` if the PDB file seems corrupt
+    /// * `Error::InvalidStreamLength` or `Error::UnexpectedEof` if the link information is malformed
+    pub fn link_info(&mut self) -> Result<Option<LinkInfo>> {
+        let stream = match self.named_stream(b"/LinkInfo") {
+            Ok(stream) => stream,
+            Err(Error::StreamNameNotFound) => return Ok(None),
+            Err(e) => return Err(e),
+        };
+
+        if stream.as_slice().is_empty() {
+            return Ok(None);
+        }
+
+        LinkInfo::parse(stream.as_slice()).map(Some)
+    }
+
     /// Retrieve a stream by its index to read its contents as bytes.
     ///
     /// # Errors
@@ -593,6 +1320,60 @@ impl<'s, S: Source<'s> + 's> PDB<'s, S> {
     }
 }
 
+/// An iterator over the procedures of all modules in a PDB.
+///
+/// Created by [`PDB::all_procedures`].
+pub struct ProcedureIter<'p, 's, S> {
+    pdb: &'p mut PDB<'s, S>,
+    modules: Vec<DBIModuleInfo>,
+    next_module: usize,
+    current: Option<(usize, ModuleInfo<'s>, Option<SymbolIndex>)>,
+}
+
+impl<'s, S: Source<'s> + 's> FallibleIterator for ProcedureIter<'_, 's, S> {
+    type Item = (usize, ProcedureSymbol);
+    type Error = Error;
+
+    fn next(&mut self) -> Result<Option<Self::Item>> {
+        loop {
+            if let Some((module_index, info, position)) = &mut self.current {
+                let mut symbols = match position {
+                    Some(index) => info.symbols_at(*index)?,
+                    None => info.symbols()?,
+                };
+
+                while let Some(symbol) = symbols.next()? {
+                    // resume after this record, skipping its length prefix
+                    let next = symbol.index().0 + symbol.raw_bytes().len() as u32 + 2;
+                    *position = Some(SymbolIndex(next));
+
+                    match symbol.parse() {
+                        Ok(SymbolData::Procedure(procedure)) => {
+                            return Ok(Some((*module_index, procedure)))
+                        }
+                        Ok(_) | Err(Error::UnimplementedSymbolKind(_)) => (),
+                        Err(e) => return Err(e),
+                    }
+                }
+
+                self.current = None;
+            }
+
+            let module_index = self.next_module;
+            let module = match self.modules.get(module_index) {
+                Some(module) => module,
+                None => return Ok(None),
+            };
+            self.next_module += 1;
+
+            if let Some(stream) = self.pdb.raw_stream(module.stream)? {
+                let info = ModuleInfo::parse(stream, module);
+                self.current = Some((module_index, info, None));
+            }
+        }
+    }
+}
+
 impl StreamIndex {
     /// Load the raw data of this stream from the PDB.
     ///
@@ -143,6 +143,89 @@ impl<'s> PDBInformation<'s> {
 
         Ok(StreamNames { names })
     }
+
+    /// Returns the feature flags declared at the end of the PDB information stream.
+    ///
+    /// Newer PDBs list feature signatures after the named stream map, which describe for example
+    /// whether the PDB contains an IPI stream. Older PDBs do not declare any features, in which case
+    /// all flags are `false`.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # fn test() -> pdb2::Result<()> {
+    /// let file = std::fs::File::open("fixtures/self/foo.pdb")?;
+    /// let mut pdb = pdb2::PDB::open(file)?;
+    /// let info = pdb.pdb_information()?;
+    /// if info.features()?.has_id_stream() {
+    ///     let ipi = pdb.id_information()?;
+    /// }
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn features(&self) -> Result<PdbFeatures> {
+        let mut buf = self.stream.parse_buffer();
+
+        // Skip the names block and the name map, see `stream_names` for the layout.
+        buf.take(self.names_offset + self.names_size)?;
+        let count = buf.parse_u32()?;
+        let _entries_size = buf.parse_u32()?;
+        let ok_words = buf.parse_u32()?;
+        buf.take(ok_words as usize * mem::size_of::<u32>())?;
+        let deleted_words = buf.parse_u32()?;
+        buf.take(deleted_words as usize * mem::size_of::<u32>())?;
+        buf.take(count as usize * 2 * mem::size_of::<u32>())?;
+
+        // https://github.com/llvm/llvm-project/blob/llvmorg-17.0.1/llvm/lib/DebugInfo/PDB/Native/InfoStream.cpp#L58-L85
+        let mut features = PdbFeatures::default();
+        while buf.len() >= mem::size_of::<u32>() {
+            match buf.parse_u32()? {
+                PDB_FEATURE_VC110 => {
+                    // no other flags follow a VC110 signature
+                    features.vc110 = true;
+                    break;
+                }
+                PDB_FEATURE_VC140 => features.vc140 = true,
+                PDB_FEATURE_NO_TYPE_MERGE => features.no_type_merge = true,
+                PDB_FEATURE_MINIMAL_DEBUG_INFO => features.minimal_debug_info = true,
+                _ => (),
+            }
+        }
+
+        Ok(features)
+    }
+}
+
+const PDB_FEATURE_VC110: u32 = 20_091_201;
+const PDB_FEATURE_VC140: u32 = 20_140_508;
+const PDB_FEATURE_NO_TYPE_MERGE: u32 = 0x4D54_4F4E; // "MTON"
+const PDB_FEATURE_MINIMAL_DEBUG_INFO: u32 = 0x494E_494D; // "MINI"
+
+/// Feature flags declared in the PDB information stream.
+///
+/// See [`PDBInformation::features`].
+#[non_exhaustive]
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq)]
+pub struct PdbFeatures {
+    /// The PDB was written by the VC110 toolchain (Visual Studio 2012).
+    pub vc110: bool,
+    /// The PDB was written by the VC140 toolchain (Visual Studio 2015) or newer.
+    pub vc140: bool,
+    /// Types were not merged into the TPI stream, as with `/DEBUG:FASTLINK`.
+    pub no_type_merge: bool,
+    /// The PDB only contains minimal debug information, as with `/DEBUG:FASTLINK`.
+    pub minimal_debug_info: bool,
+}
+
+impl PdbFeatures {
+    /// Returns whether the PDB contains an IPI (id information) stream.
+    ///
+    /// If this returns `false`, [`PDB::id_information`](crate::PDB::id_information) returns an
+    /// empty table, since a missing or nil IPI stream is treated as containing no records.
+    #[must_use]
+    pub fn has_id_stream(&self) -> bool {
+        self.vc110 || self.vc140
+    }
 }
 
 /// A named stream contained within the PDB file.
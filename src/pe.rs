@@ -98,7 +98,8 @@ pub const IMAGE_SCN_MEM_WRITE: u32 = 0x8000_0000;
 /// These are defined by Microsoft as [`IMAGE_SCN_`] constants.
 ///
 /// [`IMAGE_SCN_`]: https://docs.microsoft.com/en-us/windows/win32/api/winnt/ns-winnt-image_section_header
-#[derive(Clone, Copy, Eq, Default, PartialEq)]
+#[derive(Clone, Copy, Eq, Default, Hash, PartialEq)]
+#[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
 pub struct SectionCharacteristics(pub u32);
 
 impl SectionCharacteristics {
@@ -1,6 +1,7 @@
 #![allow(unused, non_upper_case_globals, non_camel_case_types, missing_docs)]
 
 use crate::common::{Error, Result};
+use crate::symbol::InstructionSet;
 use crate::CPUType;
 use crate::Error::UnknownRegister;
 use scroll::ctx::TryFromCtx;
@@ -73,6 +74,29 @@ impl fmt::Display for Register {
 }
 
 impl Register {
+    /// Returns the name of the register without its register set, such as `RBP`.
+    pub fn name(&self) -> String {
+        match self {
+            Register::All(r) => r.to_string(),
+            Register::X86(r) => r.to_string(),
+            Register::M68K(r) => r.to_string(),
+            Register::MIPS(r) => r.to_string(),
+            Register::ALPHAAXP(r) => r.to_string(),
+            Register::PowerPC(r) => r.to_string(),
+            Register::Java(r) => r.to_string(),
+            Register::HitachiSH(r) => r.to_string(),
+            Register::ARM(r) => r.to_string(),
+            Register::ARM64(r) => r.to_string(),
+            Register::IA64(r) => r.to_string(),
+            Register::Tricore(r) => r.to_string(),
+            Register::AM33(r) => r.to_string(),
+            Register::MitsubishiM32R(r) => r.to_string(),
+            Register::SuperHSHMedia(r) => r.to_string(),
+            Register::AMD64(r) => r.to_string(),
+            Register::HLSL(r) => r.to_string(),
+        }
+    }
+
     pub fn new(value: crate::Register, cpu: crate::CPUType) -> Result<Self> {
         match cpu {
             CPUType::Intel8080
@@ -137,6 +161,46 @@ impl Register {
     }
 }
 
+impl crate::Register {
+    /// Returns the stack pointer register of the given CPU, if known.
+    #[must_use]
+    pub fn stack_pointer(cpu: CPUType) -> Option<Self> {
+        match cpu.instruction_set() {
+            InstructionSet::X86 => Some(Self::X86_ESP),
+            InstructionSet::X64 => Some(Self::X64_RSP),
+            InstructionSet::Arm | InstructionSet::Thumb => Some(Self::ARM_SP),
+            InstructionSet::Arm64 => Some(Self::ARM64_SP),
+            _ => None,
+        }
+    }
+
+    /// Returns the conventional frame pointer register of the given CPU, if known.
+    ///
+    /// Whether a function actually uses a frame pointer depends on how it was compiled, see
+    /// [`FrameProcedureSymbol`](crate::FrameProcedureSymbol).
+    #[must_use]
+    pub fn frame_pointer(cpu: CPUType) -> Option<Self> {
+        match cpu.instruction_set() {
+            InstructionSet::X86 => Some(Self::X86_EBP),
+            InstructionSet::X64 => Some(Self::X64_RBP),
+            InstructionSet::Arm => Some(Self::ARM_R11),
+            InstructionSet::Thumb => Some(Self::ARM_R7),
+            InstructionSet::Arm64 => Some(Self::ARM64_FP),
+            _ => None,
+        }
+    }
+
+    /// Returns the lowercase name of this register on the given CPU, such as `"rbp"`.
+    ///
+    /// Returns `None` if the register number is not known for the CPU. See
+    /// [`Register`] for the full register sets.
+    #[must_use]
+    pub fn name(self, cpu: CPUType) -> Option<String> {
+        let register = Register::new(self, cpu).ok()?;
+        Some(register.name().to_lowercase())
+    }
+}
+
 /// Register subset shared by all processor types,
 #[non_exhaustive]
 #[derive(Copy, Clone, Debug, Eq, PartialEq)]
@@ -9664,3 +9728,41 @@ impl<'a> TryFromCtx<'a, Endian> for HLSLRegister {
         Ok((v.try_into()?, l))
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use crate::common::Register;
+    use crate::symbol::CPUType;
+
+    #[test]
+    fn test_name() {
+        assert_eq!(Register(334).name(CPUType::X64).as_deref(), Some("rbp"));
+        assert_eq!(Register(21).name(CPUType::Pentium3).as_deref(), Some("esp"));
+        assert_eq!(Register(0xffff).name(CPUType::X64), None);
+    }
+
+    #[test]
+    fn test_by_cpu() {
+        assert_eq!(
+            Register::frame_pointer(CPUType::X64),
+            Some(Register::X64_RBP)
+        );
+        assert_eq!(
+            Register::stack_pointer(CPUType::X64),
+            Some(Register::X64_RSP)
+        );
+        assert_eq!(
+            Register::stack_pointer(CPUType::Pentium3),
+            Some(Register::X86_ESP)
+        );
+        assert_eq!(
+            Register::frame_pointer(CPUType::ARM64),
+            Some(Register::ARM64_FP)
+        );
+        assert_eq!(
+            Register::frame_pointer(CPUType::Thumb),
+            Some(Register::ARM_R7)
+        );
+        assert_eq!(Register::stack_pointer(CPUType::Alpha), None);
+    }
+}
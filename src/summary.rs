@@ -0,0 +1,88 @@
+// Copyright 2017 pdb Developers
+//
+// Licensed under the Apache License, Version 2.0, <LICENSE-APACHE or
+// http://apache.org/licenses/LICENSE-2.0> or the MIT license <LICENSE-MIT or
+// http://opensource.org/licenses/MIT>, at your option. This file may not be
+// copied, modified, or distributed except according to those terms.
+
+//! Facilities for summarizing how the modules of a PDB were built.
+
+use std::collections::{BTreeMap, HashMap};
+use std::ops::RangeInclusive;
+
+use fallible_iterator::FallibleIterator;
+
+use crate::common::*;
+use crate::symbol::{CompilerVersion, SourceLanguage, SymbolData, SymbolIter};
+
+/// Aggregated build information of all modules in a PDB.
+///
+/// Built by [`PDB::build_summary`](crate::PDB::build_summary) from the `S_COMPILE2` or `S_COMPILE3`
+/// record of every module, see [`CompileFlagsSymbol`](crate::CompileFlagsSymbol), and the
+/// `S_FRAMEPROC` records of their procedures, see
+/// [`FrameProcedureSymbol`](crate::FrameProcedureSymbol). Modules without compile flags, such as
+/// import modules, are not counted.
+#[derive(Clone, Debug, Default, Eq, PartialEq)]
+pub struct BuildSummary {
+    /// The number of modules with compile flags.
+    pub modules: usize,
+    /// The number of modules per source language.
+    pub languages: HashMap<SourceLanguage, usize>,
+    /// The number of modules per compiler, keyed by the compiler's display name.
+    pub compilers: HashMap<String, usize>,
+    /// The number of modules per compiler backend version.
+    pub backend_versions: BTreeMap<CompilerVersion, usize>,
+    /// The number of modules containing at least one procedure optimized for speed.
+    pub optimized_for_speed: usize,
+    /// The number of modules containing at least one procedure compiled with profile-guided
+    /// optimization.
+    pub profile_guided: usize,
+}
+
+impl BuildSummary {
+    /// Returns the lowest and highest compiler backend version of all modules.
+    #[must_use]
+    pub fn backend_version_range(&self) -> Option<RangeInclusive<CompilerVersion>> {
+        let (&first, _) = self.backend_versions.iter().next()?;
+        let (&last, _) = self.backend_versions.iter().next_back()?;
+        Some(first..=last)
+    }
+
+    /// Adds the symbols of a module to the summary.
+    pub(crate) fn add_module(&mut self, mut symbols: SymbolIter<'_>) -> Result<()> {
+        let mut compile_flags = None;
+        let mut optimized_for_speed = false;
+        let mut profile_guided = false;
+
+        while let Some(symbol) = symbols.next()? {
+            match symbol.parse_lenient() {
+                SymbolData::CompileFlags(flags) => compile_flags = Some(flags),
+                SymbolData::FrameProcedure(frame) => {
+                    optimized_for_speed |= frame.flags.opt_speed();
+                    profile_guided |= frame.flags.pogo_on();
+                }
+                _ => (),
+            }
+        }
+
+        let compile_flags = match compile_flags {
+            Some(compile_flags) => compile_flags,
+            None => return Ok(()),
+        };
+
+        self.modules += 1;
+        *self.languages.entry(compile_flags.language).or_default() += 1;
+        *self
+            .compilers
+            .entry(compile_flags.version_string)
+            .or_default() += 1;
+        *self
+            .backend_versions
+            .entry(compile_flags.backend_version)
+            .or_default() += 1;
+        self.optimized_for_speed += usize::from(optimized_for_speed);
+        self.profile_guided += usize::from(profile_guided);
+
+        Ok(())
+    }
+}
@@ -238,7 +238,8 @@ impl FallibleIterator for BinaryAnnotationsIter<'_> {
 /// stream. The X64 unwind code and the DWARF standard have a similar design.
 ///
 /// Binary annotations are primarily used as line programs for inline function calls.
-#[derive(Clone, Debug, Default, Eq, PartialEq)]
+#[derive(Clone, Debug, Default, Eq, Hash, PartialEq)]
+#[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
 pub struct BinaryAnnotations {
     data: Box<[u8]>,
 }
@@ -250,9 +251,14 @@ impl BinaryAnnotations {
         BinaryAnnotations { data: data.into() }
     }
 
+    /// Returns the number of heap bytes owned by these annotations.
+    pub(crate) fn heap_size(&self) -> usize {
+        self.data.len()
+    }
+
     /// Iterates through binary annotations.
     #[must_use]
-    pub fn iter(&self) -> BinaryAnnotationsIter {
+    pub fn iter(&self) -> BinaryAnnotationsIter<'_> {
         BinaryAnnotationsIter {
             buffer: ParseBuffer::from(self.data.as_ref()),
         }
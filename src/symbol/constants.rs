@@ -11,10 +11,15 @@
 
 #![allow(unused, non_upper_case_globals, non_camel_case_types)]
 
+use std::convert::TryFrom;
 use std::fmt;
 
 use scroll::{ctx::TryFromCtx, Endian};
 
+use crate::common::Error;
+
+use super::SymbolKind;
+
 pub const S_COMPILE: u16 = 0x0001; // Compile flags symbol
 pub const S_REGISTER_16T: u16 = 0x0002; // Register variable
 pub const S_CONSTANT_16T: u16 = 0x0003; // constant symbol
@@ -288,12 +293,273 @@ pub const S_DEFRANGE_REGISTER_REL_INDIR: u16 = 0x1177;
 pub const S_RECTYPE_MAX: u16 = 0x1178;
 pub const S_RECTYPE_PAD: u16 = 0x1278;
 
-/// These values correspond to the `CV_CPU_TYPE_e` enumeration, and are documented
-/// [on MSDN](https://msdn.microsoft.com/en-us/library/b2fc64ek.aspx).
-#[non_exhaustive]
-#[allow(missing_docs)]
-#[derive(Clone, Copy, Debug, Eq, PartialEq)]
-pub enum CPUType {
+/// Returns the name of the `S_*` constant for a symbol kind, such as `"S_GPROC32"`.
+///
+/// Returns `None` for kinds that are not known to this library.
+#[must_use]
+pub fn symbol_kind_name(kind: SymbolKind) -> Option<&'static str> {
+    Some(match kind {
+        S_COMPILE => "S_COMPILE",
+        S_REGISTER_16T => "S_REGISTER_16T",
+        S_CONSTANT_16T => "S_CONSTANT_16T",
+        S_UDT_16T => "S_UDT_16T",
+        S_SSEARCH => "S_SSEARCH",
+        S_END => "S_END",
+        S_SKIP => "S_SKIP",
+        S_CVRESERVE => "S_CVRESERVE",
+        S_OBJNAME_ST => "S_OBJNAME_ST",
+        S_ENDARG => "S_ENDARG",
+        S_COBOLUDT_16T => "S_COBOLUDT_16T",
+        S_MANYREG_16T => "S_MANYREG_16T",
+        S_RETURN => "S_RETURN",
+        S_ENTRYTHIS => "S_ENTRYTHIS",
+        S_BPREL16 => "S_BPREL16",
+        S_LDATA16 => "S_LDATA16",
+        S_GDATA16 => "S_GDATA16",
+        S_PUB16 => "S_PUB16",
+        S_LPROC16 => "S_LPROC16",
+        S_GPROC16 => "S_GPROC16",
+        S_THUNK16 => "S_THUNK16",
+        S_BLOCK16 => "S_BLOCK16",
+        S_WITH16 => "S_WITH16",
+        S_LABEL16 => "S_LABEL16",
+        S_CEXMODEL16 => "S_CEXMODEL16",
+        S_VFTABLE16 => "S_VFTABLE16",
+        S_REGREL16 => "S_REGREL16",
+        S_BPREL32_16T => "S_BPREL32_16T",
+        S_LDATA32_16T => "S_LDATA32_16T",
+        S_GDATA32_16T => "S_GDATA32_16T",
+        S_PUB32_16T => "S_PUB32_16T",
+        S_LPROC32_16T => "S_LPROC32_16T",
+        S_GPROC32_16T => "S_GPROC32_16T",
+        S_THUNK32_ST => "S_THUNK32_ST",
+        S_BLOCK32_ST => "S_BLOCK32_ST",
+        S_WITH32_ST => "S_WITH32_ST",
+        S_LABEL32_ST => "S_LABEL32_ST",
+        S_CEXMODEL32 => "S_CEXMODEL32",
+        S_VFTABLE32_16T => "S_VFTABLE32_16T",
+        S_REGREL32_16T => "S_REGREL32_16T",
+        S_LTHREAD32_16T => "S_LTHREAD32_16T",
+        S_GTHREAD32_16T => "S_GTHREAD32_16T",
+        S_SLINK32 => "S_SLINK32",
+        S_LPROCMIPS_16T => "S_LPROCMIPS_16T",
+        S_GPROCMIPS_16T => "S_GPROCMIPS_16T",
+        S_PROCREF_ST => "S_PROCREF_ST",
+        S_DATAREF_ST => "S_DATAREF_ST",
+        S_ALIGN => "S_ALIGN",
+        S_LPROCREF_ST => "S_LPROCREF_ST",
+        S_OEM => "S_OEM",
+        S_REGISTER_ST => "S_REGISTER_ST",
+        S_CONSTANT_ST => "S_CONSTANT_ST",
+        S_UDT_ST => "S_UDT_ST",
+        S_COBOLUDT_ST => "S_COBOLUDT_ST",
+        S_MANYREG_ST => "S_MANYREG_ST",
+        S_BPREL32_ST => "S_BPREL32_ST",
+        S_LDATA32_ST => "S_LDATA32_ST",
+        S_GDATA32_ST => "S_GDATA32_ST",
+        S_PUB32_ST => "S_PUB32_ST",
+        S_LPROC32_ST => "S_LPROC32_ST",
+        S_GPROC32_ST => "S_GPROC32_ST",
+        S_VFTABLE32 => "S_VFTABLE32",
+        S_REGREL32_ST => "S_REGREL32_ST",
+        S_LTHREAD32_ST => "S_LTHREAD32_ST",
+        S_GTHREAD32_ST => "S_GTHREAD32_ST",
+        S_LPROCMIPS_ST => "S_LPROCMIPS_ST",
+        S_GPROCMIPS_ST => "S_GPROCMIPS_ST",
+        S_FRAMEPROC => "S_FRAMEPROC",
+        S_COMPILE2_ST => "S_COMPILE2_ST",
+        S_MANYREG2_ST => "S_MANYREG2_ST",
+        S_LPROCIA64_ST => "S_LPROCIA64_ST",
+        S_GPROCIA64_ST => "S_GPROCIA64_ST",
+        S_LOCALSLOT_ST => "S_LOCALSLOT_ST",
+        S_PARAMSLOT_ST => "S_PARAMSLOT_ST",
+        S_ANNOTATION => "S_ANNOTATION",
+        S_GMANPROC_ST => "S_GMANPROC_ST",
+        S_LMANPROC_ST => "S_LMANPROC_ST",
+        S_RESERVED1 => "S_RESERVED1",
+        S_RESERVED2 => "S_RESERVED2",
+        S_RESERVED3 => "S_RESERVED3",
+        S_RESERVED4 => "S_RESERVED4",
+        S_LMANDATA_ST => "S_LMANDATA_ST",
+        S_GMANDATA_ST => "S_GMANDATA_ST",
+        S_MANFRAMEREL_ST => "S_MANFRAMEREL_ST",
+        S_MANREGISTER_ST => "S_MANREGISTER_ST",
+        S_MANSLOT_ST => "S_MANSLOT_ST",
+        S_MANMANYREG_ST => "S_MANMANYREG_ST",
+        S_MANREGREL_ST => "S_MANREGREL_ST",
+        S_MANMANYREG2_ST => "S_MANMANYREG2_ST",
+        S_MANTYPREF => "S_MANTYPREF",
+        S_UNAMESPACE_ST => "S_UNAMESPACE_ST",
+        S_OBJNAME => "S_OBJNAME",
+        S_THUNK32 => "S_THUNK32",
+        S_BLOCK32 => "S_BLOCK32",
+        S_WITH32 => "S_WITH32",
+        S_LABEL32 => "S_LABEL32",
+        S_REGISTER => "S_REGISTER",
+        S_CONSTANT => "S_CONSTANT",
+        S_UDT => "S_UDT",
+        S_COBOLUDT => "S_COBOLUDT",
+        S_MANYREG => "S_MANYREG",
+        S_BPREL32 => "S_BPREL32",
+        S_LDATA32 => "S_LDATA32",
+        S_GDATA32 => "S_GDATA32",
+        S_PUB32 => "S_PUB32",
+        S_LPROC32 => "S_LPROC32",
+        S_GPROC32 => "S_GPROC32",
+        S_REGREL32 => "S_REGREL32",
+        S_LTHREAD32 => "S_LTHREAD32",
+        S_GTHREAD32 => "S_GTHREAD32",
+        S_LPROCMIPS => "S_LPROCMIPS",
+        S_GPROCMIPS => "S_GPROCMIPS",
+        S_COMPILE2 => "S_COMPILE2",
+        S_MANYREG2 => "S_MANYREG2",
+        S_LPROCIA64 => "S_LPROCIA64",
+        S_GPROCIA64 => "S_GPROCIA64",
+        S_LOCALSLOT => "S_LOCALSLOT",
+        S_PARAMSLOT => "S_PARAMSLOT",
+        S_LMANDATA => "S_LMANDATA",
+        S_GMANDATA => "S_GMANDATA",
+        S_MANFRAMEREL => "S_MANFRAMEREL",
+        S_MANREGISTER => "S_MANREGISTER",
+        S_MANSLOT => "S_MANSLOT",
+        S_MANMANYREG => "S_MANMANYREG",
+        S_MANREGREL => "S_MANREGREL",
+        S_MANMANYREG2 => "S_MANMANYREG2",
+        S_UNAMESPACE => "S_UNAMESPACE",
+        S_PROCREF => "S_PROCREF",
+        S_DATAREF => "S_DATAREF",
+        S_LPROCREF => "S_LPROCREF",
+        S_ANNOTATIONREF => "S_ANNOTATIONREF",
+        S_TOKENREF => "S_TOKENREF",
+        S_GMANPROC => "S_GMANPROC",
+        S_LMANPROC => "S_LMANPROC",
+        S_TRAMPOLINE => "S_TRAMPOLINE",
+        S_MANCONSTANT => "S_MANCONSTANT",
+        S_ATTR_FRAMEREL => "S_ATTR_FRAMEREL",
+        S_ATTR_REGISTER => "S_ATTR_REGISTER",
+        S_ATTR_REGREL => "S_ATTR_REGREL",
+        S_ATTR_MANYREG => "S_ATTR_MANYREG",
+        S_SEPCODE => "S_SEPCODE",
+        S_LOCAL_2005 => "S_LOCAL_2005",
+        S_DEFRANGE_2005 => "S_DEFRANGE_2005",
+        S_DEFRANGE2_2005 => "S_DEFRANGE2_2005",
+        S_SECTION => "S_SECTION",
+        S_COFFGROUP => "S_COFFGROUP",
+        S_EXPORT => "S_EXPORT",
+        S_CALLSITEINFO => "S_CALLSITEINFO",
+        S_FRAMECOOKIE => "S_FRAMECOOKIE",
+        S_DISCARDED => "S_DISCARDED",
+        S_COMPILE3 => "S_COMPILE3",
+        S_ENVBLOCK => "S_ENVBLOCK",
+        S_LOCAL => "S_LOCAL",
+        S_DEFRANGE => "S_DEFRANGE",
+        S_DEFRANGE_SUBFIELD => "S_DEFRANGE_SUBFIELD",
+        S_DEFRANGE_REGISTER => "S_DEFRANGE_REGISTER",
+        S_DEFRANGE_FRAMEPOINTER_REL => "S_DEFRANGE_FRAMEPOINTER_REL",
+        S_DEFRANGE_SUBFIELD_REGISTER => "S_DEFRANGE_SUBFIELD_REGISTER",
+        S_DEFRANGE_FRAMEPOINTER_REL_FULL_SCOPE => "S_DEFRANGE_FRAMEPOINTER_REL_FULL_SCOPE",
+        S_DEFRANGE_REGISTER_REL => "S_DEFRANGE_REGISTER_REL",
+        S_LPROC32_ID => "S_LPROC32_ID",
+        S_GPROC32_ID => "S_GPROC32_ID",
+        S_LPROCMIPS_ID => "S_LPROCMIPS_ID",
+        S_GPROCMIPS_ID => "S_GPROCMIPS_ID",
+        S_LPROCIA64_ID => "S_LPROCIA64_ID",
+        S_GPROCIA64_ID => "S_GPROCIA64_ID",
+        S_BUILDINFO => "S_BUILDINFO",
+        S_INLINESITE => "S_INLINESITE",
+        S_INLINESITE_END => "S_INLINESITE_END",
+        S_PROC_ID_END => "S_PROC_ID_END",
+        S_DEFRANGE_HLSL => "S_DEFRANGE_HLSL",
+        S_GDATA_HLSL => "S_GDATA_HLSL",
+        S_LDATA_HLSL => "S_LDATA_HLSL",
+        S_FILESTATIC => "S_FILESTATIC",
+        S_LOCAL_DPC_GROUPSHARED => "S_LOCAL_DPC_GROUPSHARED",
+        S_LPROC32_DPC => "S_LPROC32_DPC",
+        S_LPROC32_DPC_ID => "S_LPROC32_DPC_ID",
+        S_DEFRANGE_DPC_PTR_TAG => "S_DEFRANGE_DPC_PTR_TAG",
+        S_DPC_SYM_TAG_MAP => "S_DPC_SYM_TAG_MAP",
+        S_ARMSWITCHTABLE => "S_ARMSWITCHTABLE",
+        S_CALLEES => "S_CALLEES",
+        S_CALLERS => "S_CALLERS",
+        S_POGODATA => "S_POGODATA",
+        S_INLINESITE2 => "S_INLINESITE2",
+        S_HEAPALLOCSITE => "S_HEAPALLOCSITE",
+        S_MOD_TYPEREF => "S_MOD_TYPEREF",
+        S_REF_MINIPDB => "S_REF_MINIPDB",
+        S_PDBMAP => "S_PDBMAP",
+        S_GDATA_HLSL32 => "S_GDATA_HLSL32",
+        S_LDATA_HLSL32 => "S_LDATA_HLSL32",
+        S_GDATA_HLSL32_EX => "S_GDATA_HLSL32_EX",
+        S_LDATA_HLSL32_EX => "S_LDATA_HLSL32_EX",
+        S_FASTLINK => "S_FASTLINK",
+        S_INLINEES => "S_INLINEES",
+        S_HOTPATCHFUNC => "S_HOTPATCHFUNC",
+        S_BPREL32_INDIR => "S_BPREL32_INDIR",
+        S_REGREL32_INDIR => "S_REGREL32_INDIR",
+        S_GPROC32EX => "S_GPROC32EX",
+        S_LPROC32EX => "S_LPROC32EX",
+        S_GPROC32EX_ID => "S_GPROC32EX_ID",
+        S_LPROC32EX_ID => "S_LPROC32EX_ID",
+        S_STATICLOCAL => "S_STATICLOCAL",
+        S_DEFRANGE_REGISTER_REL_INDIR => "S_DEFRANGE_REGISTER_REL_INDIR",
+        _ => return None,
+    })
+}
+
+/// Defines [`CPUType`] along with its conversions from and to the raw `u16` value.
+///
+/// Every variant is listed once with its value, so that the conversions cannot get out of sync.
+macro_rules! cpu_types {
+    ($($name:ident = $value:literal,)*) => {
+        /// These values correspond to the `CV_CPU_TYPE_e` enumeration, and are documented
+        /// [on MSDN](https://msdn.microsoft.com/en-us/library/b2fc64ek.aspx).
+        #[non_exhaustive]
+        #[allow(missing_docs)]
+        #[repr(u16)]
+        #[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+        #[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
+        pub enum CPUType {
+            $($name = $value,)*
+            /// A CPU type that is not known to this library, holding the raw value.
+            Unknown(u16),
+        }
+
+        impl fmt::Display for CPUType {
+            fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+                match self {
+                    $(Self::$name => write!(f, stringify!($name)),)*
+                    Self::Unknown(value) => write!(f, "Unknown({value:#06x})"),
+                }
+            }
+        }
+
+        /// Converts a raw value into one of the named CPU types.
+        ///
+        /// Unlike parsing a `CPUType` from a symbol record, this returns `Error::UnknownCpuType`
+        /// for values without a named variant instead of [`CPUType::Unknown`].
+        impl TryFrom<u16> for CPUType {
+            type Error = Error;
+
+            fn try_from(value: u16) -> Result<Self, Self::Error> {
+                match value {
+                    $($value => Ok(Self::$name),)*
+                    _ => Err(Error::UnknownCpuType(value)),
+                }
+            }
+        }
+
+        impl From<CPUType> for u16 {
+            fn from(value: CPUType) -> Self {
+                match value {
+                    $(CPUType::$name => $value,)*
+                    CPUType::Unknown(value) => value,
+                }
+            }
+        }
+    };
+}
+
+cpu_types! {
     Intel8080 = 0x0,
     Intel8086 = 0x1,
     Intel80286 = 0x2,
@@ -356,137 +622,100 @@ pub enum CPUType {
     D3D11_Shader = 0x100,
 }
 
-impl fmt::Display for CPUType {
-    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+impl CPUType {
+    /// Returns the instruction set that code compiled for this CPU is encoded in.
+    ///
+    /// `ARMNT` targets Windows on 32-bit ARM, which only runs Thumb-2 code, so it is classified as
+    /// [`InstructionSet::Thumb`] along with `Thumb` itself. Older ARM variants are classified as
+    /// [`InstructionSet::Arm`], even those that are able to switch into Thumb mode.
+    #[must_use]
+    pub fn instruction_set(self) -> InstructionSet {
         match self {
-            Self::Intel8080 => write!(f, "Intel8080"),
-            Self::Intel8086 => write!(f, "Intel8086"),
-            Self::Intel80286 => write!(f, "Intel80286"),
-            Self::Intel80386 => write!(f, "Intel80386"),
-            Self::Intel80486 => write!(f, "Intel80486"),
-            Self::Pentium => write!(f, "Pentium"),
-            Self::PentiumPro => write!(f, "PentiumPro"),
-            Self::Pentium3 => write!(f, "Pentium3"),
-            Self::MIPS => write!(f, "MIPS"),
-            Self::MIPS16 => write!(f, "MIPS16"),
-            Self::MIPS32 => write!(f, "MIPS32"),
-            Self::MIPS64 => write!(f, "MIPS64"),
-            Self::MIPSI => write!(f, "MIPSI"),
-            Self::MIPSII => write!(f, "MIPSII"),
-            Self::MIPSIII => write!(f, "MIPSIII"),
-            Self::MIPSIV => write!(f, "MIPSIV"),
-            Self::MIPSV => write!(f, "MIPSV"),
-            Self::M68000 => write!(f, "M68000"),
-            Self::M68010 => write!(f, "M68010"),
-            Self::M68020 => write!(f, "M68020"),
-            Self::M68030 => write!(f, "M68030"),
-            Self::M68040 => write!(f, "M68040"),
-            Self::Alpha => write!(f, "Alpha"),
-            Self::Alpha21164 => write!(f, "Alpha21164"),
-            Self::Alpha21164A => write!(f, "Alpha21164A"),
-            Self::Alpha21264 => write!(f, "Alpha21264"),
-            Self::Alpha21364 => write!(f, "Alpha21364"),
-            Self::PPC601 => write!(f, "PPC601"),
-            Self::PPC603 => write!(f, "PPC603"),
-            Self::PPC604 => write!(f, "PPC604"),
-            Self::PPC620 => write!(f, "PPC620"),
-            Self::PPCFP => write!(f, "PPCFP"),
-            Self::PPCBE => write!(f, "PPCBE"),
-            Self::SH3 => write!(f, "SH3"),
-            Self::SH3E => write!(f, "SH3E"),
-            Self::SH3DSP => write!(f, "SH3DSP"),
-            Self::SH4 => write!(f, "SH4"),
-            Self::SHMedia => write!(f, "SHMedia"),
-            Self::ARM3 => write!(f, "ARM3"),
-            Self::ARM4 => write!(f, "ARM4"),
-            Self::ARM4T => write!(f, "ARM4T"),
-            Self::ARM5 => write!(f, "ARM5"),
-            Self::ARM5T => write!(f, "ARM5T"),
-            Self::ARM6 => write!(f, "ARM6"),
-            Self::ARM_XMAC => write!(f, "ARM_XMAC"),
-            Self::ARM_WMMX => write!(f, "ARM_WMMX"),
-            Self::ARM7 => write!(f, "ARM7"),
-            Self::ARM64 => write!(f, "ARM64"),
-            Self::Omni => write!(f, "Omni"),
-            Self::Ia64 => write!(f, "Ia64"),
-            Self::Ia64_2 => write!(f, "Ia64_2"),
-            Self::CEE => write!(f, "CEE"),
-            Self::AM33 => write!(f, "AM33"),
-            Self::M32R => write!(f, "M32R"),
-            Self::TriCore => write!(f, "TriCore"),
-            Self::X64 => write!(f, "X64"),
-            Self::EBC => write!(f, "EBC"),
-            Self::Thumb => write!(f, "Thumb"),
-            Self::ARMNT => write!(f, "ARMNT"),
-            Self::D3D11_Shader => write!(f, "D3D11_Shader"),
+            Self::Intel8080 | Self::Intel8086 | Self::Intel80286 => InstructionSet::X86_16,
+            Self::Intel80386
+            | Self::Intel80486
+            | Self::Pentium
+            | Self::PentiumPro
+            | Self::Pentium3 => InstructionSet::X86,
+            Self::X64 => InstructionSet::X64,
+            Self::ARM3
+            | Self::ARM4
+            | Self::ARM4T
+            | Self::ARM5
+            | Self::ARM5T
+            | Self::ARM6
+            | Self::ARM_XMAC
+            | Self::ARM_WMMX
+            | Self::ARM7 => InstructionSet::Arm,
+            Self::Thumb | Self::ARMNT => InstructionSet::Thumb,
+            Self::ARM64 => InstructionSet::Arm64,
+            Self::Ia64 | Self::Ia64_2 => InstructionSet::Ia64,
+            Self::CEE => InstructionSet::Cil,
+            _ => InstructionSet::Other,
         }
     }
 }
 
-impl From<u16> for CPUType {
-    fn from(value: u16) -> Self {
-        match value {
-            0x0 => Self::Intel8080,
-            0x1 => Self::Intel8086,
-            0x2 => Self::Intel80286,
-            0x3 => Self::Intel80386,
-            0x4 => Self::Intel80486,
-            0x5 => Self::Pentium,
-            0x6 => Self::PentiumPro,
-            0x7 => Self::Pentium3,
-            0x10 => Self::MIPS,
-            0x11 => Self::MIPS16,
-            0x12 => Self::MIPS32,
-            0x13 => Self::MIPS64,
-            0x14 => Self::MIPSI,
-            0x15 => Self::MIPSII,
-            0x16 => Self::MIPSIII,
-            0x17 => Self::MIPSIV,
-            0x18 => Self::MIPSV,
-            0x20 => Self::M68000,
-            0x21 => Self::M68010,
-            0x22 => Self::M68020,
-            0x23 => Self::M68030,
-            0x24 => Self::M68040,
-            0x30 => Self::Alpha,
-            0x31 => Self::Alpha21164,
-            0x32 => Self::Alpha21164A,
-            0x33 => Self::Alpha21264,
-            0x34 => Self::Alpha21364,
-            0x40 => Self::PPC601,
-            0x41 => Self::PPC603,
-            0x42 => Self::PPC604,
-            0x43 => Self::PPC620,
-            0x44 => Self::PPCFP,
-            0x45 => Self::PPCBE,
-            0x50 => Self::SH3,
-            0x51 => Self::SH3E,
-            0x52 => Self::SH3DSP,
-            0x53 => Self::SH4,
-            0x54 => Self::SHMedia,
-            0x60 => Self::ARM3,
-            0x61 => Self::ARM4,
-            0x62 => Self::ARM4T,
-            0x63 => Self::ARM5,
-            0x64 => Self::ARM5T,
-            0x65 => Self::ARM6,
-            0x66 => Self::ARM_XMAC,
-            0x67 => Self::ARM_WMMX,
-            0x68 => Self::ARM7,
-            0x69 => Self::ARM64,
-            0x70 => Self::Omni,
-            0x80 => Self::Ia64,
-            0x81 => Self::Ia64_2,
-            0x90 => Self::CEE,
-            0xa0 => Self::AM33,
-            0xb0 => Self::M32R,
-            0xc0 => Self::TriCore,
-            0xd0 => Self::X64,
-            0xe0 => Self::EBC,
-            0xf0 => Self::Thumb,
-            0xf4 => Self::ARMNT,
-            0x100 => Self::D3D11_Shader,
-            _ => Self::Intel8080, // This enum doesn't have an unknown value, so we just force it to Intel8080 since it's 0x0.
+/// The instruction set of a module, derived from its [`CPUType`].
+///
+/// This determines how a disassembler should decode the code of a module.
+#[non_exhaustive]
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+pub enum InstructionSet {
+    /// 16-bit x86 code.
+    X86_16,
+    /// 32-bit x86 code.
+    X86,
+    /// 64-bit x86 code (AMD64).
+    X64,
+    /// 32-bit ARM code in ARM (A32) mode.
+    Arm,
+    /// 32-bit ARM code in Thumb (T32) mode.
+    ///
+    /// Thumb instructions are 2 or 4 bytes long and aligned to 2 bytes. Code addresses stored in
+    /// data, such as function pointers, have the lowest bit set to indicate Thumb mode, while
+    /// section offsets in symbols like `S_LABEL32` refer to the actual instruction address.
+    Thumb,
+    /// 64-bit ARM code (AArch64).
+    Arm64,
+    /// Intel Itanium code.
+    Ia64,
+    /// .NET common intermediate language.
+    Cil,
+    /// Any other instruction set.
+    Other,
+}
+
+impl InstructionSet {
+    /// Returns whether code is encoded in Thumb mode.
+    #[must_use]
+    pub fn is_thumb(self) -> bool {
+        self == Self::Thumb
+    }
+
+    /// Returns the size of a code pointer in bytes, if known.
+    #[must_use]
+    pub fn pointer_size(self) -> Option<u32> {
+        match self {
+            Self::X86_16 => Some(2),
+            Self::X86 | Self::Arm | Self::Thumb => Some(4),
+            Self::X64 | Self::Arm64 | Self::Ia64 => Some(8),
+            Self::Cil | Self::Other => None,
+        }
+    }
+
+    /// Returns the number of bytes a call instruction pushes onto the stack for the return
+    /// address.
+    ///
+    /// This is zero for instruction sets that pass the return address in a link register. If the
+    /// callee saves the link register, it is part of the callee-saved registers instead.
+    #[must_use]
+    pub fn return_address_size(self) -> u32 {
+        match self {
+            Self::X86_16 => 2,
+            Self::X86 => 4,
+            Self::X64 => 8,
+            _ => 0,
         }
     }
 }
@@ -495,14 +724,15 @@ impl<'a> TryFromCtx<'a, Endian> for CPUType {
     type Error = scroll::Error;
 
     fn try_from_ctx(this: &'a [u8], le: Endian) -> scroll::Result<(Self, usize)> {
-        u16::try_from_ctx(this, le).map(|(v, l)| (v.into(), l))
+        u16::try_from_ctx(this, le).map(|(v, l)| (Self::try_from(v).unwrap_or(Self::Unknown(v)), l))
     }
 }
 
 /// These values correspond to the `CV_CFL_LANG` enumeration, and are documented
 /// [on MSDN](https://learn.microsoft.com/en-us/visualstudio/debugger/debug-interface-access/cv-cfl-lang?view=vs-2022).
 #[non_exhaustive]
-#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+#[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
 pub enum SourceLanguage {
     /// Application language is C.
     C = 0x00,
@@ -592,9 +822,11 @@ impl fmt::Display for SourceLanguage {
     }
 }
 
-impl From<u8> for SourceLanguage {
-    fn from(value: u8) -> Self {
-        match value {
+impl TryFrom<u8> for SourceLanguage {
+    type Error = Error;
+
+    fn try_from(value: u8) -> Result<Self, Self::Error> {
+        Ok(match value {
             0x00 => Self::C,
             0x01 => Self::Cpp,
             0x02 => Self::Fortran,
@@ -619,8 +851,14 @@ impl From<u8> for SourceLanguage {
             0x15 => Self::Rust,
             0x16 => Self::Go,
             0x44 => Self::D,
-            _ => Self::Masm, // There is no unknown, so we just force to Masm as the default.
-        }
+            _ => return Err(Error::UnknownSourceLanguage(value)),
+        })
+    }
+}
+
+impl From<SourceLanguage> for u8 {
+    fn from(value: SourceLanguage) -> Self {
+        value as u8
     }
 }
 
@@ -628,6 +866,7 @@ impl<'a> TryFromCtx<'a, Endian> for SourceLanguage {
     type Error = scroll::Error;
 
     fn try_from_ctx(this: &'a [u8], le: Endian) -> scroll::Result<(Self, usize)> {
-        u8::try_from_ctx(this, le).map(|(v, l)| (v.into(), l))
+        // There is no unknown, so we just force to Masm as the default.
+        u8::try_from_ctx(this, le).map(|(v, l)| (Self::try_from(v).unwrap_or(Self::Masm), l))
     }
 }
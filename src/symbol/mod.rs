@@ -5,20 +5,28 @@
 // http://opensource.org/licenses/MIT>, at your option. This file may not be
 // copied, modified, or distributed except according to those terms.
 
+use std::collections::{BTreeMap, HashMap, HashSet};
 use std::fmt;
+use std::mem;
+use std::ops::Range;
+use std::sync::Arc;
 
 use scroll::{ctx::TryFromCtx, Endian, Pread, LE};
 
 use crate::common::*;
 use crate::msf::*;
+use crate::tpi::{resolve_signature, strip_modifiers};
 use crate::FallibleIterator;
-use crate::SectionCharacteristics;
+use crate::{
+    AddressMap, CallingConvention, ImageSectionHeader, PrimitiveKind, PrimitiveType,
+    SectionCharacteristics, TypeData, TypeFinder,
+};
 
 mod annotations;
 mod constants;
 
 use self::constants::*;
-pub use self::constants::{CPUType, SourceLanguage};
+pub use self::constants::{symbol_kind_name, CPUType, InstructionSet, SourceLanguage};
 
 pub use self::annotations::*;
 
@@ -32,6 +40,10 @@ pub type SymbolKind = u16;
 ///
 /// To avoid copying, `Symbol`s exist as references to data owned by the parent `SymbolTable`.
 /// Therefore, a `Symbol` may not outlive its parent `SymbolTable`.
+///
+/// Two `Symbol`s are only equal if both their index and their bytes are equal. To find identical
+/// records at different offsets, for example duplicates emitted by different modules, use
+/// [`data_eq`](Self::data_eq).
 #[derive(Copy, Clone, PartialEq)]
 pub struct Symbol<'t> {
     index: SymbolIndex,
@@ -54,6 +66,14 @@ impl<'t> Symbol<'t> {
         self.data.pread_with(0, LE).unwrap_or_default()
     }
 
+    /// Returns the name of this symbol's kind, such as `"S_GPROC32"`.
+    ///
+    /// See [`symbol_kind_name`].
+    #[must_use]
+    pub fn kind_name(&self) -> Option<&'static str> {
+        symbol_kind_name(self.raw_kind())
+    }
+
     /// Returns the raw bytes of this symbol record, including the symbol type and extra data, but
     /// not including the preceding symbol length indicator.
     #[inline]
@@ -62,12 +82,35 @@ impl<'t> Symbol<'t> {
         self.data
     }
 
+    /// Returns whether both symbols contain the same record, regardless of their index.
+    #[inline]
+    #[must_use]
+    pub fn data_eq(&self, other: &Symbol<'_>) -> bool {
+        self.raw_bytes() == other.raw_bytes()
+    }
+
     /// Parse the symbol into the `SymbolData` it contains.
     #[inline]
     pub fn parse(&self) -> Result<SymbolData> {
         self.raw_bytes().pread_with(0, ())
     }
 
+    /// Parse the symbol, falling back to [`SymbolData::Unknown`] instead of failing.
+    ///
+    /// Unlike [`parse`](Self::parse), this never returns an error. Symbols of unsupported kinds and
+    /// records that fail to parse are returned as an [`UnknownSymbol`] holding the raw record, so
+    /// that iteration can continue and the data can be handled elsewhere.
+    #[must_use]
+    pub fn parse_lenient(&self) -> SymbolData {
+        match self.parse() {
+            Ok(data) => data,
+            Err(_) => SymbolData::Unknown(UnknownSymbol {
+                kind: self.raw_kind(),
+                data: self.raw_bytes().get(2..).unwrap_or_default().to_vec(),
+            }),
+        }
+    }
+
     /// Returns whether this symbol starts a scope.
     ///
     /// If `true`, this symbol has a `parent` and an `end` field, which contains the offset of the
@@ -119,6 +162,17 @@ impl<'t> Symbol<'t> {
     pub fn ends_scope(&self) -> bool {
         matches!(self.raw_kind(), S_END | S_PROC_ID_END | S_INLINESITE_END)
     }
+
+    /// Copies the record bytes into an [`OwnedSymbol`] that does not borrow the parent
+    /// `SymbolTable`.
+    #[must_use]
+    #[allow(clippy::wrong_self_convention)]
+    pub fn to_owned(&self) -> OwnedSymbol {
+        OwnedSymbol {
+            index: self.index,
+            data: Arc::from(self.data),
+        }
+    }
 }
 
 impl fmt::Debug for Symbol<'_> {
@@ -132,6 +186,96 @@ impl fmt::Debug for Symbol<'_> {
     }
 }
 
+/// A symbol record that owns its data.
+///
+/// Created by [`Symbol::to_owned`]. Unlike a [`Symbol`], an `OwnedSymbol` can outlive the
+/// `SymbolTable` it was read from and can be shared across threads. Cloning is cheap, since the
+/// record bytes are reference counted.
+#[derive(Clone, PartialEq, Eq)]
+pub struct OwnedSymbol {
+    index: SymbolIndex,
+    data: Arc<[u8]>,
+}
+
+impl OwnedSymbol {
+    /// Borrows this record as a [`Symbol`].
+    #[inline]
+    #[must_use]
+    pub fn as_symbol(&self) -> Symbol<'_> {
+        Symbol {
+            index: self.index,
+            data: &self.data,
+        }
+    }
+
+    /// The index of this symbol in the containing symbol stream.
+    #[inline]
+    #[must_use]
+    pub fn index(&self) -> SymbolIndex {
+        self.index
+    }
+
+    /// Returns the kind of symbol identified by this Symbol.
+    #[inline]
+    #[must_use]
+    pub fn raw_kind(&self) -> SymbolKind {
+        self.as_symbol().raw_kind()
+    }
+
+    /// Returns the raw bytes of this symbol record, including the symbol type and extra data, but
+    /// not including the preceding symbol length indicator.
+    #[inline]
+    #[must_use]
+    pub fn raw_bytes(&self) -> &[u8] {
+        &self.data
+    }
+
+    /// Parse the symbol into the `SymbolData` it contains.
+    #[inline]
+    pub fn parse(&self) -> Result<SymbolData> {
+        self.as_symbol().parse()
+    }
+
+    /// Parse the symbol, falling back to [`SymbolData::Unknown`] instead of failing.
+    ///
+    /// See [`Symbol::parse_lenient`].
+    #[must_use]
+    pub fn parse_lenient(&self) -> SymbolData {
+        self.as_symbol().parse_lenient()
+    }
+
+    /// Returns whether this symbol starts a scope.
+    ///
+    /// See [`Symbol::starts_scope`].
+    #[must_use]
+    pub fn starts_scope(&self) -> bool {
+        self.as_symbol().starts_scope()
+    }
+
+    /// Returns whether this symbol declares the end of a scope.
+    #[must_use]
+    pub fn ends_scope(&self) -> bool {
+        self.as_symbol().ends_scope()
+    }
+}
+
+impl fmt::Debug for OwnedSymbol {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "OwnedSymbol{{ kind: 0x{:x} [{} bytes] }}",
+            self.raw_kind(),
+            self.data.len()
+        )
+    }
+}
+
+impl From<Symbol<'_>> for OwnedSymbol {
+    fn from(symbol: Symbol<'_>) -> Self {
+        symbol.to_owned()
+    }
+}
+
 fn parse_symbol_name<'t>(buf: &mut ParseBuffer<'t>, kind: SymbolKind) -> Result<RawString<'t>> {
     if kind < S_ST_MAX {
         // Pascal-style name
@@ -171,7 +315,8 @@ fn parse_optional_index(buf: &mut ParseBuffer<'_>) -> Result<Option<SymbolIndex>
 
 /// Information parsed from a [`Symbol`] record.
 #[non_exhaustive]
-#[derive(Clone, Debug, Eq, PartialEq)]
+#[derive(Clone, Debug, Eq, Hash, PartialEq)]
+#[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
 pub enum SymbolData {
     /// End of a scope, such as a procedure.
     ScopeEnd,
@@ -273,9 +418,184 @@ pub enum SymbolData {
     HeapAllocationSite(HeapAllocationSiteSymbol),
     /// A security cookie on a stack frame
     FrameCookie(FrameCookieSymbol),
+    /// A symbol that could not be parsed, returned by [`Symbol::parse_lenient`].
+    Unknown(UnknownSymbol),
+}
+
+/// The kind of a [`SymbolData`], without its payload.
+///
+/// Returned by [`SymbolData::kind`]. This is useful to group or count symbols by kind without
+/// matching on the parsed data.
+#[non_exhaustive]
+#[derive(Clone, Copy, Debug, Eq, Hash, Ord, PartialEq, PartialOrd)]
+pub enum SymbolKindTag {
+    /// End of a scope, such as a procedure.
+    ScopeEnd,
+    /// Name of the object file of this module.
+    ObjName,
+    /// A Register variable.
+    RegisterVariable,
+    /// A constant value.
+    Constant,
+    /// A user defined type.
+    UserDefinedType,
+    /// A Register variable spanning multiple registers.
+    MultiRegisterVariable,
+    /// Static data, such as a global variable.
+    Data,
+    /// A public symbol with a mangled name.
+    Public,
+    /// A procedure, such as a function or method.
+    Procedure,
+    /// A managed procedure, such as a function or method.
+    ManagedProcedure,
+    /// A thread local variable.
+    ThreadStorage,
+    /// Flags used to compile a module.
+    CompileFlags,
+    /// A using namespace directive.
+    UsingNamespace,
+    /// Reference to a [`ProcedureSymbol`].
+    ProcedureReference,
+    /// Reference to an imported variable.
+    DataReference,
+    /// Reference to an annotation.
+    AnnotationReference,
+    /// Reference to a managed procedure.
+    TokenReference,
+    /// Trampoline thunk.
+    Trampoline,
+    /// An exported symbol.
+    Export,
+    /// A local symbol in optimized code.
+    Local,
+    /// A managed local variable slot.
+    ManagedSlot,
+    /// Reference to build information.
+    BuildInfo,
+    /// The callsite of an inlined function.
+    InlineSite,
+    /// End of an inline callsite.
+    InlineSiteEnd,
+    /// End of a procedure.
+    ProcedureEnd,
+    /// A label.
+    Label,
+    /// A block.
+    Block,
+    /// Data allocated relative to a register.
+    RegisterRelative,
+    /// A thunk.
+    Thunk,
+    /// A block of separated code.
+    SeparatedCode,
+    /// OEM information.
+    OEM,
+    /// Environment block split off from `S_COMPILE2`.
+    EnvBlock,
+    /// A COFF section in a PE executable.
+    Section,
+    /// A COFF group.
+    CoffGroup,
+    /// A live range of a variable.
+    DefRange,
+    /// A live range of a sub field of a variable.
+    DefRangeSubField,
+    /// A live range of a register variable.
+    DefRangeRegister,
+    /// A live range of a frame pointer-relative variable.
+    DefRangeFramePointerRelative,
+    /// A frame-pointer variable which is valid in the full scope of the function.
+    DefRangeFramePointerRelativeFullScope,
+    /// A live range of a sub field of a register variable.
+    DefRangeSubFieldRegister,
+    /// A live range of a variable related to a register.
+    DefRangeRegisterRelative,
+    /// A base pointer-relative variable.
+    BasePointerRelative,
+    /// Extra frame and proc information.
+    FrameProcedure,
+    /// Indirect call site information.
+    CallSiteInfo,
+    /// Callers of a function.
+    Callers,
+    /// Callees of a function.
+    Callees,
+    /// Inlinees of a function.
+    Inlinees,
+    /// Describes the layout of a jump table
+    ArmSwitchTable,
+    /// Heap allocation site
+    HeapAllocationSite,
+    /// A security cookie on a stack frame
+    FrameCookie,
+    /// A symbol that could not be parsed.
+    Unknown,
 }
 
 impl SymbolData {
+    /// Returns the kind of this symbol as a [`SymbolKindTag`].
+    ///
+    /// Unlike [`Symbol::raw_kind`], symbol kinds that parse into the same variant share a tag.
+    #[must_use]
+    pub fn kind(&self) -> SymbolKindTag {
+        match self {
+            Self::ScopeEnd => SymbolKindTag::ScopeEnd,
+            Self::ObjName(_) => SymbolKindTag::ObjName,
+            Self::RegisterVariable(_) => SymbolKindTag::RegisterVariable,
+            Self::Constant(_) => SymbolKindTag::Constant,
+            Self::UserDefinedType(_) => SymbolKindTag::UserDefinedType,
+            Self::MultiRegisterVariable(_) => SymbolKindTag::MultiRegisterVariable,
+            Self::Data(_) => SymbolKindTag::Data,
+            Self::Public(_) => SymbolKindTag::Public,
+            Self::Procedure(_) => SymbolKindTag::Procedure,
+            Self::ManagedProcedure(_) => SymbolKindTag::ManagedProcedure,
+            Self::ThreadStorage(_) => SymbolKindTag::ThreadStorage,
+            Self::CompileFlags(_) => SymbolKindTag::CompileFlags,
+            Self::UsingNamespace(_) => SymbolKindTag::UsingNamespace,
+            Self::ProcedureReference(_) => SymbolKindTag::ProcedureReference,
+            Self::DataReference(_) => SymbolKindTag::DataReference,
+            Self::AnnotationReference(_) => SymbolKindTag::AnnotationReference,
+            Self::TokenReference(_) => SymbolKindTag::TokenReference,
+            Self::Trampoline(_) => SymbolKindTag::Trampoline,
+            Self::Export(_) => SymbolKindTag::Export,
+            Self::Local(_) => SymbolKindTag::Local,
+            Self::ManagedSlot(_) => SymbolKindTag::ManagedSlot,
+            Self::BuildInfo(_) => SymbolKindTag::BuildInfo,
+            Self::InlineSite(_) => SymbolKindTag::InlineSite,
+            Self::InlineSiteEnd => SymbolKindTag::InlineSiteEnd,
+            Self::ProcedureEnd => SymbolKindTag::ProcedureEnd,
+            Self::Label(_) => SymbolKindTag::Label,
+            Self::Block(_) => SymbolKindTag::Block,
+            Self::RegisterRelative(_) => SymbolKindTag::RegisterRelative,
+            Self::Thunk(_) => SymbolKindTag::Thunk,
+            Self::SeparatedCode(_) => SymbolKindTag::SeparatedCode,
+            Self::OEM(_) => SymbolKindTag::OEM,
+            Self::EnvBlock(_) => SymbolKindTag::EnvBlock,
+            Self::Section(_) => SymbolKindTag::Section,
+            Self::CoffGroup(_) => SymbolKindTag::CoffGroup,
+            Self::DefRange(_) => SymbolKindTag::DefRange,
+            Self::DefRangeSubField(_) => SymbolKindTag::DefRangeSubField,
+            Self::DefRangeRegister(_) => SymbolKindTag::DefRangeRegister,
+            Self::DefRangeFramePointerRelative(_) => SymbolKindTag::DefRangeFramePointerRelative,
+            Self::DefRangeFramePointerRelativeFullScope(_) => {
+                SymbolKindTag::DefRangeFramePointerRelativeFullScope
+            }
+            Self::DefRangeSubFieldRegister(_) => SymbolKindTag::DefRangeSubFieldRegister,
+            Self::DefRangeRegisterRelative(_) => SymbolKindTag::DefRangeRegisterRelative,
+            Self::BasePointerRelative(_) => SymbolKindTag::BasePointerRelative,
+            Self::FrameProcedure(_) => SymbolKindTag::FrameProcedure,
+            Self::CallSiteInfo(_) => SymbolKindTag::CallSiteInfo,
+            Self::Callers(_) => SymbolKindTag::Callers,
+            Self::Callees(_) => SymbolKindTag::Callees,
+            Self::Inlinees(_) => SymbolKindTag::Inlinees,
+            Self::ArmSwitchTable(_) => SymbolKindTag::ArmSwitchTable,
+            Self::HeapAllocationSite(_) => SymbolKindTag::HeapAllocationSite,
+            Self::FrameCookie(_) => SymbolKindTag::FrameCookie,
+            Self::Unknown(_) => SymbolKindTag::Unknown,
+        }
+    }
+
     /// Returns the name of this symbol if it has one.
     #[must_use]
     pub fn name(&self) -> Option<&str> {
@@ -329,7 +649,187 @@ impl SymbolData {
             | Self::Inlinees(_)
             | Self::ArmSwitchTable(_)
             | Self::HeapAllocationSite(_)
-            | Self::FrameCookie(_) => None,
+            | Self::FrameCookie(_)
+            | Self::Unknown(_) => None,
+        }
+    }
+
+    /// Returns the number of heap bytes owned by this symbol.
+    ///
+    /// This sums the capacities of all owned strings and vectors, but does not include
+    /// `size_of::<SymbolData>()` itself. Add that to get the full footprint of a parsed symbol, for
+    /// instance when budgeting the memory of a symbol cache.
+    #[must_use]
+    pub fn heap_size(&self) -> usize {
+        fn string_list(strings: &Vec<String>) -> usize {
+            strings.capacity() * mem::size_of::<String>()
+                + strings.iter().map(String::capacity).sum::<usize>()
+        }
+
+        fn vec<T>(vec: &Vec<T>) -> usize {
+            vec.capacity() * mem::size_of::<T>()
+        }
+
+        fn optional(name: &Option<String>) -> usize {
+            name.as_ref().map_or(0, String::capacity)
+        }
+
+        match self {
+            Self::ObjName(data) => data.name.capacity(),
+            Self::RegisterVariable(data) => data.name.capacity(),
+            Self::Constant(data) => data.name.capacity(),
+            Self::UserDefinedType(data) => data.name.capacity(),
+            Self::MultiRegisterVariable(data) => {
+                vec(&data.registers)
+                    + data
+                        .registers
+                        .iter()
+                        .map(|(_, name)| name.capacity())
+                        .sum::<usize>()
+            }
+            Self::Data(data) => data.name.capacity(),
+            Self::Public(data) => data.name.capacity(),
+            Self::Procedure(data) => data.name.capacity(),
+            Self::ManagedProcedure(data) => optional(&data.name),
+            Self::ThreadStorage(data) => data.name.capacity(),
+            Self::CompileFlags(data) => data.version_string.capacity(),
+            Self::UsingNamespace(data) => data.name.capacity(),
+            Self::ProcedureReference(data) => optional(&data.name),
+            Self::DataReference(data) => optional(&data.name),
+            Self::AnnotationReference(data) => data.name.capacity(),
+            Self::TokenReference(data) => data.name.capacity(),
+            Self::Export(data) => data.name.capacity(),
+            Self::Local(data) => data.name.capacity(),
+            Self::ManagedSlot(data) => data.name.capacity(),
+            Self::InlineSite(data) => data.annotations.heap_size(),
+            Self::Label(data) => data.name.capacity(),
+            Self::Block(data) => data.name.capacity(),
+            Self::RegisterRelative(data) => data.name.capacity(),
+            Self::Thunk(data) => {
+                let target = match data.kind {
+                    ThunkKind::Adjustor(ref adjustor) => adjustor.target.capacity(),
+                    _ => 0,
+                };
+                data.name.capacity() + target
+            }
+            Self::OEM(data) => data.id_oem.capacity(),
+            Self::EnvBlock(data) => string_list(&data.rgsz),
+            Self::Section(data) => data.name.capacity(),
+            Self::CoffGroup(data) => data.name.capacity(),
+            Self::DefRange(data) => vec(&data.gaps),
+            Self::DefRangeSubField(data) => vec(&data.gaps),
+            Self::DefRangeRegister(data) => vec(&data.gaps),
+            Self::DefRangeFramePointerRelative(data) => vec(&data.gaps),
+            Self::DefRangeSubFieldRegister(data) => vec(&data.gaps),
+            Self::DefRangeRegisterRelative(data) => vec(&data.gaps),
+            Self::BasePointerRelative(data) => data.name.capacity(),
+            Self::Callers(data) | Self::Callees(data) => {
+                vec(&data.functions) + vec(&data.invocations)
+            }
+            Self::Inlinees(data) => vec(&data.inlinees),
+            Self::Unknown(data) => vec(&data.data),
+            Self::ScopeEnd
+            | Self::Trampoline(_)
+            | Self::BuildInfo(_)
+            | Self::InlineSiteEnd
+            | Self::ProcedureEnd
+            | Self::SeparatedCode(_)
+            | Self::DefRangeFramePointerRelativeFullScope(_)
+            | Self::FrameProcedure(_)
+            | Self::CallSiteInfo(_)
+            | Self::ArmSwitchTable(_)
+            | Self::HeapAllocationSite(_)
+            | Self::FrameCookie(_) => 0,
+        }
+    }
+
+    /// Converts this symbol into a [`DefRangeData`] if it is one of the `S_DEFRANGE*` records.
+    #[must_use]
+    pub fn into_def_range(self) -> Option<DefRangeData> {
+        match self {
+            Self::DefRange(data) => Some(DefRangeData::DefRange(data)),
+            Self::DefRangeSubField(data) => Some(DefRangeData::SubField(data)),
+            Self::DefRangeRegister(data) => Some(DefRangeData::Register(data)),
+            Self::DefRangeFramePointerRelative(data) => {
+                Some(DefRangeData::FramePointerRelative(data))
+            }
+            Self::DefRangeFramePointerRelativeFullScope(data) => {
+                Some(DefRangeData::FramePointerRelativeFullScope(data))
+            }
+            Self::DefRangeSubFieldRegister(data) => Some(DefRangeData::SubFieldRegister(data)),
+            Self::DefRangeRegisterRelative(data) => Some(DefRangeData::RegisterRelative(data)),
+            _ => None,
+        }
+    }
+
+    /// Converts this symbol into a [`VariableSymbol`] if it is static data or thread storage.
+    #[must_use]
+    pub fn into_variable(self) -> Option<VariableSymbol> {
+        match self {
+            Self::Data(data) => Some(VariableSymbol::Data(data)),
+            Self::ThreadStorage(data) => Some(VariableSymbol::ThreadStorage(data)),
+            _ => None,
+        }
+    }
+
+    /// Returns the code or data offset of this symbol if it has one.
+    ///
+    /// For thunks and trampolines this is the start of the thunk, and for live ranges it is the start
+    /// of the range.
+    #[must_use]
+    pub fn offset(&self) -> Option<PdbInternalSectionOffset> {
+        match self {
+            Self::Public(data) => Some(data.offset),
+            Self::Data(data) => Some(data.offset),
+            Self::Procedure(data) => Some(data.offset),
+            Self::ManagedProcedure(data) => Some(data.offset),
+            Self::ThreadStorage(data) => Some(data.offset),
+            Self::Trampoline(data) => Some(data.thunk),
+            Self::ManagedSlot(data) => Some(data.offset),
+            Self::Label(data) => Some(data.offset),
+            Self::Block(data) => Some(data.offset),
+            Self::Thunk(data) => Some(data.offset),
+            Self::SeparatedCode(data) => Some(data.offset),
+            Self::CoffGroup(data) => Some(data.offset),
+            Self::DefRange(data) => Some(data.range.offset),
+            Self::DefRangeSubField(data) => Some(data.range.offset),
+            Self::DefRangeRegister(data) => Some(data.range.offset),
+            Self::DefRangeFramePointerRelative(data) => Some(data.range.offset),
+            Self::DefRangeSubFieldRegister(data) => Some(data.range.offset),
+            Self::DefRangeRegisterRelative(data) => Some(data.range.offset),
+            Self::CallSiteInfo(data) => Some(data.offset),
+            Self::HeapAllocationSite(data) => Some(data.offset),
+            Self::ScopeEnd
+            | Self::ObjName(_)
+            | Self::RegisterVariable(_)
+            | Self::Constant(_)
+            | Self::UserDefinedType(_)
+            | Self::MultiRegisterVariable(_)
+            | Self::CompileFlags(_)
+            | Self::UsingNamespace(_)
+            | Self::ProcedureReference(_)
+            | Self::DataReference(_)
+            | Self::AnnotationReference(_)
+            | Self::TokenReference(_)
+            | Self::Export(_)
+            | Self::Local(_)
+            | Self::BuildInfo(_)
+            | Self::InlineSite(_)
+            | Self::InlineSiteEnd
+            | Self::ProcedureEnd
+            | Self::RegisterRelative(_)
+            | Self::OEM(_)
+            | Self::EnvBlock(_)
+            | Self::Section(_)
+            | Self::DefRangeFramePointerRelativeFullScope(_)
+            | Self::BasePointerRelative(_)
+            | Self::FrameProcedure(_)
+            | Self::Callers(_)
+            | Self::Callees(_)
+            | Self::Inlinees(_)
+            | Self::ArmSwitchTable(_)
+            | Self::FrameCookie(_)
+            | Self::Unknown(_) => None,
         }
     }
 }
@@ -424,7 +924,8 @@ impl<'t> TryFromCtx<'t> for SymbolData {
 /// A Register variable.
 ///
 /// Symbol kind `S_REGISTER`, or `S_REGISTER_ST`
-#[derive(Clone, Debug, Eq, PartialEq)]
+#[derive(Clone, Debug, Eq, Hash, PartialEq)]
+#[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
 pub struct RegisterVariableSymbol {
     /// Identifier of the variable type.
     pub type_index: TypeIndex,
@@ -471,7 +972,8 @@ impl<'t> TryFromCtx<'t, SymbolKind> for RegisterVariableSymbol {
 /// A Register variable spanning multiple registers.
 ///
 /// Symbol kind `S_MANYREG`, `S_MANYREG_ST`, `S_MANYREG2`, or `S_MANYREG2_ST`.
-#[derive(Clone, Debug, Eq, PartialEq)]
+#[derive(Clone, Debug, Eq, Hash, PartialEq)]
+#[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
 pub struct MultiRegisterVariableSymbol {
     /// Identifier of the variable type.
     pub type_index: TypeIndex,
@@ -517,7 +1019,8 @@ const CVPSF_MSIL: u32 = 0x8;
 /// A public symbol with a mangled name.
 ///
 /// Symbol kind `S_PUB32`, or `S_PUB32_ST`.
-#[derive(Clone, Debug, Eq, PartialEq)]
+#[derive(Clone, Debug, Eq, Hash, PartialEq)]
+#[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
 pub struct PublicSymbol {
     /// The public symbol refers to executable code.
     pub code: bool,
@@ -553,6 +1056,45 @@ impl<'t> TryFromCtx<'t, SymbolKind> for PublicSymbol {
     }
 }
 
+impl PublicSymbol {
+    /// Returns whether this public most likely names a thunk rather than a real function.
+    ///
+    /// Publics that refer to code but are not flagged as functions are typically incremental
+    /// linking thunks. If `symbols` is given, this also looks for an `S_TRAMPOLINE` or `S_THUNK32`
+    /// record starting at the same offset. Linker-generated trampolines are found in the symbols of
+    /// the `* Linker *` module, and import thunks in the `Import:` modules.
+    pub fn looks_like_thunk(&self, symbols: Option<SymbolIter<'_>>) -> Result<bool> {
+        if self.code && !self.function {
+            return Ok(true);
+        }
+
+        let mut symbols = match symbols {
+            Some(symbols) => symbols,
+            None => return Ok(false),
+        };
+
+        while let Some(symbol) = symbols.next()? {
+            let offset = match symbol.raw_kind() {
+                S_TRAMPOLINE => match symbol.parse()? {
+                    SymbolData::Trampoline(data) => data.thunk,
+                    _ => continue,
+                },
+                S_THUNK32 | S_THUNK32_ST => match symbol.parse()? {
+                    SymbolData::Thunk(data) => data.offset,
+                    _ => continue,
+                },
+                _ => continue,
+            };
+
+            if offset == self.offset {
+                return Ok(true);
+            }
+        }
+
+        Ok(false)
+    }
+}
+
 /// Static data, such as a global variable.
 ///
 /// Symbol kinds:
@@ -560,7 +1102,8 @@ impl<'t> TryFromCtx<'t, SymbolKind> for PublicSymbol {
 ///  - `S_GDATA32` and `S_GDATA32_ST` for global unmanaged data
 ///  - `S_LMANDATA32` and `S_LMANDATA32_ST` for local managed data
 ///  - `S_GMANDATA32` and `S_GMANDATA32_ST` for global managed data
-#[derive(Clone, Debug, Eq, PartialEq)]
+#[derive(Clone, Debug, Eq, Hash, PartialEq)]
+#[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
 pub struct DataSymbol {
     /// Whether this data is global or local.
     pub global: bool,
@@ -595,10 +1138,52 @@ impl<'t> TryFromCtx<'t, SymbolKind> for DataSymbol {
     }
 }
 
+impl DataSymbol {
+    /// Classifies the storage of this data by the section it is placed in.
+    ///
+    /// `sections` are the section headers of the executable, for example as returned by
+    /// [`PDB::sections`](crate::PDB::sections). Data is considered uninitialized if its section is
+    /// marked as containing uninitialized data (such as `.bss`), or if its offset lies beyond the
+    /// section's raw data. The latter happens when the linker merges `.bss` into the zero-filled
+    /// tail of `.data`.
+    ///
+    /// Returns `None` if the symbol refers to a section that does not exist.
+    #[must_use]
+    pub fn storage(&self, sections: &[ImageSectionHeader]) -> Option<DataStorage> {
+        let section = (self.offset.section as usize)
+            .checked_sub(1)
+            .and_then(|index| sections.get(index))?;
+
+        let characteristics = section.characteristics;
+        if characteristics.uninitialized_data() && !characteristics.initialized_data() {
+            return Some(DataStorage::Uninitialized);
+        }
+
+        if self.offset.offset >= section.size_of_raw_data {
+            Some(DataStorage::Uninitialized)
+        } else {
+            Some(DataStorage::Initialized)
+        }
+    }
+}
+
+/// The kind of storage backing a [`DataSymbol`].
+///
+/// See [`DataSymbol::storage`].
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum DataStorage {
+    /// The data is stored in the image file, for example in `.data` or `.rdata`.
+    Initialized,
+    /// The data is zero-filled when loaded and takes no space in the image file, for example in
+    /// `.bss`.
+    Uninitialized,
+}
+
 /// Reference to an imported procedure.
 ///
 /// Symbol kind `S_PROCREF`, `S_PROCREF_ST`, `S_LPROCREF`, or `S_LPROCREF_ST`.
-#[derive(Clone, Debug, Eq, PartialEq)]
+#[derive(Clone, Debug, Eq, Hash, PartialEq)]
+#[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
 pub struct ProcedureReferenceSymbol {
     /// Whether the referenced procedure is global or local.
     pub global: bool,
@@ -643,7 +1228,8 @@ impl<'t> TryFromCtx<'t, SymbolKind> for ProcedureReferenceSymbol {
 /// Reference to an imported variable.
 ///
 /// Symbol kind `S_DATAREF`, or `S_DATAREF_ST`.
-#[derive(Clone, Debug, Eq, PartialEq)]
+#[derive(Clone, Debug, Eq, Hash, PartialEq)]
+#[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
 pub struct DataReferenceSymbol {
     /// SUC of the name.
     pub sum_name: u32,
@@ -684,7 +1270,8 @@ impl<'t> TryFromCtx<'t, SymbolKind> for DataReferenceSymbol {
 /// Reference to an annotation.
 ///
 /// Symbol kind `S_ANNOTATIONREF`.
-#[derive(Clone, Debug, Eq, PartialEq)]
+#[derive(Clone, Debug, Eq, Hash, PartialEq)]
+#[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
 pub struct AnnotationReferenceSymbol {
     /// SUC of the name.
     pub sum_name: u32,
@@ -725,7 +1312,8 @@ impl<'t> TryFromCtx<'t, SymbolKind> for AnnotationReferenceSymbol {
 /// Reference to a managed procedure symbol (`S_LMANPROC` or `S_GMANPROC`).
 ///
 /// Symbol kind `S_TOKENREF`.
-#[derive(Clone, Debug, Eq, PartialEq)]
+#[derive(Clone, Debug, Eq, Hash, PartialEq)]
+#[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
 pub struct TokenReferenceSymbol {
     /// SUC of the name.
     pub sum_name: u32,
@@ -765,7 +1353,8 @@ impl<'t> TryFromCtx<'t, SymbolKind> for TokenReferenceSymbol {
 
 /// Subtype of [`TrampolineSymbol`].
 #[non_exhaustive]
-#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+#[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
 pub enum TrampolineType {
     /// An incremental thunk.
     Incremental,
@@ -778,7 +1367,8 @@ pub enum TrampolineType {
 /// Trampoline thunk.
 ///
 /// Symbol kind `S_TRAMPOLINE`.
-#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+#[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
 pub struct TrampolineSymbol {
     /// Trampoline symbol subtype.
     pub tramp_type: TrampolineType,
@@ -822,7 +1412,8 @@ impl TryFromCtx<'_, SymbolKind> for TrampolineSymbol {
 /// A constant value.
 ///
 /// Symbol kind `S_CONSTANT`, or `S_CONSTANT_ST`.
-#[derive(Clone, Debug, Eq, PartialEq)]
+#[derive(Clone, Debug, Eq, Hash, PartialEq)]
+#[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
 pub struct ConstantSymbol {
     /// Whether this constant has metadata type information.
     pub managed: bool,
@@ -851,16 +1442,183 @@ impl<'t> TryFromCtx<'t, SymbolKind> for ConstantSymbol {
     }
 }
 
-/// A user defined type.
-///
-/// Symbol kind `S_UDT`, or `S_UDT_ST`.
-#[derive(Clone, Debug, Eq, PartialEq)]
-pub struct UserDefinedTypeSymbol {
-    /// Identifier of the type.
-    pub type_index: TypeIndex,
-    /// Name of the type.
-    pub name: String,
-}
+impl ConstantSymbol {
+    /// Resolves this constant as a member of an enumeration.
+    ///
+    /// Returns `None` if the constant's type is not an enumeration, or if the modifiers of the type
+    /// form a cycle. Otherwise, the value is
+    /// converted to the enumeration's underlying type, since the PDB stores constants in the
+    /// smallest numeric leaf that fits. A qualifying `enum_name::` prefix is stripped from the
+    /// member name of scoped enumerations.
+    ///
+    /// # Errors
+    ///
+    /// * `Error::TypeNotFound` or `Error::TypeNotIndexed` if the type cannot be found
+    /// * `Error::UnimplementedTypeKind` if the type record cannot be parsed
+    pub fn enum_member(&self, type_finder: &TypeFinder<'_>) -> Result<Option<EnumConstant>> {
+        let enumeration = match strip_modifiers(type_finder, self.type_index)? {
+            Some(TypeData::Enumeration(enumeration)) => enumeration,
+            _ => return Ok(None),
+        };
+
+        let value = match type_finder.find(enumeration.underlying_type)?.parse()? {
+            TypeData::Primitive(PrimitiveType {
+                kind,
+                indirection: None,
+            }) => cast_variant(self.value, kind),
+            _ => self.value,
+        };
+
+        let member_name = self
+            .name
+            .strip_prefix(&enumeration.name)
+            .and_then(|name| name.strip_prefix("::"))
+            .unwrap_or(&self.name)
+            .to_owned();
+
+        Ok(Some(EnumConstant {
+            enum_name: enumeration.name,
+            member_name,
+            underlying_type: enumeration.underlying_type,
+            value,
+        }))
+    }
+
+    /// Formats the value of this constant according to its type.
+    ///
+    /// The PDB stores constants in the smallest numeric leaf that fits, so the raw
+    /// [`value`](Self::value) loses its semantic type. This resolves the type of the constant,
+    /// looking through modifiers such as `const`:
+    ///
+    ///  - Booleans are rendered as `true` or `false`.
+    ///  - Enumerations are rendered as `Enum::Member` if the value matches one of the enumerators.
+    ///    Other values, such as combinations of flags, are rendered as integers.
+    ///  - Integers are converted to their declared type, for instance `-1` instead of `255` for a
+    ///    `signed char`.
+    ///
+    /// Values of all other types, or types whose modifiers form a cycle, are rendered like
+    /// [`Variant`].
+    ///
+    /// # Errors
+    ///
+    /// * `Error::TypeNotFound` or `Error::TypeNotIndexed` if a type cannot be found
+    /// * `Error::UnimplementedTypeKind` if a type record cannot be parsed
+    pub fn display_value(&self, type_finder: &TypeFinder<'_>) -> Result<String> {
+        let data = match strip_modifiers(type_finder, self.type_index)? {
+            Some(data) => data,
+            None => return Ok(self.value.to_string()),
+        };
+
+        let cast = |index: TypeIndex, value: Variant| -> Result<Variant> {
+            Ok(match type_finder.find(index)?.parse()? {
+                TypeData::Primitive(PrimitiveType {
+                    kind,
+                    indirection: None,
+                }) => cast_variant(value, kind),
+                _ => value,
+            })
+        };
+
+        match data {
+            TypeData::Primitive(PrimitiveType {
+                kind:
+                    PrimitiveKind::Bool8
+                    | PrimitiveKind::Bool16
+                    | PrimitiveKind::Bool32
+                    | PrimitiveKind::Bool64,
+                indirection: None,
+            }) => Ok(match self.value.as_bool() {
+                Some(value) => value.to_string(),
+                None => self.value.to_string(),
+            }),
+            TypeData::Primitive(primitive) => Ok(match primitive.indirection {
+                None => cast_variant(self.value, primitive.kind).to_string(),
+                Some(_) => self.value.to_string(),
+            }),
+            TypeData::Enumeration(enumeration) => {
+                let value = cast(enumeration.underlying_type, self.value)?;
+
+                if !enumeration.properties.forward_reference() {
+                    let mut fields = type_finder.fields(enumeration.fields);
+                    while let Some(field) = fields.next()? {
+                        if let TypeData::Enumerate(enumerate) = field {
+                            if cast(enumeration.underlying_type, enumerate.value)? == value {
+                                return Ok(format!("{}::{}", enumeration.name, enumerate.name));
+                            }
+                        }
+                    }
+                }
+
+                Ok(value.to_string())
+            }
+            _ => Ok(self.value.to_string()),
+        }
+    }
+}
+
+/// A [`ConstantSymbol`] resolved as a member of an enumeration.
+///
+/// Created by [`ConstantSymbol::enum_member`].
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct EnumConstant {
+    /// Name of the enumeration.
+    pub enum_name: String,
+    /// Name of the enumerator, without the enumeration name.
+    pub member_name: String,
+    /// The underlying integer type of the enumeration.
+    pub underlying_type: TypeIndex,
+    /// The value of the enumerator, converted to the underlying type.
+    pub value: Variant,
+}
+
+/// Converts `value` to the integer type described by `kind`, wrapping like an `as` cast.
+///
+/// Values are returned unchanged for non-integer kinds.
+fn cast_variant(value: Variant, kind: PrimitiveKind) -> Variant {
+    let wide = match value {
+        Variant::U8(v) => i128::from(v),
+        Variant::U16(v) => i128::from(v),
+        Variant::U32(v) => i128::from(v),
+        Variant::U64(v) => i128::from(v),
+        Variant::I8(v) => i128::from(v),
+        Variant::I16(v) => i128::from(v),
+        Variant::I32(v) => i128::from(v),
+        Variant::I64(v) => i128::from(v),
+        Variant::U128(v) => v as i128,
+        Variant::I128(v) => v,
+    };
+
+    match kind {
+        PrimitiveKind::Char | PrimitiveKind::RChar | PrimitiveKind::I8 => Variant::I8(wide as i8),
+        PrimitiveKind::UChar | PrimitiveKind::Char8 | PrimitiveKind::U8 => Variant::U8(wide as u8),
+        PrimitiveKind::Short | PrimitiveKind::I16 => Variant::I16(wide as i16),
+        PrimitiveKind::UShort
+        | PrimitiveKind::U16
+        | PrimitiveKind::WChar
+        | PrimitiveKind::RChar16 => Variant::U16(wide as u16),
+        PrimitiveKind::Long | PrimitiveKind::I32 => Variant::I32(wide as i32),
+        PrimitiveKind::ULong | PrimitiveKind::U32 | PrimitiveKind::RChar32 => {
+            Variant::U32(wide as u32)
+        }
+        PrimitiveKind::Quad | PrimitiveKind::I64 => Variant::I64(wide as i64),
+        PrimitiveKind::UQuad | PrimitiveKind::U64 => Variant::U64(wide as u64),
+        PrimitiveKind::Octa | PrimitiveKind::I128 => Variant::I128(wide),
+        PrimitiveKind::UOcta | PrimitiveKind::U128 => Variant::U128(wide as u128),
+        _ => value,
+    }
+}
+
+/// A user defined type.
+///
+/// Symbol kind `S_UDT`, or `S_UDT_ST`.
+#[derive(Clone, Debug, Eq, Hash, PartialEq)]
+#[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
+pub struct UserDefinedTypeSymbol {
+    /// Identifier of the type.
+    pub type_index: TypeIndex,
+    /// Name of the type.
+    pub name: String,
+}
 
 impl<'t> TryFromCtx<'t, SymbolKind> for UserDefinedTypeSymbol {
     type Error = Error;
@@ -882,7 +1640,8 @@ impl<'t> TryFromCtx<'t, SymbolKind> for UserDefinedTypeSymbol {
 /// Symbol kinds:
 ///  - `S_LTHREAD32`, `S_LTHREAD32_ST` for local thread storage.
 ///  - `S_GTHREAD32`, or `S_GTHREAD32_ST` for global thread storage.
-#[derive(Clone, Debug, Eq, PartialEq)]
+#[derive(Clone, Debug, Eq, Hash, PartialEq)]
+#[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
 pub struct ThreadStorageSymbol {
     /// Whether this is a global or local thread storage.
     pub global: bool,
@@ -911,6 +1670,205 @@ impl<'t> TryFromCtx<'t, SymbolKind> for ThreadStorageSymbol {
     }
 }
 
+impl ThreadStorageSymbol {
+    /// Returns the offset of this thread local within the TLS block.
+    ///
+    /// `sections` are the section headers of the executable, for example as returned by
+    /// [`PDB::sections`](crate::PDB::sections). The loader copies the `.tls` section into a
+    /// separate block for every thread, so the offset relative to the start of that section is
+    /// the offset into the block referenced by the module's slot in the thread's TLS array.
+    ///
+    /// Returns `None` if the symbol does not refer to the `.tls` section or lies outside of it. The
+    /// size of the TLS block is the section's virtual size. Only if that is zero, the size on disk
+    /// is used instead, since the raw data is padded to the file alignment.
+    #[must_use]
+    pub fn tls_offset(&self, sections: &[ImageSectionHeader]) -> Option<u32> {
+        let section = (self.offset.section as usize)
+            .checked_sub(1)
+            .and_then(|index| sections.get(index))?;
+
+        if section.name() != ".tls" {
+            return None;
+        }
+
+        let size = match section.virtual_size {
+            0 => section.size_of_raw_data,
+            size => size,
+        };
+        (self.offset.offset < size).then_some(self.offset.offset)
+    }
+}
+
+impl UserDefinedTypeSymbol {
+    /// Classifies whether this symbol names a type definition or a typedef.
+    ///
+    /// `S_UDT` records are emitted both for `typedef` declarations and for the canonical names of
+    /// classes, unions and enumerations. This looks up the referenced type to tell them apart, so
+    /// that header reconstruction does not emit a definition twice:
+    ///
+    ///  - [`Definition`](UserDefinedTypeKind::Definition) if the type is a class, union or
+    ///    enumeration that carries this symbol's name, or that is anonymous and only named by this
+    ///    symbol, as in `typedef struct { .. } foo;`.
+    ///  - [`Alias`](UserDefinedTypeKind::Alias) if the type is a class, union or enumeration with a
+    ///    different name, as in `typedef struct foo bar;`.
+    ///  - [`Typedef`](UserDefinedTypeKind::Typedef) for all other types, such as primitives,
+    ///    pointers, modified types and procedures.
+    ///
+    /// # Errors
+    ///
+    /// * `Error::TypeNotFound` or `Error::TypeNotIndexed` if the type cannot be found
+    /// * `Error::UnimplementedTypeKind` if the type record cannot be parsed
+    pub fn classify(&self, type_finder: &TypeFinder<'_>) -> Result<UserDefinedTypeKind> {
+        let name = match type_finder.find(self.type_index)?.parse()? {
+            TypeData::Class(class) => class.name,
+            TypeData::Union(union) => union.name,
+            TypeData::Enumeration(enumeration) => enumeration.name,
+            _ => return Ok(UserDefinedTypeKind::Typedef),
+        };
+
+        if is_anonymous_type_name(&name) || same_qualified_name(&name, &self.name) {
+            Ok(UserDefinedTypeKind::Definition)
+        } else {
+            Ok(UserDefinedTypeKind::Alias)
+        }
+    }
+}
+
+/// Returns whether `name` is a placeholder the compiler assigns to anonymous types.
+fn is_anonymous_type_name(name: &str) -> bool {
+    name.starts_with("<unnamed-")
+        || name.starts_with("<anonymous-")
+        || name.starts_with("__unnamed")
+}
+
+/// Compares two qualified names, treating all spellings of anonymous namespaces as equal.
+///
+/// Type records spell anonymous namespaces as ``` `anonymous-namespace' ```, while `S_UDT` records
+/// use a mangled `?A0x1234abcd` name for the same namespace.
+fn same_qualified_name(a: &str, b: &str) -> bool {
+    fn is_anonymous_namespace(segment: &str) -> bool {
+        segment == "`anonymous-namespace'"
+            || segment == "`anonymous namespace'"
+            || segment.starts_with("?A0x")
+    }
+
+    if a == b {
+        return true;
+    }
+
+    let mut a = a.split("::");
+    let mut b = b.split("::");
+    loop {
+        match (a.next(), b.next()) {
+            (None, None) => return true,
+            (Some(a), Some(b))
+                if a == b || (is_anonymous_namespace(a) && is_anonymous_namespace(b)) => {}
+            _ => return false,
+        }
+    }
+}
+
+/// What a [`UserDefinedTypeSymbol`] names.
+///
+/// See [`UserDefinedTypeSymbol::classify`].
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum UserDefinedTypeKind {
+    /// The canonical name of a class, union or enumeration.
+    Definition,
+    /// A typedef naming a class, union or enumeration that has a name of its own.
+    Alias,
+    /// A typedef of any other type, such as a primitive or a pointer.
+    Typedef,
+}
+
+/// The storage class of a module-scope variable.
+///
+/// See [`VariableSymbol::storage_class`].
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum StorageClass {
+    /// Static data that is visible to all modules, such as a global variable.
+    Static,
+    /// Static data that is only visible within its module, such as a `static` variable in C.
+    Local,
+    /// Thread local data, which has a separate instance for every thread.
+    Tls,
+}
+
+/// A module-scope variable, either regular static data or thread local storage.
+///
+/// This unifies [`DataSymbol`] and [`ThreadStorageSymbol`] for callers that want to enumerate all
+/// variables without distinguishing the two. Use [`SymbolData::into_variable`] to obtain one from a
+/// parsed symbol.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum VariableSymbol {
+    /// Static data, such as a global variable.
+    Data(DataSymbol),
+    /// A thread local variable.
+    ThreadStorage(ThreadStorageSymbol),
+}
+
+impl VariableSymbol {
+    /// Returns the storage class of this variable.
+    #[must_use]
+    pub fn storage_class(&self) -> StorageClass {
+        match self {
+            Self::Data(data) if data.global => StorageClass::Static,
+            Self::Data(_) => StorageClass::Local,
+            Self::ThreadStorage(_) => StorageClass::Tls,
+        }
+    }
+
+    /// Returns whether this variable is visible to all modules.
+    #[must_use]
+    pub fn global(&self) -> bool {
+        match self {
+            Self::Data(data) => data.global,
+            Self::ThreadStorage(data) => data.global,
+        }
+    }
+
+    /// Type identifier of the type of the variable.
+    #[must_use]
+    pub fn type_index(&self) -> TypeIndex {
+        match self {
+            Self::Data(data) => data.type_index,
+            Self::ThreadStorage(data) => data.type_index,
+        }
+    }
+
+    /// Offset of the variable.
+    ///
+    /// For thread local variables, this is the offset within the thread local storage template.
+    #[must_use]
+    pub fn offset(&self) -> PdbInternalSectionOffset {
+        match self {
+            Self::Data(data) => data.offset,
+            Self::ThreadStorage(data) => data.offset,
+        }
+    }
+
+    /// Name of the variable.
+    #[must_use]
+    pub fn name(&self) -> &str {
+        match self {
+            Self::Data(data) => &data.name,
+            Self::ThreadStorage(data) => &data.name,
+        }
+    }
+}
+
+impl From<DataSymbol> for VariableSymbol {
+    fn from(data: DataSymbol) -> Self {
+        Self::Data(data)
+    }
+}
+
+impl From<ThreadStorageSymbol> for VariableSymbol {
+    fn from(data: ThreadStorageSymbol) -> Self {
+        Self::ThreadStorage(data)
+    }
+}
+
 // CV_PROCFLAGS:
 const CV_PFLAG_NOFPO: u8 = 0x01;
 const CV_PFLAG_INT: u8 = 0x02;
@@ -923,7 +1881,8 @@ const CV_PFLAG_OPTDBGINFO: u8 = 0x80;
 
 /// Flags of a [`ProcedureSymbol`].
 #[non_exhaustive]
-#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+#[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
 pub struct ProcedureFlags {
     /// Frame pointer is present (not omitted).
     pub nofpo: bool,
@@ -972,7 +1931,8 @@ impl<'t> TryFromCtx<'t, Endian> for ProcedureFlags {
 ///  - `S_LPROC32_DPC` for DPC procedures
 ///  - `S_GPROC32_ID`, `S_LPROC32_ID`, `S_LPROC32_DPC_ID` for procedures referencing types from the
 ///    ID stream rather than the Type stream.
-#[derive(Clone, Debug, Eq, PartialEq)]
+#[derive(Clone, Debug, Eq, Hash, PartialEq)]
+#[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
 pub struct ProcedureSymbol {
     /// Whether this is a global or local procedure.
     pub global: bool,
@@ -995,6 +1955,12 @@ pub struct ProcedureSymbol {
     /// The type contains the complete signature, including parameters, modifiers and the return
     /// type.
     pub type_index: TypeIndex,
+    /// Whether [`type_index`](Self::type_index) refers to the ID stream instead of the type stream.
+    ///
+    /// This is the case for the `*_ID` symbol kinds, whose index refers to an `LF_FUNC_ID` or
+    /// `LF_MFUNC_ID` record. Resolve it with an [`IdFinder`](crate::IdFinder) rather than a
+    /// [`TypeFinder`].
+    pub type_index_in_id_stream: bool,
     /// Code offset of the start of this procedure.
     pub offset: PdbInternalSectionOffset,
     /// Detailed flags of this procedure.
@@ -1019,6 +1985,7 @@ impl<'t> TryFromCtx<'t, SymbolKind> for ProcedureSymbol {
             dbg_start_offset: buf.parse()?,
             dbg_end_offset: buf.parse()?,
             type_index: buf.parse()?,
+            type_index_in_id_stream: matches!(kind, S_GPROC32_ID | S_LPROC32_ID | S_LPROC32_DPC_ID),
             offset: buf.parse()?,
             flags: buf.parse()?,
             name: parse_symbol_name(&mut buf, kind)?.to_string().to_string(),
@@ -1028,6 +1995,160 @@ impl<'t> TryFromCtx<'t, SymbolKind> for ProcedureSymbol {
     }
 }
 
+impl ProcedureSymbol {
+    /// Returns the start of the procedure's body, right after the prologue.
+    ///
+    /// This is where debuggers place a breakpoint on function entry, since the stack frame and
+    /// locals are set up at this point.
+    #[must_use]
+    pub fn body_start(&self) -> PdbInternalSectionOffset {
+        self.offset.saturating_add(self.dbg_start_offset)
+    }
+
+    /// Returns the end of the procedure's body, which is the start of the epilogue.
+    #[must_use]
+    pub fn body_end(&self) -> PdbInternalSectionOffset {
+        self.offset.saturating_add(self.dbg_end_offset)
+    }
+
+    /// Returns an iterator over the procedures following this one along the
+    /// [`next`](Self::next) chain.
+    ///
+    /// `symbols` must iterate the symbol stream containing this procedure, such as the symbols of
+    /// its module. The iterator stops at the end of the chain, at the first record that is not a
+    /// procedure, or when the chain loops back to a procedure it has already visited.
+    #[must_use]
+    pub fn next_procedures<'t>(&self, symbols: SymbolIter<'t>) -> NextProcedureIter<'t> {
+        NextProcedureIter {
+            symbols,
+            next: self.next,
+            visited: HashSet::new(),
+        }
+    }
+}
+
+/// An iterator following the [`next`](ProcedureSymbol::next) chain of procedures.
+///
+/// Created by [`ProcedureSymbol::next_procedures`].
+#[derive(Debug)]
+pub struct NextProcedureIter<'t> {
+    symbols: SymbolIter<'t>,
+    next: Option<SymbolIndex>,
+    visited: HashSet<SymbolIndex>,
+}
+
+impl FallibleIterator for NextProcedureIter<'_> {
+    type Item = ProcedureSymbol;
+    type Error = Error;
+
+    fn next(&mut self) -> Result<Option<Self::Item>> {
+        let index = match self.next.take() {
+            Some(index) if self.visited.insert(index) => index,
+            _ => return Ok(None),
+        };
+
+        let symbol = match self.symbols.skip_to(index)? {
+            Some(symbol) => symbol,
+            None => return Ok(None),
+        };
+
+        match symbol.parse()? {
+            SymbolData::Procedure(procedure) => {
+                self.next = procedure.next;
+                Ok(Some(procedure))
+            }
+            _ => Ok(None),
+        }
+    }
+}
+
+/// An index of the procedures in a module, sorted by address.
+///
+/// Unlike public symbols, procedures carry the length of their code, so a lookup only succeeds if
+/// the offset actually lies within a procedure. Lookups perform a binary search over the
+/// procedures, sorted by their [`offset`](ProcedureSymbol::offset).
+///
+/// # Example
+///
+/// ```
+/// # use pdb2::FallibleIterator;
+/// #
+/// # fn test() -> pdb2::Result<()> {
+/// let file = std::fs::File::open("fixtures/self/foo.pdb")?;
+/// let mut pdb = pdb2::PDB::open(file)?;
+///
+/// let dbi = pdb.debug_information()?;
+/// let mut modules = dbi.modules()?;
+/// if let Some(module) = modules.next()? {
+///     if let Some(info) = pdb.module_info(&module)? {
+///         let index = pdb2::ModuleProcedureIndex::new(info.symbols()?)?;
+///         let offset = pdb2::PdbInternalSectionOffset::new(1, 0x54f0);
+///         if let Some(procedure) = index.procedure_at(offset) {
+///             println!("{:?} is in {}", offset, procedure.name);
+///         }
+///     }
+/// }
+/// # Ok(())
+/// # }
+/// # test().unwrap()
+/// ```
+#[derive(Clone, Debug, Default)]
+pub struct ModuleProcedureIndex {
+    procedures: Vec<ProcedureSymbol>,
+}
+
+impl ModuleProcedureIndex {
+    /// Collects and sorts all procedures from the symbols of a module.
+    ///
+    /// # Errors
+    ///
+    /// * `Error::SymbolTooShort` if a record has an invalid length
+    /// * `Error::UnexpectedEof` if the symbol stream is truncated mid-record
+    /// * any error returned when parsing a procedure record
+    pub fn new(mut symbols: SymbolIter<'_>) -> Result<Self> {
+        let mut procedures = Vec::new();
+
+        while let Some(symbol) = symbols.next()? {
+            if let S_LPROC32 | S_LPROC32_ST | S_GPROC32 | S_GPROC32_ST | S_LPROC32_ID
+            | S_GPROC32_ID | S_LPROC32_DPC | S_LPROC32_DPC_ID = symbol.raw_kind()
+            {
+                if let SymbolData::Procedure(procedure) = symbol.parse()? {
+                    procedures.push(procedure);
+                }
+            }
+        }
+
+        procedures.sort_by_key(|procedure| (procedure.offset.section, procedure.offset.offset));
+        Ok(ModuleProcedureIndex { procedures })
+    }
+
+    /// Returns all procedures, sorted by section and offset.
+    #[must_use]
+    pub fn procedures(&self) -> &[ProcedureSymbol] {
+        &self.procedures
+    }
+
+    /// Returns the procedure whose code contains the given offset.
+    ///
+    /// Returns `None` if the offset is not covered by any procedure of this module. If procedures
+    /// overlap, the one starting closest before the offset is returned.
+    #[must_use]
+    pub fn procedure_at(&self, offset: PdbInternalSectionOffset) -> Option<&ProcedureSymbol> {
+        let key = (offset.section, offset.offset);
+        let index = self.procedures.partition_point(|procedure| {
+            (procedure.offset.section, procedure.offset.offset) <= key
+        });
+
+        let procedure = self.procedures.get(index.checked_sub(1)?)?;
+        let end = procedure.offset.offset.saturating_add(procedure.len);
+        if procedure.offset.section != offset.section || offset.offset >= end {
+            return None;
+        }
+
+        Some(procedure)
+    }
+}
+
 /// A managed procedure, such as a function or method.
 ///
 /// Symbol kinds:
@@ -1035,7 +2156,8 @@ impl<'t> TryFromCtx<'t, SymbolKind> for ProcedureSymbol {
 /// - `S_LMANPROC`, `S_LMANPROCIA64` for local procedures
 ///
 /// `S_GMANPROCIA64` and `S_LMANPROCIA64` are only mentioned, there is no available source.
-#[derive(Clone, Debug, Eq, PartialEq)]
+#[derive(Clone, Debug, Eq, Hash, PartialEq)]
+#[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
 pub struct ManagedProcedureSymbol {
     /// Whether this is a global or local procedure.
     pub global: bool,
@@ -1051,7 +2173,7 @@ pub struct ManagedProcedureSymbol {
     pub dbg_start_offset: u32,
     /// End offset of the procedure's body code, which marks the start of the epilogue.
     pub dbg_end_offset: u32,
-    /// COM+ metadata token
+    /// COM+ metadata token of the method, referring to the `MethodDef` table.
     pub token: COMToken,
     /// Code offset of the start of this procedure.
     pub offset: PdbInternalSectionOffset,
@@ -1091,7 +2213,8 @@ impl<'t> TryFromCtx<'t, SymbolKind> for ManagedProcedureSymbol {
 /// The callsite of an inlined function.
 ///
 /// Symbol kind `S_INLINESITE`, or `S_INLINESITE2`.
-#[derive(Clone, Debug, Eq, PartialEq)]
+#[derive(Clone, Debug, Eq, Hash, PartialEq)]
+#[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
 pub struct InlineSiteSymbol {
     /// Index of the parent function.
     ///
@@ -1128,10 +2251,36 @@ impl<'t> TryFromCtx<'t, SymbolKind> for InlineSiteSymbol {
     }
 }
 
+impl InlineSiteSymbol {
+    /// Returns the total size of the inlined code in bytes.
+    ///
+    /// This sums up the code lengths set by `ChangeCodeLength` and `ChangeCodeLengthAndCodeOffset`
+    /// annotations, which gives an estimate of the code added by inlining even for `S_INLINESITE`
+    /// records that lack an invocation count. Decoding stops at the first malformed annotation.
+    #[must_use]
+    pub fn code_length(&self) -> u32 {
+        let mut annotations = self.annotations.iter();
+        let mut length = 0u32;
+
+        while let Ok(Some(annotation)) = annotations.next() {
+            match annotation {
+                BinaryAnnotation::ChangeCodeLength(len)
+                | BinaryAnnotation::ChangeCodeLengthAndCodeOffset(len, _) => {
+                    length = length.saturating_add(len);
+                }
+                _ => {}
+            }
+        }
+
+        length
+    }
+}
+
 /// Reference to build information.
 ///
 /// Symbol kind `S_BUILDINFO`.
-#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+#[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
 pub struct BuildInfoSymbol {
     /// Index of the build information record.
     pub id: IdIndex,
@@ -1152,7 +2301,19 @@ impl<'t> TryFromCtx<'t, SymbolKind> for BuildInfoSymbol {
 /// Name of the object file of this module.
 ///
 /// Symbol kind `S_OBJNAME`, or `S_OBJNAME_ST`.
-#[derive(Clone, Debug, Eq, PartialEq)]
+///
+/// Besides real object files, the toolchain emits this record for synthetic modules with special
+/// names:
+///
+///  - `* CIL *`: Code generated from CIL (MSIL) by link-time code generation. The objects that
+///    contributed to this module are not recorded individually.
+///  - `* Linker *`: Contributions of the linker itself, such as section headers, COFF groups and
+///    thunks.
+///
+/// Both have a signature of `0`. Use [`signature_kind`](Self::signature_kind) to tell them apart
+/// from real object files.
+#[derive(Clone, Debug, Eq, Hash, PartialEq)]
+#[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
 pub struct ObjNameSymbol {
     /// Signature.
     pub signature: u32,
@@ -1160,6 +2321,42 @@ pub struct ObjNameSymbol {
     pub name: String,
 }
 
+impl ObjNameSymbol {
+    /// Classifies the signature of this object, see [`ObjSignatureKind`].
+    #[must_use]
+    pub fn signature_kind(&self) -> ObjSignatureKind {
+        match (self.signature, self.name.as_str()) {
+            (0, "* CIL *") => ObjSignatureKind::Cil,
+            (0, "* Linker *") => ObjSignatureKind::Linker,
+            (0, _) => ObjSignatureKind::None,
+            (signature, _) => ObjSignatureKind::Signature(signature),
+        }
+    }
+}
+
+/// The kind of signature of an [`ObjNameSymbol`].
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum ObjSignatureKind {
+    /// The synthetic `* CIL *` module, produced by link-time code generation.
+    Cil,
+    /// The synthetic `* Linker *` module.
+    Linker,
+    /// An object file without a signature.
+    ///
+    /// Most compilers, including MSVC, leave the signature of regular object files empty.
+    None,
+    /// An object file with the given non-zero signature.
+    Signature(u32),
+}
+
+impl ObjSignatureKind {
+    /// Returns `true` if this is a synthetic module that does not correspond to an object file.
+    #[must_use]
+    pub fn is_synthetic(self) -> bool {
+        matches!(self, Self::Cil | Self::Linker)
+    }
+}
+
 impl<'t> TryFromCtx<'t, SymbolKind> for ObjNameSymbol {
     type Error = Error;
 
@@ -1176,7 +2373,12 @@ impl<'t> TryFromCtx<'t, SymbolKind> for ObjNameSymbol {
 }
 
 /// A version number refered to by `CompileFlagsSymbol`.
-#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+///
+/// Versions are compared by `major`, `minor`, `build` and `qfe` in that order. A missing `qfe` is
+/// treated as `0`, both for comparison and hashing, and when formatting the version as
+/// `major.minor.build.qfe`.
+#[derive(Clone, Copy, Debug)]
+#[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
 pub struct CompilerVersion {
     /// The major version number.
     pub major: u16,
@@ -1205,9 +2407,49 @@ impl<'t> TryFromCtx<'t, bool> for CompilerVersion {
     }
 }
 
+impl CompilerVersion {
+    fn key(&self) -> (u16, u16, u16, u16) {
+        (self.major, self.minor, self.build, self.qfe.unwrap_or(0))
+    }
+}
+
+impl PartialEq for CompilerVersion {
+    fn eq(&self, other: &Self) -> bool {
+        self.key() == other.key()
+    }
+}
+
+impl Eq for CompilerVersion {}
+
+impl std::hash::Hash for CompilerVersion {
+    fn hash<H: std::hash::Hasher>(&self, state: &mut H) {
+        self.key().hash(state);
+    }
+}
+
+impl PartialOrd for CompilerVersion {
+    fn partial_cmp(&self, other: &Self) -> Option<std::cmp::Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Ord for CompilerVersion {
+    fn cmp(&self, other: &Self) -> std::cmp::Ordering {
+        self.key().cmp(&other.key())
+    }
+}
+
+impl fmt::Display for CompilerVersion {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let (major, minor, build, qfe) = self.key();
+        write!(f, "{major}.{minor}.{build}.{qfe}")
+    }
+}
+
 /// Compile flags declared in `CompileFlagsSymbol`.
 #[non_exhaustive]
-#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+#[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
 pub struct CompileFlags {
     /// Compiled for edit and continue.
     pub edit_and_continue: bool,
@@ -1233,6 +2475,28 @@ pub struct CompileFlags {
     pub pgo: bool,
     /// This is a .exp module.
     pub exp_module: bool,
+    raw: u16,
+}
+
+impl CompileFlags {
+    /// Returns the raw flags word this was parsed from, including bits that are not decoded.
+    #[must_use]
+    pub fn raw(self) -> u16 {
+        self.raw
+    }
+
+    /// Returns the bits of [`raw`](Self::raw) that do not correspond to any decoded flag.
+    ///
+    /// `sdl`, `pgo` and `exp_module` are only defined for `S_COMPILE3`, so for older records their
+    /// bits are reported here if set.
+    #[must_use]
+    pub fn unknown_bits(self) -> u16 {
+        let known = 0x01ff
+            | u16::from(self.sdl) << 9
+            | u16::from(self.pgo) << 10
+            | u16::from(self.exp_module) << 11;
+        self.raw & !known
+    }
 }
 
 impl<'t> TryFromCtx<'t, SymbolKind> for CompileFlags {
@@ -1257,6 +2521,7 @@ impl<'t> TryFromCtx<'t, SymbolKind> for CompileFlags {
             sdl: (raw >> 9) & 1 != 0 && is_compile3,
             pgo: (raw >> 10) & 1 != 0 && is_compile3,
             exp_module: (raw >> 11) & 1 != 0 && is_compile3,
+            raw,
         };
 
         Ok((flags, 3))
@@ -1266,7 +2531,8 @@ impl<'t> TryFromCtx<'t, SymbolKind> for CompileFlags {
 /// Flags used to compile a module.
 ///
 /// Symbol kind `S_COMPILE2`, `S_COMPILE2_ST`, or `S_COMPILE3`.
-#[derive(Clone, Debug, Eq, PartialEq)]
+#[derive(Clone, Debug, Eq, Hash, PartialEq)]
+#[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
 pub struct CompileFlagsSymbol {
     /// The source code language.
     pub language: SourceLanguage,
@@ -1283,6 +2549,16 @@ pub struct CompileFlagsSymbol {
     // TODO: Command block for S_COMPILE2?
 }
 
+impl CompileFlagsSymbol {
+    /// Returns the instruction set of the code in this module.
+    ///
+    /// See [`CPUType::instruction_set`].
+    #[must_use]
+    pub fn instruction_set(&self) -> InstructionSet {
+        self.cpu_type.instruction_set()
+    }
+}
+
 impl<'t> TryFromCtx<'t, SymbolKind> for CompileFlagsSymbol {
     type Error = Error;
 
@@ -1306,7 +2582,8 @@ impl<'t> TryFromCtx<'t, SymbolKind> for CompileFlagsSymbol {
 /// A using namespace directive.
 ///
 /// Symbol kind `S_UNAMESPACE`, or `S_UNAMESPACE_ST`.
-#[derive(Clone, Debug, Eq, PartialEq)]
+#[derive(Clone, Debug, Eq, Hash, PartialEq)]
+#[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
 pub struct UsingNamespaceSymbol {
     /// The name of the imported namespace.
     pub name: String,
@@ -1340,7 +2617,8 @@ const CV_LVARFLAG_ISENREG_STAT: u16 = 0x200;
 
 /// Flags for a [`LocalSymbol`].
 #[non_exhaustive]
-#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+#[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
 pub struct LocalVariableFlags {
     /// Variable is a parameter.
     pub isparam: bool,
@@ -1391,7 +2669,8 @@ impl<'t> TryFromCtx<'t, Endian> for LocalVariableFlags {
 /// A local symbol in optimized code.
 ///
 /// Symbol kind `S_LOCAL`.
-#[derive(Clone, Debug, Eq, PartialEq)]
+#[derive(Clone, Debug, Eq, Hash, PartialEq)]
+#[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
 pub struct LocalSymbol {
     /// The type of the symbol.
     pub type_index: TypeIndex,
@@ -1438,7 +2717,8 @@ impl<'t> TryFromCtx<'t, SymbolKind> for LocalSymbol {
 /// A managed local variable slot.
 ///
 /// Symbol kind `S_MANSLOT`.
-#[derive(Clone, Debug, Eq, PartialEq)]
+#[derive(Clone, Debug, Eq, Hash, PartialEq)]
+#[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
 pub struct ManagedSlotSymbol {
     /// Slot index.
     pub slot: u32,
@@ -1452,6 +2732,18 @@ pub struct ManagedSlotSymbol {
     pub name: String,
 }
 
+impl ManagedSlotSymbol {
+    /// Returns [`type_index`](Self::type_index) as a .NET metadata token.
+    ///
+    /// For managed code, this is a token of the variable's type rather than an index into the TPI
+    /// stream. It usually refers to the `TypeDef`, `TypeRef` or `TypeSpec` table, see
+    /// [`COMToken::table`].
+    #[must_use]
+    pub fn type_token(&self) -> COMToken {
+        COMToken(self.type_index.0)
+    }
+}
+
 impl<'t> TryFromCtx<'t, SymbolKind> for ManagedSlotSymbol {
     type Error = Error;
 
@@ -1472,7 +2764,8 @@ impl<'t> TryFromCtx<'t, SymbolKind> for ManagedSlotSymbol {
 
 // https://github.com/Microsoft/microsoft-pdb/blob/082c5290e5aff028ae84e43affa8be717aa7af73/include/cvinfo.h#L3102
 /// An address range of a live range.
-#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+#[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
 pub struct AddressRange {
     /// Offset of the range.
     pub offset: PdbInternalSectionOffset,
@@ -1495,10 +2788,48 @@ impl<'t> TryFromCtx<'t, Endian> for AddressRange {
     }
 }
 
+impl AddressRange {
+    /// Splits this range into the sub-ranges that are not covered by any of the `gaps`.
+    ///
+    /// Gap offsets are relative to the start of this range, as stored in `DefRange*` symbols. Gaps
+    /// may be unordered, overlap each other, or extend past the end of the range. The returned
+    /// sub-ranges are sorted by offset and given as pairs of start offset and length.
+    #[must_use]
+    pub fn subtract_gaps(&self, gaps: &[AddressGap]) -> Vec<(PdbInternalSectionOffset, u16)> {
+        let mut sorted = gaps.to_vec();
+        sorted.sort_unstable_by_key(|gap| gap.gap_start_offset);
+
+        let end = u32::from(self.cb_range);
+        let mut cursor = 0;
+        let mut intervals = Vec::with_capacity(sorted.len() + 1);
+
+        let mut push = |start: u32, end: u32| {
+            intervals.push((self.offset.saturating_add(start), (end - start) as u16));
+        };
+
+        for gap in sorted {
+            let gap_start = u32::from(gap.gap_start_offset).min(end);
+            let gap_end = (gap_start + u32::from(gap.cb_range)).min(end);
+
+            if gap_start > cursor {
+                push(cursor, gap_start);
+            }
+            cursor = cursor.max(gap_end);
+        }
+
+        if cursor < end {
+            push(cursor, end);
+        }
+
+        intervals
+    }
+}
+
 // https://github.com/Microsoft/microsoft-pdb/blob/082c5290e5aff028ae84e43affa8be717aa7af73/include/cvinfo.h#L4456
 /// Flags of an [`ExportSymbol`].
 #[non_exhaustive]
-#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+#[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
 pub struct ExportSymbolFlags {
     /// An exported constant.
     pub constant: bool,
@@ -1536,7 +2867,8 @@ impl<'t> TryFromCtx<'t, Endian> for ExportSymbolFlags {
 /// An exported symbol.
 ///
 /// Symbol kind `S_EXPORT`.
-#[derive(Clone, Debug, Eq, PartialEq)]
+#[derive(Clone, Debug, Eq, Hash, PartialEq)]
+#[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
 pub struct ExportSymbol {
     /// Ordinal of the symbol.
     pub ordinal: u16,
@@ -1565,11 +2897,17 @@ impl<'t> TryFromCtx<'t, SymbolKind> for ExportSymbol {
 /// A label symbol.
 ///
 /// Symbol kind `S_LABEL32`, `S_LABEL16`, or `S_LABEL32_ST`.
-#[derive(Clone, Debug, Eq, PartialEq)]
+#[derive(Clone, Debug, Eq, Hash, PartialEq)]
+#[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
 pub struct LabelSymbol {
     /// Code offset of the start of this label.
     pub offset: PdbInternalSectionOffset,
     /// Detailed flags of this label.
+    ///
+    /// Labels share the flags layout of procedures, but only [`far`](ProcedureFlags::far) and
+    /// [`int`](ProcedureFlags::int) apply to them, marking targets of far jumps and interrupt
+    /// returns in segmented code. The remaining bits describe properties of functions, such as
+    /// `noinline` or `optdbginfo`, and are not meaningful for labels.
     pub flags: ProcedureFlags,
     /// Name of the symbol.
     pub name: String,
@@ -1591,10 +2929,31 @@ impl<'t> TryFromCtx<'t, SymbolKind> for LabelSymbol {
     }
 }
 
+impl LabelSymbol {
+    /// Returns whether this label points into code rather than data.
+    ///
+    /// Compilers emit labels for jump targets in code, but hand-written assembly may also label
+    /// data. The label is considered a code label if its section contains code or is executable.
+    /// `sections` are the section headers of the executable, for example as returned by
+    /// [`PDB::sections`](crate::PDB::sections).
+    ///
+    /// Returns `None` if the label refers to a section that does not exist.
+    #[must_use]
+    pub fn is_code_label(&self, sections: &[ImageSectionHeader]) -> Option<bool> {
+        let section = (self.offset.section as usize)
+            .checked_sub(1)
+            .and_then(|index| sections.get(index))?;
+
+        let characteristics = section.characteristics;
+        Some(characteristics.executable() || characteristics.execute())
+    }
+}
+
 /// A block symbol.
 ///
 /// Symbol kind `S_BLOCK32`, or `S_BLOCK32_ST`.
-#[derive(Clone, Debug, Eq, PartialEq)]
+#[derive(Clone, Debug, Eq, Hash, PartialEq)]
+#[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
 pub struct BlockSymbol {
     /// The parent scope that this block is nested in.
     pub parent: SymbolIndex,
@@ -1631,7 +2990,8 @@ impl<'t> TryFromCtx<'t, SymbolKind> for BlockSymbol {
 /// The address of the variable is the value in the register + offset (e.g. %EBP + 8).
 ///
 /// Symbol kind `S_REGREL32`.
-#[derive(Clone, Debug, Eq, PartialEq)]
+#[derive(Clone, Debug, Eq, Hash, PartialEq)]
+#[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
 pub struct RegisterRelativeSymbol {
     /// The variable offset.
     pub offset: i32,
@@ -1680,7 +3040,8 @@ impl<'t> TryFromCtx<'t, SymbolKind> for RegisterRelativeSymbol {
 }
 
 /// Thunk adjustor
-#[derive(Clone, Debug, Eq, PartialEq)]
+#[derive(Clone, Debug, Eq, Hash, PartialEq)]
+#[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
 pub struct ThunkAdjustor {
     delta: u16,
     target: String,
@@ -1688,7 +3049,8 @@ pub struct ThunkAdjustor {
 
 /// A thunk kind
 #[non_exhaustive]
-#[derive(Clone, Debug, Eq, PartialEq)]
+#[derive(Clone, Debug, Eq, Hash, PartialEq)]
+#[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
 pub enum ThunkKind {
     /// Standard thunk
     NoType,
@@ -1707,7 +3069,8 @@ pub enum ThunkKind {
 /// A thunk symbol.
 ///
 /// Symbol kind `S_THUNK32`, or `S_THUNK32_ST`.
-#[derive(Clone, Debug, Eq, PartialEq)]
+#[derive(Clone, Debug, Eq, Hash, PartialEq)]
+#[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
 pub struct ThunkSymbol {
     /// The parent scope that this thunk is nested in.
     pub parent: Option<SymbolIndex>,
@@ -1771,7 +3134,8 @@ const CV_SEPCODEFLAG_RETURNS_TO_PARENT: u32 = 0x02;
 
 /// Flags for a [`SeparatedCodeSymbol`].
 #[non_exhaustive]
-#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+#[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
 pub struct SeparatedCodeFlags {
     /// `S_SEPCODE` doubles as lexical scope.
     pub islexicalscope: bool,
@@ -1794,10 +3158,24 @@ impl<'t> TryFromCtx<'t, Endian> for SeparatedCodeFlags {
     }
 }
 
+/// A symbol record that could not be parsed.
+///
+/// Returned by [`Symbol::parse_lenient`] for symbol kinds that are not supported by this crate, and
+/// for records that are malformed.
+#[derive(Clone, Debug, Eq, Hash, PartialEq)]
+#[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
+pub struct UnknownSymbol {
+    /// The raw kind of the symbol record.
+    pub kind: SymbolKind,
+    /// The contents of the record following its kind.
+    pub data: Vec<u8>,
+}
+
 /// A separated code symbol.
 ///
 /// Symbol kind `S_SEPCODE`.
-#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+#[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
 pub struct SeparatedCodeSymbol {
     /// The parent scope that this block is nested in.
     pub parent: SymbolIndex,
@@ -1844,10 +3222,50 @@ impl<'t> TryFromCtx<'t, SymbolKind> for SeparatedCodeSymbol {
     }
 }
 
+impl SeparatedCodeSymbol {
+    /// Resolves the procedure that this separated code block was split off from.
+    ///
+    /// The procedure is located by following [`parent`](Self::parent), skipping over enclosing
+    /// blocks and separated code blocks. Its [`offset`](ProcedureSymbol::offset) usually equals
+    /// [`parent_offset`](Self::parent_offset), which allows attributing cold code to its owning
+    /// function in profiles. Returns `None` if the parent chain does not lead to a procedure.
+    ///
+    /// # Errors
+    ///
+    /// * `Error::SymbolTooShort` if a record has an invalid length
+    /// * `Error::UnexpectedEof` if the symbol stream is truncated mid-record
+    /// * any error from parsing a record in the parent chain
+    pub fn parent_procedure(&self, mut symbols: SymbolIter<'_>) -> Result<Option<ProcedureSymbol>> {
+        let mut parent = self.parent;
+        while parent.0 != 0 {
+            let symbol = match symbols.skip_to(parent)? {
+                Some(symbol) => symbol,
+                None => break,
+            };
+
+            let next = match symbol.parse()? {
+                SymbolData::Procedure(procedure) => return Ok(Some(procedure)),
+                SymbolData::Block(block) => block.parent,
+                SymbolData::SeparatedCode(code) => code.parent,
+                _ => break,
+            };
+
+            // parents always precede their children, which also rules out cycles
+            if next >= parent {
+                break;
+            }
+            parent = next;
+        }
+
+        Ok(None)
+    }
+}
+
 /// An OEM symbol.
 ///
 /// Symbol kind `S_OEM`.
-#[derive(Clone, Debug, Eq, PartialEq)]
+#[derive(Clone, Debug, Eq, Hash, PartialEq)]
+#[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
 pub struct OemSymbol {
     /// OEM's identifier (16B GUID).
     pub id_oem: String,
@@ -1878,7 +3296,8 @@ impl<'t> TryFromCtx<'t, SymbolKind> for OemSymbol {
 /// Environment block split off from `S_COMPILE2`.
 ///
 /// Symbol kind `S_ENVBLOCK`.
-#[derive(Clone, Debug, Eq, PartialEq)]
+#[derive(Clone, Debug, Eq, Hash, PartialEq)]
+#[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
 pub struct EnvBlockSymbol {
     /// EC flag (previously called `rev`).
     pub edit_and_continue: bool,
@@ -1908,10 +3327,25 @@ impl<'t> TryFromCtx<'t, SymbolKind> for EnvBlockSymbol {
     }
 }
 
+impl EnvBlockSymbol {
+    /// Returns the key/value pairs stored in this environment block.
+    ///
+    /// The strings in [`rgsz`](Self::rgsz) alternate between keys, such as `cwd`, `exe` or `cmd`,
+    /// and their values. The block is terminated by an empty string, followed by padding, which is
+    /// where iteration stops. A trailing key without a value is returned with an empty value.
+    pub fn entries(&self) -> impl Iterator<Item = (&str, &str)> + '_ {
+        self.rgsz
+            .chunks(2)
+            .map(|pair| (pair[0].as_str(), pair.get(1).map_or("", String::as_str)))
+            .take_while(|(key, _)| !key.is_empty())
+    }
+}
+
 /// A COFF section in a PE executable.
 ///
 /// Symbol kind `S_SECTION`.
-#[derive(Clone, Debug, Eq, PartialEq)]
+#[derive(Clone, Debug, Eq, Hash, PartialEq)]
+#[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
 pub struct SectionSymbol {
     /// Section number.
     pub isec: u16,
@@ -1949,10 +3383,160 @@ impl<'t> TryFromCtx<'t, SymbolKind> for SectionSymbol {
     }
 }
 
+/// A difference between an `S_SECTION` record and the corresponding PE section header.
+///
+/// See [`validate_sections`].
+#[non_exhaustive]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum SectionMismatch {
+    /// The section number of the symbol does not refer to a section header.
+    MissingHeader {
+        /// One-based section number of the symbol.
+        section: u16,
+    },
+    /// The section names differ.
+    Name {
+        /// One-based section number.
+        section: u16,
+        /// Name stored in the `S_SECTION` record.
+        symbol: String,
+        /// Name stored in the section header.
+        header: String,
+    },
+    /// The relative virtual addresses differ.
+    Rva {
+        /// One-based section number.
+        section: u16,
+        /// RVA stored in the `S_SECTION` record.
+        symbol: u32,
+        /// Virtual address stored in the section header.
+        header: u32,
+    },
+    /// The section sizes differ.
+    Size {
+        /// One-based section number.
+        section: u16,
+        /// Size stored in the `S_SECTION` record.
+        symbol: u32,
+        /// Virtual size stored in the section header.
+        header: u32,
+    },
+    /// The section characteristics differ.
+    Characteristics {
+        /// One-based section number.
+        section: u16,
+        /// Characteristics stored in the `S_SECTION` record.
+        symbol: SectionCharacteristics,
+        /// Characteristics stored in the section header.
+        header: SectionCharacteristics,
+    },
+}
+
+/// Collects all `S_SECTION` records from a symbol stream.
+///
+/// The linker emits these records into the symbols of the `* Linker *` module. They can be used
+/// to translate addresses when the section headers are not available, see
+/// [`PdbInternalSectionOffset::to_internal_rva_from_sections`].
+///
+/// # Errors
+///
+/// * `Error::SymbolTooShort` or `Error::UnexpectedEof` if the symbol stream is malformed
+/// * any error from parsing an `S_SECTION` record
+pub fn section_symbols(mut symbols: SymbolIter<'_>) -> Result<Vec<SectionSymbol>> {
+    let mut sections = Vec::new();
+
+    while let Some(symbol) = symbols.next()? {
+        if symbol.raw_kind() == S_SECTION {
+            if let SymbolData::Section(section) = symbol.parse()? {
+                sections.push(section);
+            }
+        }
+    }
+
+    Ok(sections)
+}
+
+/// Cross-checks `S_SECTION` records against the section headers of the executable.
+///
+/// `S_SECTION` records are emitted by the linker into the symbols of the `* Linker *` module and
+/// duplicate the name, address, size and characteristics of the PE section headers, for example as
+/// returned by [`PDB::sections`](crate::PDB::sections). Differences between the two indicate
+/// that the PDB was relinked or rewritten inconsistently. Note that the linker may legitimately
+/// change characteristics after emitting the record, such as dropping the write flag of `.idata`.
+///
+/// All mismatches are returned, in the order of the records. Section headers without a
+/// corresponding `S_SECTION` record are not reported.
+///
+/// # Errors
+///
+/// * `Error::SymbolTooShort` or `Error::UnexpectedEof` if the symbol stream is malformed
+/// * any error from parsing an `S_SECTION` record
+pub fn validate_sections(
+    mut symbols: SymbolIter<'_>,
+    sections: &[ImageSectionHeader],
+) -> Result<Vec<SectionMismatch>> {
+    let mut mismatches = Vec::new();
+
+    while let Some(symbol) = symbols.next()? {
+        if symbol.raw_kind() != S_SECTION {
+            continue;
+        }
+
+        let data = match symbol.parse()? {
+            SymbolData::Section(data) => data,
+            _ => continue,
+        };
+
+        let section = data.isec;
+        let header = match (section as usize)
+            .checked_sub(1)
+            .and_then(|index| sections.get(index))
+        {
+            Some(header) => header,
+            None => {
+                mismatches.push(SectionMismatch::MissingHeader { section });
+                continue;
+            }
+        };
+
+        if data.name != header.name() {
+            mismatches.push(SectionMismatch::Name {
+                section,
+                symbol: data.name.clone(),
+                header: header.name().to_owned(),
+            });
+        }
+        if data.rva != header.virtual_address {
+            mismatches.push(SectionMismatch::Rva {
+                section,
+                symbol: data.rva,
+                header: header.virtual_address,
+            });
+        }
+        if data.cb != header.virtual_size {
+            mismatches.push(SectionMismatch::Size {
+                section,
+                symbol: data.cb,
+                header: header.virtual_size,
+            });
+        }
+        if data.characteristics != header.characteristics {
+            mismatches.push(SectionMismatch::Characteristics {
+                section,
+                symbol: data.characteristics,
+                header: header.characteristics,
+            });
+        }
+    }
+
+    Ok(mismatches)
+}
+
 /// A COFF section in a PE executable.
 ///
 /// Symbol kind `S_COFFGROUP`.
-#[derive(Clone, Debug, Eq, PartialEq)]
+#[derive(Clone, Debug, Eq, Hash, PartialEq)]
+#[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
 pub struct CoffGroupSymbol {
     /// COFF group's CB.
     pub cb: u32,
@@ -1983,7 +3567,8 @@ impl<'t> TryFromCtx<'t, SymbolKind> for CoffGroupSymbol {
 
 // https://github.com/Microsoft/microsoft-pdb/blob/082c5290e5aff028ae84e43affa8be717aa7af73/include/cvinfo.h#L3111
 /// A gap in a live range.
-#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+#[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
 pub struct AddressGap {
     /// Relative offset from the beginning of the live range
     pub gap_start_offset: u16,
@@ -2006,11 +3591,60 @@ impl<'t> TryFromCtx<'t, Endian> for AddressGap {
     }
 }
 
+/// Parses the gaps that fill the remainder of a def-range record.
+///
+/// See <https://github.com/Microsoft/microsoft-pdb/blob/082c5290e5aff028ae84e43affa8be717aa7af73/include/cvinfo.h#L4313>
+fn parse_address_gaps(buf: &mut ParseBuffer<'_>) -> Result<Vec<AddressGap>> {
+    let mut gaps = Vec::with_capacity(buf.len() / 4);
+    while buf.len() >= 4
+    /* sizeof(CV_LVAR_ADDR_GAP) */
+    {
+        gaps.push(buf.parse()?);
+    }
+    Ok(gaps)
+}
+
+/// An iterator over the live ranges of a def-range symbol.
+///
+/// Yields the address ranges in which the value is available, with all gaps removed, along with the
+/// [`maybe`](RangeFlags::maybe) flag of the symbol. Created by the `live_ranges` method of the
+/// `DefRange*` symbols, such as [`DefRangeRegisterSymbol::live_ranges`].
+#[derive(Clone, Debug)]
+pub struct LiveRangeIter {
+    ranges: std::vec::IntoIter<(PdbInternalSectionOffset, u16)>,
+    maybe: bool,
+}
+
+impl LiveRangeIter {
+    fn new(range: AddressRange, gaps: &[AddressGap], maybe: bool) -> Self {
+        Self {
+            ranges: range.subtract_gaps(gaps).into_iter(),
+            maybe,
+        }
+    }
+}
+
+impl Iterator for LiveRangeIter {
+    type Item = (AddressRange, bool);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let (offset, cb_range) = self.ranges.next()?;
+        Some((AddressRange { offset, cb_range }, self.maybe))
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.ranges.size_hint()
+    }
+}
+
+impl ExactSizeIterator for LiveRangeIter {}
+
 // https://github.com/Microsoft/microsoft-pdb/blob/082c5290e5aff028ae84e43affa8be717aa7af73/include/cvinfo.h#L4209
 /// A live range of sub field of variable
 ///
 /// Symbol kind `S_DEFRANGE`.
-#[derive(Clone, Debug, Eq, PartialEq)]
+#[derive(Clone, Debug, Eq, Hash, PartialEq)]
+#[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
 pub struct DefRangeSymbol {
     /// DIA program to evaluate the value of the symbol
     pub program: u32,
@@ -2026,29 +3660,33 @@ impl TryFromCtx<'_, SymbolKind> for DefRangeSymbol {
     fn try_from_ctx(this: &'_ [u8], _kind: SymbolKind) -> Result<(Self, usize)> {
         let mut buf = ParseBuffer::from(this);
 
-        // https://github.com/Microsoft/microsoft-pdb/blob/082c5290e5aff028ae84e43affa8be717aa7af73/include/cvinfo.h#L4313
-        let gap_count = (
-            buf.len() + 4 /* sizeof(reclen) + buf offset */
-                - 16 /* sizeof(DEFRANGESYM) */
-        ) / 4 /* sizeof(CV_LVAR_ADDR_GAP) */;
-        let mut symbol = Self {
+        let symbol = Self {
             program: buf.parse()?,
             range: buf.parse()?,
-            gaps: vec![],
+            gaps: parse_address_gaps(&mut buf)?,
         };
-        for _ in 0..gap_count {
-            symbol.gaps.push(buf.parse()?);
-        }
 
         Ok((symbol, buf.pos()))
     }
 }
 
+impl DefRangeSymbol {
+    /// Returns the address ranges in which the value is available.
+    ///
+    /// The gaps are subtracted from the range. This symbol has no flags, so ranges are
+    /// never tagged as `maybe`.
+    #[must_use]
+    pub fn live_ranges(&self) -> LiveRangeIter {
+        LiveRangeIter::new(self.range, &self.gaps, false)
+    }
+}
+
 // https://github.com/Microsoft/microsoft-pdb/blob/082c5290e5aff028ae84e43affa8be717aa7af73/include/cvinfo.h#L3102
 /// A live range of sub field of variable. like locala.i
 ///
 /// Symbol kind `S_DEFRANGE_SUBFIELD`
-#[derive(Clone, Debug, Eq, PartialEq)]
+#[derive(Clone, Debug, Eq, Hash, PartialEq)]
+#[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
 pub struct DefRangeSubFieldSymbol {
     /// DIA program to evaluate the value of the symbol
     pub program: u32,
@@ -2066,28 +3704,32 @@ impl TryFromCtx<'_, SymbolKind> for DefRangeSubFieldSymbol {
     fn try_from_ctx(this: &'_ [u8], _kind: SymbolKind) -> Result<(Self, usize)> {
         let mut buf = ParseBuffer::from(this);
 
-        // https://github.com/Microsoft/microsoft-pdb/blob/082c5290e5aff028ae84e43affa8be717aa7af73/include/cvinfo.h#L4313
-        let gap_count = (
-            buf.len() + 4 /* sizeof(reclen) + buf offset */
-                - 20 /* sizeof(DEFRANGESYMSUBFIELD) */
-        ) / 4 /* sizeof(CV_LVAR_ADDR_GAP) */;
-        let mut symbol = Self {
+        let symbol = Self {
             program: buf.parse()?,
             parent_offset: buf.parse()?,
             range: buf.parse()?,
-            gaps: vec![],
+            gaps: parse_address_gaps(&mut buf)?,
         };
-        for _ in 0..gap_count {
-            symbol.gaps.push(buf.parse()?);
-        }
 
         Ok((symbol, buf.pos()))
     }
 }
 
+impl DefRangeSubFieldSymbol {
+    /// Returns the address ranges in which the value is available.
+    ///
+    /// The gaps are subtracted from the range. This symbol has no flags, so ranges are
+    /// never tagged as `maybe`.
+    #[must_use]
+    pub fn live_ranges(&self) -> LiveRangeIter {
+        LiveRangeIter::new(self.range, &self.gaps, false)
+    }
+}
+
 // https://github.com/Microsoft/microsoft-pdb/blob/082c5290e5aff028ae84e43affa8be717aa7af73/include/cvinfo.h#L4231
 /// Flags of a [`DefRangeRegisterSymbol`] or [`DefRangeSubFieldRegisterSymbol`].
-#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+#[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
 pub struct RangeFlags {
     /// May have no user name on one of control flow path.
     pub maybe: bool,
@@ -2111,7 +3753,8 @@ impl<'t> TryFromCtx<'t, Endian> for RangeFlags {
 /// A live range of en-registed variable
 ///
 /// Symbol type `S_DEFRANGE_REGISTER`
-#[derive(Clone, Debug, Eq, PartialEq)]
+#[derive(Clone, Debug, Eq, Hash, PartialEq)]
+#[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
 pub struct DefRangeRegisterSymbol {
     /// Register to hold the value of the symbol
     pub register: Register,
@@ -2129,30 +3772,34 @@ impl TryFromCtx<'_, SymbolKind> for DefRangeRegisterSymbol {
     fn try_from_ctx(this: &'_ [u8], _kind: SymbolKind) -> Result<(Self, usize)> {
         let mut buf = ParseBuffer::from(this);
 
-        // https://github.com/Microsoft/microsoft-pdb/blob/082c5290e5aff028ae84e43affa8be717aa7af73/include/cvinfo.h#L4313
-        let gap_count = (
-            buf.len() + 4 /* sizeof(reclen) + buf offset */
-                - 16 /* sizeof(DEFRANGESYM) */
-        ) / 4 /* sizeof(CV_LVAR_ADDR_GAP) */;
-        let mut symbol = Self {
+        let symbol = Self {
             register: buf.parse()?,
             flags: buf.parse()?,
             range: buf.parse()?,
-            gaps: vec![],
+            gaps: parse_address_gaps(&mut buf)?,
         };
-        for _ in 0..gap_count {
-            symbol.gaps.push(buf.parse()?);
-        }
 
         Ok((symbol, buf.pos()))
     }
 }
 
+impl DefRangeRegisterSymbol {
+    /// Returns the address ranges in which the value is available.
+    ///
+    /// The gaps are subtracted from the range. Each range is tagged with the
+    /// [`maybe`](RangeFlags::maybe) flag of this symbol.
+    #[must_use]
+    pub fn live_ranges(&self) -> LiveRangeIter {
+        LiveRangeIter::new(self.range, &self.gaps, self.flags.maybe)
+    }
+}
+
 // https://github.com/Microsoft/microsoft-pdb/blob/082c5290e5aff028ae84e43affa8be717aa7af73/include/cvinfo.h#L4245
 /// A live range of frame variable
 ///
 /// Symbol type `S_DEFRANGE_FRAMEPOINTER_REL`
-#[derive(Clone, Debug, Eq, PartialEq)]
+#[derive(Clone, Debug, Eq, Hash, PartialEq)]
+#[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
 pub struct DefRangeFramePointerRelativeSymbol {
     /// offset to frame pointer
     pub offset: i32,
@@ -2168,29 +3815,33 @@ impl TryFromCtx<'_, SymbolKind> for DefRangeFramePointerRelativeSymbol {
     fn try_from_ctx(this: &'_ [u8], _kind: SymbolKind) -> Result<(Self, usize)> {
         let mut buf = ParseBuffer::from(this);
 
-        // https://github.com/Microsoft/microsoft-pdb/blob/082c5290e5aff028ae84e43affa8be717aa7af73/include/cvinfo.h#L4313
-        let gap_count = (
-            buf.len() + 4 /* sizeof(reclen) + buf offset */
-                - 16 /* sizeof(DEFRANGESYM) */
-        ) / 4 /* sizeof(CV_LVAR_ADDR_GAP) */;
-        let mut symbol = Self {
+        let symbol = Self {
             offset: buf.parse()?,
             range: buf.parse()?,
-            gaps: vec![],
+            gaps: parse_address_gaps(&mut buf)?,
         };
-        for _ in 0..gap_count {
-            symbol.gaps.push(buf.parse()?);
-        }
 
         Ok((symbol, buf.pos()))
     }
 }
 
+impl DefRangeFramePointerRelativeSymbol {
+    /// Returns the address ranges in which the value is available.
+    ///
+    /// The gaps are subtracted from the range. This symbol has no flags, so ranges are
+    /// never tagged as `maybe`.
+    #[must_use]
+    pub fn live_ranges(&self) -> LiveRangeIter {
+        LiveRangeIter::new(self.range, &self.gaps, false)
+    }
+}
+
 // https://github.com/Microsoft/microsoft-pdb/blob/082c5290e5aff028ae84e43affa8be717aa7af73/include/cvinfo.h#L4255
 /// A frame variable valid in all function scope
 ///
 /// Symbol type `S_DEFRANGE_FRAMEPOINTER_REL_FULL_SCOPE`
-#[derive(Copy, Clone, Debug, Eq, PartialEq)]
+#[derive(Copy, Clone, Debug, Eq, Hash, PartialEq)]
+#[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
 pub struct DefRangeFramePointerRelativeFullScopeSymbol {
     /// offset to frame pointer
     pub offset: i32,
@@ -2214,7 +3865,8 @@ impl TryFromCtx<'_, SymbolKind> for DefRangeFramePointerRelativeFullScopeSymbol
 /// A live range of sub field of variable. like locala.i
 ///
 /// Symbol type `S_DEFRANGE_SUBFIELD_REGISTER`
-#[derive(Clone, Debug, Eq, PartialEq)]
+#[derive(Clone, Debug, Eq, Hash, PartialEq)]
+#[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
 pub struct DefRangeSubFieldRegisterSymbol {
     /// Register to hold the value of the symbol
     pub register: Register,
@@ -2234,37 +3886,40 @@ impl TryFromCtx<'_, SymbolKind> for DefRangeSubFieldRegisterSymbol {
     fn try_from_ctx(this: &'_ [u8], _kind: SymbolKind) -> Result<(Self, usize)> {
         let mut buf = ParseBuffer::from(this);
 
-        // https://github.com/Microsoft/microsoft-pdb/blob/082c5290e5aff028ae84e43affa8be717aa7af73/include/cvinfo.h#L4313
-        let gap_count = (
-            buf.len() + 4 /* sizeof(reclen) + buf offset */
-                - 20 /* sizeof(DEFRANGESYMSUBFIELD) */
-        ) / 4 /* sizeof(CV_LVAR_ADDR_GAP) */;
-
         let register: Register = buf.parse()?;
         let flags: RangeFlags = buf.parse()?;
         let offset_padding: u32 = buf.parse()?;
         let offset = offset_padding & 0xFFFu32;
 
-        let mut symbol = Self {
+        let symbol = Self {
             register,
             flags,
             offset,
             range: buf.parse()?,
-            gaps: vec![],
+            gaps: parse_address_gaps(&mut buf)?,
         };
-        for _ in 0..gap_count {
-            symbol.gaps.push(buf.parse()?);
-        }
 
         Ok((symbol, buf.pos()))
     }
 }
 
+impl DefRangeSubFieldRegisterSymbol {
+    /// Returns the address ranges in which the value is available.
+    ///
+    /// The gaps are subtracted from the range. Each range is tagged with the
+    /// [`maybe`](RangeFlags::maybe) flag of this symbol.
+    #[must_use]
+    pub fn live_ranges(&self) -> LiveRangeIter {
+        LiveRangeIter::new(self.range, &self.gaps, self.flags.maybe)
+    }
+}
+
 // https://github.com/Microsoft/microsoft-pdb/blob/082c5290e5aff028ae84e43affa8be717aa7af73/include/cvinfo.h#L4279
 /// A live range of variable related to a register.
 ///
 /// Symbol type `S_DEFRANGE_REGISTER_REL`
-#[derive(Clone, Debug, Eq, PartialEq)]
+#[derive(Clone, Debug, Eq, Hash, PartialEq)]
+#[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
 pub struct DefRangeRegisterRelativeSymbol {
     /// Register to hold the base pointer of the symbol
     pub base_register: Register,
@@ -2286,38 +3941,123 @@ impl TryFromCtx<'_, SymbolKind> for DefRangeRegisterRelativeSymbol {
     fn try_from_ctx(this: &'_ [u8], _kind: SymbolKind) -> Result<(Self, usize)> {
         let mut buf = ParseBuffer::from(this);
 
-        // https://github.com/Microsoft/microsoft-pdb/blob/082c5290e5aff028ae84e43affa8be717aa7af73/include/cvinfo.h#L4313
-        let gap_count = (
-            buf.len() + 4 /* sizeof(reclen) + buf offset */
-                - 20 /* sizeof(DEFRANGESYMSUBFIELD) */
-        ) / 4 /* sizeof(CV_LVAR_ADDR_GAP) */;
-
         let base_register: Register = buf.parse()?;
         let bitfield: u16 = buf.parse()?;
         let spilled_udt_member = bitfield & 0x1;
         let offset_parent = (bitfield >> 4) & 0xFFF;
 
-        let mut symbol = Self {
+        let symbol = Self {
             base_register,
             spilled_udt_member,
             offset_parent,
             offset_base_pointer: buf.parse()?,
             range: buf.parse()?,
-            gaps: vec![],
+            gaps: parse_address_gaps(&mut buf)?,
         };
-        for _ in 0..gap_count {
-            symbol.gaps.push(buf.parse()?);
-        }
 
         Ok((symbol, buf.pos()))
     }
 }
 
+impl DefRangeRegisterRelativeSymbol {
+    /// Returns the address ranges in which the value is available.
+    ///
+    /// The gaps are subtracted from the range. This symbol has no flags, so ranges are
+    /// never tagged as `maybe`.
+    #[must_use]
+    pub fn live_ranges(&self) -> LiveRangeIter {
+        LiveRangeIter::new(self.range, &self.gaps, false)
+    }
+}
+
+/// Any of the `S_DEFRANGE*` records describing where a local variable lives.
+///
+/// See [`SymbolData::into_def_range`] and [`local_def_ranges`].
+#[non_exhaustive]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum DefRangeData {
+    /// A live range of a variable.
+    DefRange(DefRangeSymbol),
+    /// A live range of a sub field of a variable.
+    SubField(DefRangeSubFieldSymbol),
+    /// A live range of a register variable.
+    Register(DefRangeRegisterSymbol),
+    /// A live range of a frame pointer-relative variable.
+    FramePointerRelative(DefRangeFramePointerRelativeSymbol),
+    /// A frame-pointer variable which is valid in the full scope of the function.
+    FramePointerRelativeFullScope(DefRangeFramePointerRelativeFullScopeSymbol),
+    /// A live range of a sub field of a register variable.
+    SubFieldRegister(DefRangeSubFieldRegisterSymbol),
+    /// A live range of a variable related to a register.
+    RegisterRelative(DefRangeRegisterRelativeSymbol),
+}
+
+/// Groups the `S_DEFRANGE*` records of a symbol stream with the local variables they belong to.
+///
+/// Def-range records do not refer to their variable. Instead, they directly follow the `S_LOCAL`
+/// record they describe. This walks `symbols` once and associates every def-range with the most
+/// recent preceding `S_LOCAL` in the same scope. Nested scopes, such as blocks and inline sites,
+/// are tracked so that a def-range after the end of a nested scope is associated with the local of
+/// the enclosing scope. Def-ranges without a preceding local are dropped.
+///
+/// Locals are returned in stream order, including locals without any def-ranges.
+///
+/// # Errors
+///
+/// * `Error::SymbolTooShort` or `Error::UnexpectedEof` if the symbol stream is malformed
+/// * any error from parsing an `S_LOCAL` or `S_DEFRANGE*` record
+pub fn local_def_ranges(
+    mut symbols: SymbolIter<'_>,
+) -> Result<Vec<(LocalSymbol, Vec<DefRangeData>)>> {
+    let mut locals: Vec<(LocalSymbol, Vec<DefRangeData>)> = Vec::new();
+    // index of the most recent local for every open scope
+    let mut scopes: Vec<Option<usize>> = vec![None];
+
+    while let Some(symbol) = symbols.next()? {
+        if symbol.starts_scope() {
+            scopes.push(None);
+            continue;
+        } else if symbol.ends_scope() {
+            if scopes.len() > 1 {
+                scopes.pop();
+            }
+            continue;
+        }
+
+        match symbol.raw_kind() {
+            S_LOCAL => {
+                if let SymbolData::Local(local) = symbol.parse()? {
+                    locals.push((local, Vec::new()));
+                    if let Some(current) = scopes.last_mut() {
+                        *current = Some(locals.len() - 1);
+                    }
+                }
+            }
+            S_DEFRANGE
+            | S_DEFRANGE_SUBFIELD
+            | S_DEFRANGE_REGISTER
+            | S_DEFRANGE_FRAMEPOINTER_REL
+            | S_DEFRANGE_FRAMEPOINTER_REL_FULL_SCOPE
+            | S_DEFRANGE_SUBFIELD_REGISTER
+            | S_DEFRANGE_REGISTER_REL => {
+                let current = scopes.last().copied().flatten();
+                if let (Some(index), Some(range)) = (current, symbol.parse()?.into_def_range()) {
+                    locals[index].1.push(range);
+                }
+            }
+            _ => (),
+        }
+    }
+
+    Ok(locals)
+}
+
 // https://github.com/Microsoft/microsoft-pdb/blob/082c5290e5aff028ae84e43affa8be717aa7af73/include/cvinfo.h#L3573
 /// BP-Relative variable
 ///
 /// Symbol type `S_BPREL32`, `S_BPREL32_ST`, `S_BPREL16`, `S_BPREL32_16T`
-#[derive(Clone, Debug, Eq, PartialEq)]
+#[derive(Clone, Debug, Eq, Hash, PartialEq)]
+#[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
 pub struct BasePointerRelativeSymbol {
     /// BP-relative offset
     pub offset: i32,
@@ -2365,9 +4105,115 @@ impl<'t> TryFromCtx<'t, SymbolKind> for BasePointerRelativeSymbol {
     }
 }
 
-/// Frame procedure flags declared in `FrameProcedureSymbol`
+impl BasePointerRelativeSymbol {
+    /// Locates this variable in the stack frame described by the enclosing procedure's
+    /// `S_FRAMEPROC` record.
+    ///
+    /// Variables at non-negative offsets from BP lie above the saved frame pointer and return
+    /// address and are parameters, variables at negative offsets are locals. The offset is then
+    /// rebased onto the register that the frame uses for parameters or locals, respectively. If
+    /// that register is the stack pointer, the offset is relative to the stack pointer after the
+    /// prologue, which is below the locals and the saved callee registers.
+    #[must_use]
+    pub fn frame_variable(&self, frame: &FrameProcedureSymbol) -> FrameVariable {
+        let offset = i64::from(self.offset);
+        let (kind, base) = if offset >= 0 {
+            (
+                FrameVariableKind::Parameter,
+                frame.flags.param_base_pointer(),
+            )
+        } else {
+            (FrameVariableKind::Local, frame.flags.local_base_pointer())
+        };
+
+        let offset = match base {
+            FrameBasePointer::FramePointer => Some(offset),
+            FrameBasePointer::StackPointer => Some(
+                offset
+                    + i64::from(frame.frame_byte_count)
+                    + i64::from(frame.callee_save_registers_byte_count),
+            ),
+            FrameBasePointer::None | FrameBasePointer::Alternate => None,
+        };
+
+        FrameVariable { kind, base, offset }
+    }
+}
+
+/// Whether a stack variable is a parameter or a local variable.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum FrameVariableKind {
+    /// A parameter passed on the stack.
+    Parameter,
+    /// A local variable.
+    Local,
+}
+
+/// Location of a stack variable within the frame of its procedure.
+///
+/// Returned by [`BasePointerRelativeSymbol::frame_variable`].
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub struct FrameVariable {
+    /// Whether the variable is a parameter or a local variable.
+    pub kind: FrameVariableKind,
+    /// The register the frame uses to address variables of this kind.
+    pub base: FrameBasePointer,
+    /// Offset of the variable relative to `base`, if it can be computed.
+    pub offset: Option<i64>,
+}
+
+/// Register used as the base for addressing parameters or locals in a stack frame.
+///
+/// Declared in [`FrameProcedureFlags`].
 #[non_exhaustive]
 #[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum FrameBasePointer {
+    /// No base pointer was recorded.
+    None,
+    /// The stack pointer, `ESP` or `RSP`.
+    StackPointer,
+    /// The frame pointer, `EBP` or `RBP`.
+    FramePointer,
+    /// An alternate frame pointer, such as `EBX`, `RBX` or `R13`, used with dynamic stack alignment.
+    Alternate,
+}
+
+impl FrameBasePointer {
+    /// Resolves the base pointer to the actual register on the given CPU.
+    ///
+    /// Returns `None` if no base pointer was recorded, or if the register is not known for the
+    /// CPU. The alternate frame pointer is `EBX` on x86, `R13` on x64 and `X19` on ARM64.
+    #[must_use]
+    pub fn register(self, cpu: CPUType) -> Option<Register> {
+        match self {
+            Self::None => None,
+            Self::StackPointer => Register::stack_pointer(cpu),
+            Self::FramePointer => Register::frame_pointer(cpu),
+            Self::Alternate => match cpu.instruction_set() {
+                InstructionSet::X86 => Some(Register::X86_EBX),
+                InstructionSet::X64 => Some(Register::X64_R13),
+                InstructionSet::Arm64 => Some(Register::ARM64_X19),
+                _ => None,
+            },
+        }
+    }
+}
+
+impl From<u8> for FrameBasePointer {
+    fn from(value: u8) -> Self {
+        match value & 3 {
+            1 => Self::StackPointer,
+            2 => Self::FramePointer,
+            3 => Self::Alternate,
+            _ => Self::None,
+        }
+    }
+}
+
+/// Frame procedure flags declared in `FrameProcedureSymbol`
+#[non_exhaustive]
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+#[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
 pub struct FrameProcedureFlags {
     /// function uses `_alloca()`
     has_alloca: bool,
@@ -2411,6 +4257,45 @@ pub struct FrameProcedureFlags {
     guard_cf: bool,
     /// function contains CFW checks and/or instrumentation
     guard_cfw: bool,
+    raw: u32,
+}
+
+impl FrameProcedureFlags {
+    /// Returns the register used as the base for local variables.
+    #[must_use]
+    pub fn local_base_pointer(self) -> FrameBasePointer {
+        FrameBasePointer::from(self.encoded_local_base_pointer)
+    }
+
+    /// Returns the register used as the base for parameters.
+    #[must_use]
+    pub fn param_base_pointer(self) -> FrameBasePointer {
+        FrameBasePointer::from(self.encoded_param_base_pointer)
+    }
+
+    /// Returns whether the function was compiled with profile-guided optimization.
+    #[must_use]
+    pub fn pogo_on(self) -> bool {
+        self.pogo_on
+    }
+
+    /// Returns whether the function was optimized for speed.
+    #[must_use]
+    pub fn opt_speed(self) -> bool {
+        self.opt_speed
+    }
+
+    /// Returns the raw flags word this was parsed from, including bits that are not decoded.
+    #[must_use]
+    pub fn raw(self) -> u32 {
+        self.raw
+    }
+
+    /// Returns the bits of [`raw`](Self::raw) that do not correspond to any decoded flag.
+    #[must_use]
+    pub fn unknown_bits(self) -> u32 {
+        self.raw & !0x007f_ffff
+    }
 }
 
 impl<'t> TryFromCtx<'t, Endian> for FrameProcedureFlags {
@@ -2440,6 +4325,7 @@ impl<'t> TryFromCtx<'t, Endian> for FrameProcedureFlags {
             opt_speed: (raw >> 20) & 1 != 0,
             guard_cf: (raw >> 21) & 1 != 0,
             guard_cfw: (raw >> 22) & 1 != 0,
+            raw,
         };
 
         Ok((flags, 4))
@@ -2450,7 +4336,8 @@ impl<'t> TryFromCtx<'t, Endian> for FrameProcedureFlags {
 /// Extra frame and proc information
 ///
 /// Symbol type `S_FRAMEPROC`
-#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+#[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
 pub struct FrameProcedureSymbol {
     /// count of bytes of total frame of procedure
     pub frame_byte_count: u32,
@@ -2485,11 +4372,70 @@ impl TryFromCtx<'_, SymbolKind> for FrameProcedureSymbol {
     }
 }
 
+impl FrameProcedureSymbol {
+    /// Computes the layout of the stack frame described by this record.
+    ///
+    /// The base pointers declared in the [`flags`](Self::flags) are resolved to registers of the
+    /// given CPU. See [`FrameLayout`] for how the regions are laid out.
+    #[must_use]
+    pub fn frame_layout(&self, cpu: CPUType) -> FrameLayout {
+        let local_base = self.flags.local_base_pointer();
+        let param_base = self.flags.param_base_pointer();
+        let saved_end = self
+            .frame_byte_count
+            .saturating_add(self.callee_save_registers_byte_count);
+
+        FrameLayout {
+            frame_size: saved_end,
+            local_base,
+            local_base_register: local_base.register(cpu),
+            param_base,
+            param_base_register: param_base.register(cpu),
+            locals: 0..self.frame_byte_count,
+            callee_saved: self.frame_byte_count..saved_end,
+            padding: self.offset_padding
+                ..self.offset_padding.saturating_add(self.padding_byte_count),
+            params_offset: saved_end.saturating_add(cpu.instruction_set().return_address_size()),
+        }
+    }
+}
+
+/// Layout of a stack frame, computed by [`FrameProcedureSymbol::frame_layout`].
+///
+/// Offsets of `locals`, `callee_saved` and `params_offset` are relative to the stack pointer
+/// after the prologue, growing towards the caller's frame. This matches the offsets returned by
+/// [`BasePointerRelativeSymbol::frame_variable`] for stack pointer based frames. The `padding`
+/// region is relative to the frame pointer, as recorded in the symbol.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct FrameLayout {
+    /// Total size of the frame in bytes, including locals and saved callee registers.
+    pub frame_size: u32,
+    /// The base pointer used to address local variables.
+    pub local_base: FrameBasePointer,
+    /// The register that `local_base` resolves to, if known.
+    pub local_base_register: Option<Register>,
+    /// The base pointer used to address parameters.
+    pub param_base: FrameBasePointer,
+    /// The register that `param_base` resolves to, if known.
+    pub param_base_register: Option<Register>,
+    /// Region holding local variables.
+    pub locals: Range<u32>,
+    /// Region holding callee-saved registers.
+    pub callee_saved: Range<u32>,
+    /// Padding inserted into the frame, relative to the frame pointer.
+    pub padding: Range<u32>,
+    /// Offset at which the caller's frame, including stack parameters, begins.
+    ///
+    /// This skips the return address pushed by the call instruction, if the CPU pushes one.
+    pub params_offset: u32,
+}
+
 // https://github.com/Microsoft/microsoft-pdb/blob/082c5290e5aff028ae84e43affa8be717aa7af73/include/cvinfo.h#L4491
 /// Indirect call site information
 ///
 /// Symbol type `S_CALLSITEINFO`
-#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+#[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
 pub struct CallSiteInfoSymbol {
     /// offset of call site
     pub offset: PdbInternalSectionOffset,
@@ -2512,11 +4458,32 @@ impl TryFromCtx<'_, SymbolKind> for CallSiteInfoSymbol {
     }
 }
 
+impl CallSiteInfoSymbol {
+    /// Resolves the signature of the called function.
+    ///
+    /// Returns the parameter types, the return type and the calling convention of the function
+    /// type referenced by [`type_index`](Self::type_index). The `type_finder` must have been
+    /// populated up to that index.
+    ///
+    /// # Errors
+    ///
+    /// * `Error::NotAFunctionType` if the type is not a procedure or member function, or the
+    ///   pointers and modifiers leading to it form a cycle
+    /// * `Error::TypeNotFound` or `Error::TypeNotIndexed` if the type cannot be found
+    pub fn signature(
+        &self,
+        type_finder: &TypeFinder<'_>,
+    ) -> Result<(Vec<TypeIndex>, TypeIndex, CallingConvention)> {
+        resolve_signature(type_finder, self.type_index)
+    }
+}
+
 // https://github.com/microsoft/microsoft-pdb/blob/082c5290e5aff028ae84e43affa8be717aa7af73/include/cvinfo.h#L4382
 /// A list of functions and their invocation counts.
 ///
 /// Symbol kind `S_CALLEES` or `S_CALLERS`.
-#[derive(Clone, Debug, Eq, PartialEq)]
+#[derive(Clone, Debug, Eq, Hash, PartialEq)]
+#[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
 pub struct FunctionListSymbol {
     /// The list of function indices.
     functions: Vec<TypeIndex>,
@@ -2529,15 +4496,19 @@ impl<'t> TryFromCtx<'t, SymbolKind> for FunctionListSymbol {
     fn try_from_ctx(this: &'t [u8], _kind: SymbolKind) -> Result<(Self, usize)> {
         let mut buf = ParseBuffer::from(this);
         let count: u32 = buf.parse()?;
-        let functions = vec![buf.parse()?; count as usize];
+
+        // the count is not trusted for the allocation, parsing fails at the end of the record.
+        let mut functions = Vec::with_capacity((count as usize).min(buf.len() / 4));
+        for _ in 0..count {
+            functions.push(buf.parse()?);
+        }
 
         // the function list is followed by a parallel list of invocation counts.
-        // non-existent counts are implicitly zero.
+        // non-existent counts are implicitly zero, excess counts are ignored.
         let mut invocations = Vec::new();
         while !buf.is_empty() {
             invocations.push(buf.parse()?);
         }
-        debug_assert!(invocations.len() <= functions.len());
         invocations.resize(functions.len(), 0);
 
         let symbol = FunctionListSymbol {
@@ -2553,7 +4524,8 @@ impl<'t> TryFromCtx<'t, SymbolKind> for FunctionListSymbol {
 /// List of inlinees of a function
 ///
 /// Symbol kind `S_INLINEES`.
-#[derive(Clone, Debug, Eq, PartialEq)]
+#[derive(Clone, Debug, Eq, Hash, PartialEq)]
+#[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
 pub struct InlineesSymbol {
     /// function ids of the inlinees
     pub inlinees: Vec<TypeIndex>,
@@ -2563,12 +4535,11 @@ impl<'t> TryFromCtx<'t, SymbolKind> for InlineesSymbol {
     type Error = Error;
     fn try_from_ctx(this: &'t [u8], _kind: SymbolKind) -> Result<(Self, usize)> {
         let mut buf = ParseBuffer::from(this);
-        let count = buf.parse::<u32>()?;
+        let _count = buf.parse::<u32>()?;
         let mut inlinees = Vec::new();
         while !buf.is_empty() {
             inlinees.push(buf.parse()?);
         }
-        debug_assert_eq!(inlinees.len(), count as usize);
 
         let symbol = InlineesSymbol { inlinees };
         Ok((symbol, buf.pos()))
@@ -2578,7 +4549,8 @@ impl<'t> TryFromCtx<'t, SymbolKind> for InlineesSymbol {
 /// used to describe the layout of a jump table
 ///
 /// Symbol kind `S_ARMSWITCHTABLE`
-#[derive(Clone, Debug, Eq, PartialEq)]
+#[derive(Clone, Debug, Eq, Hash, PartialEq)]
+#[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
 pub struct ArmSwitchTableSymbol {
     /// The base address that the values in the jump table are relative to.
     pub offset_base: PdbInternalSectionOffset,
@@ -2624,10 +4596,121 @@ impl<'t> TryFromCtx<'t, SymbolKind> for ArmSwitchTableSymbol {
     }
 }
 
+impl ArmSwitchTableSymbol {
+    /// Returns the size of the jump table in bytes.
+    ///
+    /// `pointer_size` is the size of a pointer on the target architecture in bytes, which is used
+    /// for [`JumpTableEntrySize::Pointer`] entries. Returns `None` if the entry type is invalid or
+    /// the size overflows.
+    #[must_use]
+    pub fn table_size(&self, pointer_size: u32) -> Option<u32> {
+        let entry_size = self.switch_type.size(pointer_size)?;
+        self.num_entries.checked_mul(entry_size)
+    }
+
+    /// Returns the labels that lie within the jump table.
+    ///
+    /// A label is inside the table if it is in the same section as `offset_table` and its offset
+    /// is in `[offset_table, offset_table + table_size)`. See [`table_size`](Self::table_size) for
+    /// the meaning of `pointer_size`. If the entry type is invalid, no labels are returned.
+    #[must_use]
+    pub fn labels_in_table<'a>(
+        &self,
+        labels: &'a [LabelSymbol],
+        pointer_size: u32,
+    ) -> Vec<&'a LabelSymbol> {
+        let size = match self.table_size(pointer_size) {
+            Some(size) => u64::from(size),
+            None => return Vec::new(),
+        };
+
+        let start = u64::from(self.offset_table.offset);
+        labels
+            .iter()
+            .filter(|label| {
+                let offset = u64::from(label.offset.offset);
+                label.offset.section == self.offset_table.section
+                    && offset >= start
+                    && offset < start + size
+            })
+            .collect()
+    }
+
+    /// Decodes the jump table and returns the RVA of each branch target.
+    ///
+    /// `image_bytes` is the image as mapped into memory, such that the byte at RVA `n` is located
+    /// at `image_bytes[n]`. The table is read at [`offset_table`](Self::offset_table), and each
+    /// entry is sign- or zero-extended according to [`switch_type`](Self::switch_type). Entries of
+    /// the `*ShiftLeft` types count halfwords and are shifted left by one. The targets are relative
+    /// to [`offset_base`](Self::offset_base).
+    ///
+    /// # Errors
+    ///
+    /// * `Error::UnmappedAddress` if the table or base address cannot be translated to an RVA
+    /// * `Error::UnexpectedEof` if the table extends beyond `image_bytes`
+    /// * `Error::UnimplementedFeature` for absolute [`Pointer`](JumpTableEntrySize::Pointer)
+    ///   entries, which require the image base, and for unknown entry types
+    pub fn jump_table_targets(
+        &self,
+        image_bytes: &[u8],
+        address_map: &AddressMap<'_>,
+    ) -> Result<Vec<Rva>> {
+        let (size, signed, shift) = match self.switch_type {
+            JumpTableEntrySize::Int8 => (1, true, 0),
+            JumpTableEntrySize::UInt8 => (1, false, 0),
+            JumpTableEntrySize::Int16 => (2, true, 0),
+            JumpTableEntrySize::UInt16 => (2, false, 0),
+            JumpTableEntrySize::Int32 => (4, true, 0),
+            JumpTableEntrySize::UInt32 => (4, false, 0),
+            JumpTableEntrySize::UInt8ShiftLeft => (1, false, 1),
+            JumpTableEntrySize::UInt16ShiftLeft => (2, false, 1),
+            JumpTableEntrySize::Int8ShiftLeft => (1, true, 1),
+            JumpTableEntrySize::Int16ShiftLeft => (2, true, 1),
+            JumpTableEntrySize::Pointer => {
+                return Err(Error::UnimplementedFeature("absolute jump table entries"))
+            }
+            JumpTableEntrySize::Invalid => {
+                return Err(Error::UnimplementedFeature("unknown jump table entry type"))
+            }
+        };
+
+        let to_rva = |offset: PdbInternalSectionOffset| {
+            offset
+                .to_rva(address_map)
+                .ok_or(Error::UnmappedAddress(offset.section, offset.offset))
+        };
+        let base = to_rva(self.offset_base)?;
+        let table = to_rva(self.offset_table)?;
+
+        let mut buf = ParseBuffer::from(image_bytes);
+        buf.take(table.0 as usize)?;
+
+        // The entry count comes from the file, so do not reserve more than the image can hold.
+        let capacity = (self.num_entries as usize).min(buf.len() / size);
+        let mut targets = Vec::with_capacity(capacity);
+        for _ in 0..self.num_entries {
+            let value = match (size, signed) {
+                (1, true) => i64::from(buf.parse::<i8>()?),
+                (1, false) => i64::from(buf.parse_u8()?),
+                (2, true) => i64::from(buf.parse_i16()?),
+                (2, false) => i64::from(buf.parse_u16()?),
+                (_, true) => i64::from(buf.parse_i32()?),
+                (_, false) => i64::from(buf.parse_u32()?),
+            };
+
+            let target = i64::from(base.0) + (value << shift);
+            targets.push(Rva(target as u32));
+        }
+
+        Ok(targets)
+    }
+}
+
 // https://github.com/microsoft/microsoft-pdb/blob/082c5290e5aff028ae84e43affa8be717aa7af73/include/cvinfo.h#L4366
 // enum CV_armswitchtype
 /// Enumeration of possible jump table entry sizes.
-#[derive(Clone, Debug, Eq, PartialEq)]
+#[derive(Clone, Debug, Eq, Hash, PartialEq)]
+#[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
 #[repr(u16)]
 pub enum JumpTableEntrySize {
     /// 0x00: Entry type is int8.
@@ -2656,6 +4739,23 @@ pub enum JumpTableEntrySize {
     Invalid = 0xffff,
 }
 
+impl JumpTableEntrySize {
+    /// Returns the size of a single jump table entry in bytes.
+    ///
+    /// `pointer_size` is the size of [`Pointer`](Self::Pointer) entries, which depends on the
+    /// target architecture. Returns `None` for [`Invalid`](Self::Invalid) entries.
+    #[must_use]
+    pub fn size(&self, pointer_size: u32) -> Option<u32> {
+        match self {
+            Self::Int8 | Self::UInt8 | Self::UInt8ShiftLeft | Self::Int8ShiftLeft => Some(1),
+            Self::Int16 | Self::UInt16 | Self::UInt16ShiftLeft | Self::Int16ShiftLeft => Some(2),
+            Self::Int32 | Self::UInt32 => Some(4),
+            Self::Pointer => Some(pointer_size),
+            Self::Invalid => None,
+        }
+    }
+}
+
 impl<'t> TryFromCtx<'t, Endian> for JumpTableEntrySize {
     type Error = Error;
     fn try_from_ctx(this: &'t [u8], _unused: Endian) -> Result<(Self, usize)> {
@@ -2683,13 +4783,17 @@ impl<'t> TryFromCtx<'t, Endian> for JumpTableEntrySize {
 /// Description of a heap allocation site.
 ///
 /// Symbol kind `S_HEAPALLOCSITE`
-#[derive(Clone, Debug, Eq, PartialEq)]
+#[derive(Clone, Debug, Eq, Hash, PartialEq)]
+#[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
 pub struct HeapAllocationSiteSymbol {
     /// The offset of the allocation site.
     pub offset: PdbInternalSectionOffset,
     /// length of the heap allocation call instruction
     pub instr_length: u16,
-    /// The type index describing the function signature.
+    /// The type index describing the signature of the allocating function.
+    ///
+    /// Some compilers record the type of the allocated object here instead, see
+    /// [`group_heap_allocation_sites`].
     pub type_index: TypeIndex,
 }
 
@@ -2711,11 +4815,361 @@ impl<'t> TryFromCtx<'t, SymbolKind> for HeapAllocationSiteSymbol {
     }
 }
 
+impl HeapAllocationSiteSymbol {
+    /// Resolves the signature of the allocating function.
+    ///
+    /// See [`CallSiteInfoSymbol::signature`] for details.
+    ///
+    /// # Errors
+    ///
+    /// * `Error::NotAFunctionType` if the type is not a procedure or member function, or the
+    ///   pointers and modifiers leading to it form a cycle
+    /// * `Error::TypeNotFound` or `Error::TypeNotIndexed` if the type cannot be found
+    pub fn signature(
+        &self,
+        type_finder: &TypeFinder<'_>,
+    ) -> Result<(Vec<TypeIndex>, TypeIndex, CallingConvention)> {
+        resolve_signature(type_finder, self.type_index)
+    }
+}
+
+/// Collects all heap allocation sites from a symbol stream.
+///
+/// `symbols` can iterate either a module's symbols or a global [`SymbolTable`]. Records that fail
+/// to parse are returned as errors.
+///
+/// # Example
+///
+/// ```
+/// # use pdb2::FallibleIterator;
+/// #
+/// # fn test() -> pdb2::Result<usize> {
+/// let file = std::fs::File::open("fixtures/self/foo.pdb")?;
+/// let mut pdb = pdb2::PDB::open(file)?;
+///
+/// let dbi = pdb.debug_information()?;
+/// let mut modules = dbi.modules()?;
+/// let mut count = 0;
+/// while let Some(module) = modules.next()? {
+///     if let Some(info) = pdb.module_info(&module)? {
+///         count += pdb2::heap_allocation_sites(info.symbols()?)?.len();
+///     }
+/// }
+/// # Ok(count)
+/// # }
+/// # assert!(test().expect("test") > 0);
+/// ```
+pub fn heap_allocation_sites(mut symbols: SymbolIter<'_>) -> Result<Vec<HeapAllocationSiteSymbol>> {
+    let mut sites = Vec::new();
+
+    while let Some(symbol) = symbols.next()? {
+        if symbol.raw_kind() == S_HEAPALLOCSITE {
+            if let SymbolData::HeapAllocationSite(site) = symbol.parse()? {
+                sites.push(site);
+            }
+        }
+    }
+
+    Ok(sites)
+}
+
+/// Groups heap allocation sites by the signature of their allocating function.
+///
+/// The [`type_index`](HeapAllocationSiteSymbol::type_index) of every site is resolved through the
+/// `type_finder`, which must have been populated up to the highest referenced index. Signatures
+/// are formatted as `Return convention(Parameter, ..)`, for instance `Void* __cdecl(UQuad)`. If the
+/// type is not a function type, the site is grouped by the type's name instead. Types without a
+/// name are formatted by their primitive kind or type index.
+///
+/// # Errors
+///
+/// * `Error::TypeNotFound` or `Error::TypeNotIndexed` if a type cannot be found
+pub fn group_heap_allocation_sites(
+    sites: &[HeapAllocationSiteSymbol],
+    type_finder: &TypeFinder<'_>,
+) -> Result<BTreeMap<String, Vec<HeapAllocationSiteSymbol>>> {
+    let mut groups: BTreeMap<String, Vec<HeapAllocationSiteSymbol>> = BTreeMap::new();
+
+    for site in sites {
+        let key = match site.signature(type_finder) {
+            Ok((parameters, return_type, calling_convention)) => {
+                let parameters = parameters
+                    .into_iter()
+                    .map(|parameter| type_label(type_finder, parameter))
+                    .collect::<Result<Vec<_>>>()?;
+                format!(
+                    "{} {}({})",
+                    type_label(type_finder, return_type)?,
+                    calling_convention,
+                    parameters.join(", ")
+                )
+            }
+            Err(Error::NotAFunctionType(_)) => type_label(type_finder, site.type_index)?,
+            Err(error) => return Err(error),
+        };
+
+        groups.entry(key).or_default().push(site.clone());
+    }
+
+    Ok(groups)
+}
+
+/// Formats a type by its name, its primitive kind, or its index.
+fn type_label(type_finder: &TypeFinder<'_>, index: TypeIndex) -> Result<String> {
+    Ok(match type_finder.find(index)?.parse()? {
+        TypeData::Primitive(primitive) => match primitive.indirection {
+            Some(_) => format!("{:?}*", primitive.kind),
+            None => format!("{:?}", primitive.kind),
+        },
+        data => match data.name() {
+            Some(name) => name.to_owned(),
+            None => index.to_string(),
+        },
+    })
+}
+
+/// A function joined from a [`PublicSymbol`] and a [`ProcedureSymbol`] at the same address.
+///
+/// Returned by [`merge_functions`].
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct MergedFunction {
+    /// Address of the function.
+    pub rva: Rva,
+    /// Code size of the function, if a procedure symbol was found.
+    pub size: Option<u32>,
+    /// The readable name of the function.
+    ///
+    /// This is the procedure's name if one was found, otherwise the public symbol's name.
+    pub name: String,
+    /// The mangled name of the public symbol, if it differs from `name`.
+    pub mangled_name: Option<String>,
+}
+
+/// Joins public symbols and procedures by their resolved address.
+///
+/// Compilers usually emit both an `S_PUB32` with the mangled name and an `S_GPROC32` with the
+/// demangled name for the same function. This merges them into a single [`MergedFunction`] that
+/// prefers the procedure's name and size and keeps the public's name as an alias. Publics that do
+/// not refer to code are ignored. If several symbols of the same kind share an address, the first
+/// one wins.
+///
+/// Symbols whose address cannot be translated through the `address_map` are skipped. The result is
+/// sorted by address.
+#[must_use]
+pub fn merge_functions(
+    publics: &[PublicSymbol],
+    procedures: &[ProcedureSymbol],
+    address_map: &AddressMap<'_>,
+) -> Vec<MergedFunction> {
+    let mut functions: BTreeMap<Rva, MergedFunction> = BTreeMap::new();
+
+    for procedure in procedures {
+        if let Some(rva) = procedure.offset.to_rva(address_map) {
+            functions.entry(rva).or_insert_with(|| MergedFunction {
+                rva,
+                size: Some(procedure.len),
+                name: procedure.name.clone(),
+                mangled_name: None,
+            });
+        }
+    }
+
+    for public in publics
+        .iter()
+        .filter(|public| public.code || public.function)
+    {
+        let rva = match public.offset.to_rva(address_map) {
+            Some(rva) => rva,
+            None => continue,
+        };
+
+        let function = functions.entry(rva).or_insert_with(|| MergedFunction {
+            rva,
+            size: None,
+            name: public.name.clone(),
+            mangled_name: None,
+        });
+
+        if function.mangled_name.is_none() && function.name != public.name {
+            function.mangled_name = Some(public.name.clone());
+        }
+    }
+
+    functions.into_values().collect()
+}
+
+/// The differences between two sets of symbols, as computed by [`diff_symbols`].
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct SymbolDiff {
+    /// Symbols that only exist in the new set.
+    pub added: Vec<SymbolData>,
+    /// Symbols that only exist in the old set.
+    pub removed: Vec<SymbolData>,
+    /// Symbols that only differ in their offset, as `(old, new)` pairs.
+    pub moved: Vec<(SymbolData, SymbolData)>,
+    /// Symbols whose type or size changed, as `(old, new)` pairs. These may have moved as well.
+    pub changed: Vec<(SymbolData, SymbolData)>,
+}
+
+impl SymbolDiff {
+    /// Returns `true` if both sets of symbols are equivalent.
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.added.is_empty()
+            && self.removed.is_empty()
+            && self.moved.is_empty()
+            && self.changed.is_empty()
+    }
+}
+
+/// Returns the type index and size compared by [`diff_symbols`] to detect changed symbols.
+fn diff_signature(data: &SymbolData) -> (Option<TypeIndex>, Option<u32>) {
+    match data {
+        SymbolData::Procedure(data) => (Some(data.type_index), Some(data.len)),
+        SymbolData::ManagedProcedure(data) => (None, Some(data.len)),
+        SymbolData::Data(data) => (Some(data.type_index), None),
+        SymbolData::ThreadStorage(data) => (Some(data.type_index), None),
+        SymbolData::Thunk(data) => (None, Some(data.len.into())),
+        _ => (None, None),
+    }
+}
+
+/// Collects the named symbols with an offset, keyed by their kind and name.
+fn diff_collect(
+    mut symbols: SymbolIter<'_>,
+) -> Result<BTreeMap<(SymbolKindTag, String), Vec<SymbolData>>> {
+    let mut map: BTreeMap<_, Vec<_>> = BTreeMap::new();
+
+    while let Some(symbol) = symbols.next()? {
+        let data = match symbol.parse() {
+            Ok(data) => data,
+            Err(Error::UnimplementedSymbolKind(_)) => continue,
+            Err(e) => return Err(e),
+        };
+
+        if data.offset().is_none() {
+            continue;
+        }
+
+        if let Some(name) = data.name() {
+            let key = (data.kind(), name.to_owned());
+            map.entry(key).or_default().push(data);
+        }
+    }
+
+    Ok(map)
+}
+
+/// Counts symbols by the section they refer to and their kind.
+///
+/// `symbols` can iterate either a module's symbols or a global [`SymbolTable`]. Only symbols with an
+/// [`offset`](SymbolData::offset) are counted, keyed by the section of that offset and the
+/// [`kind`](SymbolData::kind) of the symbol. Records that fail to parse are skipped, see
+/// [`Symbol::parse_lenient`], so the histogram also covers streams with unsupported symbols.
+///
+/// # Example
+///
+/// ```
+/// # fn test() -> pdb2::Result<usize> {
+/// let file = std::fs::File::open("fixtures/self/foo.pdb")?;
+/// let mut pdb = pdb2::PDB::open(file)?;
+/// let symbols = pdb.global_symbols()?;
+///
+/// let histogram = pdb2::section_symbol_histogram(symbols.iter())?;
+/// for ((section, kind), count) in &histogram {
+///     println!("section {}: {} {:?}", section, count, kind);
+/// }
+/// # Ok(histogram.len())
+/// # }
+/// # assert!(test().expect("test") > 0);
+/// ```
+///
+/// # Errors
+///
+/// * `Error::SymbolTooShort` if a record has an invalid length
+/// * `Error::UnexpectedEof` if the symbol stream is truncated mid-record
+pub fn section_symbol_histogram(
+    mut symbols: SymbolIter<'_>,
+) -> Result<HashMap<(u16, SymbolKindTag), usize>> {
+    let mut histogram = HashMap::new();
+
+    while let Some(symbol) = symbols.next()? {
+        let data = symbol.parse_lenient();
+        if let Some(offset) = data.offset() {
+            *histogram.entry((offset.section, data.kind())).or_insert(0) += 1;
+        }
+    }
+
+    Ok(histogram)
+}
+
+/// Compares two sets of symbols, such as those of the same module in two builds of a binary.
+///
+/// Only symbols with both a name and an offset are compared, which covers procedures, data,
+/// publics and thunks. Symbols are matched by their kind and name. If a name occurs multiple times,
+/// such as for `static` functions in different translation units, the occurrences are matched
+/// in order.
+///
+/// A matched symbol is reported as changed if its type index or size differ, and as moved if only
+/// its offset differs.
+///
+/// # Example
+///
+/// ```
+/// # use pdb2::FallibleIterator;
+/// #
+/// # fn test() -> pdb2::Result<bool> {
+/// let file = std::fs::File::open("fixtures/self/foo.pdb")?;
+/// let mut pdb = pdb2::PDB::open(file)?;
+/// let symbols = pdb.global_symbols()?;
+///
+/// let diff = pdb2::diff_symbols(symbols.iter(), symbols.iter())?;
+/// # Ok(diff.is_empty())
+/// # }
+/// # assert!(test().expect("test"));
+/// ```
+///
+/// # Errors
+///
+/// * `Error::UnexpectedEof` if either set of symbols is malformed
+pub fn diff_symbols(old: SymbolIter<'_>, new: SymbolIter<'_>) -> Result<SymbolDiff> {
+    let mut old = diff_collect(old)?;
+    let new = diff_collect(new)?;
+    let mut diff = SymbolDiff::default();
+
+    for (key, new_symbols) in new {
+        let mut old_symbols = old.remove(&key).unwrap_or_default().into_iter();
+
+        for new_symbol in new_symbols {
+            let old_symbol = match old_symbols.next() {
+                Some(old_symbol) => old_symbol,
+                None => {
+                    diff.added.push(new_symbol);
+                    continue;
+                }
+            };
+
+            if diff_signature(&old_symbol) != diff_signature(&new_symbol) {
+                diff.changed.push((old_symbol, new_symbol));
+            } else if old_symbol.offset() != new_symbol.offset() {
+                diff.moved.push((old_symbol, new_symbol));
+            }
+        }
+
+        diff.removed.extend(old_symbols);
+    }
+
+    diff.removed.extend(old.into_values().flatten());
+
+    Ok(diff)
+}
+
 // https://github.com/microsoft/microsoft-pdb/blob/082c5290e5aff028ae84e43affa8be717aa7af73/include/cvinfo.h#L4522
 /// Description of a security cookie on a stack frame.
 ///
 /// Symbol kind `S_FRAMECOOKIE`
-#[derive(Clone, Debug, Eq, PartialEq)]
+#[derive(Clone, Debug, Eq, Hash, PartialEq)]
+#[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
 pub struct FrameCookieSymbol {
     /// Frame relative offset
     pub offset: i32,
@@ -2748,7 +5202,8 @@ impl TryFromCtx<'_, SymbolKind> for FrameCookieSymbol {
 }
 
 /// Construction of the security cookie value.
-#[derive(Clone, Debug, Eq, PartialEq)]
+#[derive(Clone, Debug, Eq, Hash, PartialEq)]
+#[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
 #[repr(u8)]
 pub enum FrameCookieType {
     /// Copy
@@ -2833,6 +5288,201 @@ impl<'s> SymbolTable<'s> {
         SymbolIter::new(self.stream.parse_buffer())
     }
 
+    /// Returns a standard [`Iterator`] over the symbol table, yielding a `Result` per symbol.
+    ///
+    /// This wraps [`iter`](Self::iter) for use with `for` loops and the adapters of
+    /// [`std::iter::Iterator`]. Prefer the [`FallibleIterator`] returned by `iter` where possible.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # fn test() -> pdb2::Result<usize> {
+    /// let file = std::fs::File::open("fixtures/self/foo.pdb")?;
+    /// let mut pdb = pdb2::PDB::open(file)?;
+    /// let symbol_table = pdb.global_symbols()?;
+    ///
+    /// let mut count = 0;
+    /// for symbol in symbol_table.iter_std() {
+    ///     if let Ok(pdb2::SymbolData::Public(_)) = symbol?.parse() {
+    ///         count += 1;
+    ///     }
+    /// }
+    /// # Ok(count)
+    /// # }
+    /// # assert!(test().expect("test") > 2000);
+    /// ```
+    pub fn iter_std(&self) -> impl Iterator<Item = Result<Symbol<'_>>> + '_ {
+        self.iter().iterator()
+    }
+
+    /// Returns an iterator that yields every symbol along with its scope depth.
+    ///
+    /// See [`SymbolIter::with_depth`] for details.
+    #[must_use]
+    pub fn iter_with_depth(&self) -> SymbolDepthIter<'_> {
+        self.iter().with_depth()
+    }
+
+    /// Writes every symbol of this table as newline-delimited JSON.
+    ///
+    /// Symbols are streamed to `writer` one at a time, each as a single-line JSON object
+    /// followed by `\n`. Every object contains the `index` of the symbol, its raw `kind` and
+    /// the [`SymbolKindTag`] as `tag`. Symbols with a name or an offset additionally contain
+    /// `name`, `section` and `offset`. If an `address_map` is given, offsets that can be
+    /// translated are also written as `rva`. Symbols that cannot be parsed are written with the
+    /// `Unknown` tag, see [`Symbol::parse_lenient`].
+    ///
+    /// This requires the `json` feature.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # fn test() -> pdb2::Result<()> {
+    /// let file = std::fs::File::open("fixtures/self/foo.pdb")?;
+    /// let mut pdb = pdb2::PDB::open(file)?;
+    /// let address_map = pdb.address_map()?;
+    /// let symbol_table = pdb.global_symbols()?;
+    ///
+    /// let mut output = Vec::new();
+    /// symbol_table.write_ndjson(&mut output, Some(&address_map))?;
+    /// # assert!(!output.is_empty());
+    /// # Ok(())
+    /// # }
+    /// # test().expect("test");
+    /// ```
+    ///
+    /// # Errors
+    ///
+    /// * `Error::IoError` if writing to `writer` fails
+    /// * `Error::SymbolTooShort` if a record has an invalid length
+    /// * `Error::UnexpectedEof` if the symbol table is truncated mid-record
+    #[cfg(feature = "json")]
+    pub fn write_ndjson<W: std::io::Write>(
+        &self,
+        mut writer: W,
+        address_map: Option<&AddressMap<'_>>,
+    ) -> Result<()> {
+        use serde_json::{Map, Value};
+
+        let mut symbols = self.iter();
+        while let Some(symbol) = symbols.next()? {
+            let data = symbol.parse_lenient();
+
+            let mut object = Map::new();
+            object.insert("index".into(), symbol.index().0.into());
+            object.insert("kind".into(), symbol.raw_kind().into());
+            object.insert("tag".into(), format!("{:?}", data.kind()).into());
+            if let Some(name) = data.name() {
+                object.insert("name".into(), name.into());
+            }
+            if let Some(offset) = data.offset() {
+                object.insert("section".into(), offset.section.into());
+                object.insert("offset".into(), offset.offset.into());
+                if let Some(rva) = address_map.and_then(|map| offset.to_rva(map)) {
+                    object.insert("rva".into(), rva.0.into());
+                }
+            }
+
+            serde_json::to_writer(&mut writer, &Value::Object(object))
+                .map_err(std::io::Error::from)?;
+            writer.write_all(b"\n")?;
+        }
+
+        Ok(())
+    }
+
+    /// Collects the code and data symbols of this table into a map keyed by their address.
+    ///
+    /// Procedures, thunks, data and public symbols are parsed and their offsets translated through
+    /// the `address_map`. Symbols that cannot be parsed or translated are skipped. If several
+    /// symbols share an address, procedures are preferred over thunks, thunks over data, and data
+    /// over public symbols. Among symbols of the same rank, the first one wins.
+    ///
+    /// The global symbol table does not contain procedures itself. It only refers to them through
+    /// [`ProcedureReferenceSymbol`]s, which are skipped here, so for the global table the map only
+    /// holds thunks, data and public symbols. Use
+    /// [`PDB::global_symbol_rva_map`](crate::PDB::global_symbol_rva_map) instead, which resolves
+    /// the references so that procedures take precedence over their public symbols.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # fn test() -> pdb2::Result<()> {
+    /// let file = std::fs::File::open("fixtures/self/foo.pdb")?;
+    /// let mut pdb = pdb2::PDB::open(file)?;
+    /// let address_map = pdb.address_map()?;
+    /// let symbol_table = pdb.global_symbols()?;
+    ///
+    /// let symbols = symbol_table.to_rva_map(&address_map)?;
+    /// if let Some((rva, symbol)) = symbols.range(..=pdb2::Rva(0x64f4)).next_back() {
+    ///     println!("{} at {}", symbol.name().unwrap_or_default(), rva);
+    /// }
+    /// # Ok(())
+    /// # }
+    /// # test().expect("test");
+    /// ```
+    ///
+    /// # Errors
+    ///
+    /// * `Error::SymbolTooShort` if a record has an invalid length
+    /// * `Error::UnexpectedEof` if the symbol table is truncated mid-record
+    pub fn to_rva_map(&self, address_map: &AddressMap<'_>) -> Result<BTreeMap<Rva, SymbolData>> {
+        self.to_rva_map_with(address_map, |_| Ok(None))
+    }
+
+    /// Collects symbols into a map keyed by their address like [`to_rva_map`](Self::to_rva_map),
+    /// replacing procedure references with the symbol returned by `resolve`.
+    pub(crate) fn to_rva_map_with<F>(
+        &self,
+        address_map: &AddressMap<'_>,
+        mut resolve: F,
+    ) -> Result<BTreeMap<Rva, SymbolData>>
+    where
+        F: FnMut(&ProcedureReferenceSymbol) -> Result<Option<SymbolData>>,
+    {
+        fn rank(data: &SymbolData) -> Option<u8> {
+            match data {
+                SymbolData::Procedure(_) | SymbolData::ManagedProcedure(_) => Some(3),
+                SymbolData::Thunk(_) => Some(2),
+                SymbolData::Data(_) => Some(1),
+                SymbolData::Public(_) => Some(0),
+                _ => None,
+            }
+        }
+
+        let mut map: BTreeMap<Rva, SymbolData> = BTreeMap::new();
+
+        let mut symbols = self.iter();
+        while let Some(symbol) = symbols.next()? {
+            let data = match symbol.parse_lenient() {
+                SymbolData::ProcedureReference(reference) => match resolve(&reference)? {
+                    Some(data) => data,
+                    None => continue,
+                },
+                data => data,
+            };
+
+            let priority = match rank(&data) {
+                Some(priority) => priority,
+                None => continue,
+            };
+
+            let rva = match data.offset().and_then(|offset| offset.to_rva(address_map)) {
+                Some(rva) => rva,
+                None => continue,
+            };
+
+            match map.get(&rva) {
+                Some(existing) if rank(existing) >= Some(priority) => {}
+                _ => {
+                    map.insert(rva, data);
+                }
+            }
+        }
+
+        Ok(map)
+    }
+
     /// Returns an iterator over symbols starting at the given index.
     #[must_use]
     pub fn iter_at(&self, index: SymbolIndex) -> SymbolIter<'_> {
@@ -2840,6 +5490,45 @@ impl<'s> SymbolTable<'s> {
         iter.seek(index);
         iter
     }
+
+    /// Counts the symbols in this table.
+    ///
+    /// This only reads the length prefix and kind of each record without parsing it, which makes it
+    /// suitable for pre-sizing collections before iterating. Padding records (`S_ALIGN` and
+    /// `S_SKIP`) are skipped the same way as in [`iter`](Self::iter).
+    ///
+    /// # Errors
+    ///
+    /// * `Error::SymbolTooShort` if a record has an invalid length
+    /// * `Error::UnexpectedEof` if the symbol table is truncated mid-record
+    pub fn count(&self) -> Result<usize> {
+        self.iter().count()
+    }
+
+    /// Returns the size of the underlying symbol stream in bytes.
+    #[must_use]
+    pub fn byte_len(&self) -> usize {
+        self.stream.byte_len()
+    }
+
+    /// Returns whether this symbol table contains no data.
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.byte_len() == 0
+    }
+
+    /// Returns an iterator over symbols whose offset lies in `section` within `offset_range`.
+    ///
+    /// Symbols without an offset (see [`SymbolData::offset`]) are skipped, as are symbols of kinds
+    /// that cannot be parsed yet.
+    #[must_use]
+    pub fn iter_in_range(&self, section: u16, offset_range: Range<u32>) -> SymbolRangeIter<'_> {
+        SymbolRangeIter {
+            inner: self.iter(),
+            section,
+            offset_range,
+        }
+    }
 }
 
 /// A `SymbolIter` iterates over a `SymbolTable`, producing `Symbol`s.
@@ -2850,11 +5539,24 @@ impl<'s> SymbolTable<'s> {
 #[derive(Debug)]
 pub struct SymbolIter<'t> {
     buf: ParseBuffer<'t>,
+    visited: Option<HashSet<SymbolIndex>>,
 }
 
 impl<'t> SymbolIter<'t> {
     pub(crate) fn new(buf: ParseBuffer<'t>) -> SymbolIter<'t> {
-        SymbolIter { buf }
+        SymbolIter { buf, visited: None }
+    }
+
+    /// Enables cycle detection for [`skip_to`](Self::skip_to).
+    ///
+    /// Navigating symbols via their `parent`, `end` or `next` fields can loop forever if the symbol
+    /// stream is malformed. With the guard enabled, the iterator records every index passed to
+    /// `skip_to`, and returns `Error::SymbolCycle` if an index is reached a second time. Sequential
+    /// iteration with `next` and [`seek`](Self::seek) are not affected.
+    #[must_use]
+    pub fn with_cycle_guard(mut self) -> Self {
+        self.visited = Some(HashSet::new());
+        self
     }
 
     /// Move the iterator to the symbol referred to by `index`.
@@ -2864,14 +5566,121 @@ impl<'t> SymbolIter<'t> {
         self.buf.seek(index.0 as usize);
     }
 
-    /// Skip to the symbol referred to by `index`, returning the symbol.
+    /// Returns the index of the next record that this iterator will read.
+    ///
+    /// Between calls to `next`, this is always located at a record boundary and can be passed to
+    /// [`seek`](Self::seek) or [`SymbolTable::iter_at`] to resume iteration later. Every call to
+    /// `next` advances the position past the returned record, including its 2-byte length prefix.
+    #[must_use]
+    pub fn position(&self) -> SymbolIndex {
+        SymbolIndex(self.buf.pos() as u32)
+    }
+
+    /// Limits iteration to roughly `limit` bytes of the symbol stream.
+    ///
+    /// The returned iterator yields records until the bytes consumed since this call reach
+    /// `limit`. The first record and the record crossing the limit are always returned, so every
+    /// call makes progress even if `limit` is zero or smaller than a single record. Afterwards,
+    /// [`SymbolBudgetIter::position`] gives the index at which to resume.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use pdb2::FallibleIterator;
+    /// #
+    /// # fn test() -> pdb2::Result<usize> {
+    /// let file = std::fs::File::open("fixtures/self/foo.pdb")?;
+    /// let mut pdb = pdb2::PDB::open(file)?;
+    /// let symbol_table = pdb.global_symbols()?;
+    ///
+    /// // load the symbol table in chunks of 4 KiB
+    /// let mut count = 0;
+    /// let mut position = pdb2::SymbolIndex(0);
+    /// loop {
+    ///     let mut chunk = symbol_table.iter_at(position).take_bytes(4096);
+    ///     while let Some(_symbol) = chunk.next()? {
+    ///         count += 1;
+    ///     }
+    ///     if chunk.is_exhausted() {
+    ///         break;
+    ///     }
+    ///     position = chunk.position();
+    /// }
+    /// # Ok(count)
+    /// # }
+    /// # assert!(test().expect("test") > 2000);
+    /// ```
+    #[must_use]
+    pub fn take_bytes(self, limit: usize) -> SymbolBudgetIter<'t> {
+        SymbolBudgetIter {
+            start: self.buf.pos(),
+            inner: self,
+            limit,
+        }
+    }
+
+    /// Annotates every symbol with its scope depth.
+    ///
+    /// Symbols outside of any scope have a depth of `0`. A symbol that
+    /// [starts a scope](Symbol::starts_scope) has the depth of its surrounding scope, and the
+    /// symbols within the scope are one level deeper. The symbol that [ends](Symbol::ends_scope)
+    /// the scope has the same depth as the symbol that started it. This makes the depth suitable
+    /// for indenting a dump of the symbols.
+    ///
+    /// The depth is tracked sequentially, so the `end` fields of the scope-starting symbols are
+    /// not followed. Unbalanced scope ends do not decrease the depth below `0`.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use pdb2::FallibleIterator;
+    /// #
+    /// # fn test() -> pdb2::Result<()> {
+    /// let file = std::fs::File::open("fixtures/self/foo.pdb")?;
+    /// let mut pdb = pdb2::PDB::open(file)?;
+    ///
+    /// let dbi = pdb.debug_information()?;
+    /// let mut modules = dbi.modules()?;
+    /// if let Some(module) = modules.next()? {
+    ///     if let Some(info) = pdb.module_info(&module)? {
+    ///         let mut symbols = info.symbols()?.with_depth();
+    ///         while let Some((depth, symbol)) = symbols.next()? {
+    ///             println!("{:indent$}{:?}", "", symbol.parse(), indent = depth * 2);
+    ///         }
+    ///     }
+    /// }
+    /// # Ok(())
+    /// # }
+    /// # test().expect("test");
+    /// ```
+    #[must_use]
+    pub fn with_depth(self) -> SymbolDepthIter<'t> {
+        SymbolDepthIter {
+            inner: self,
+            depth: 0,
+        }
+    }
+
+    /// Skip to the symbol referred to by `index`, returning the symbol.
     ///
     /// This can be used to jump to the sibiling or parent of a symbol record. Iteration continues
     /// after that symbol.
     ///
     /// Note that the symbol may be located **before** the originating symbol, for instance when
-    /// jumping to the parent symbol. Take care not to enter an endless loop in this case.
+    /// jumping to the parent symbol. Take care not to enter an endless loop in this case, for
+    /// instance by enabling the [cycle guard](Self::with_cycle_guard).
+    ///
+    /// # Errors
+    ///
+    /// * `Error::SymbolCycle` if the [cycle guard](Self::with_cycle_guard) is enabled and `index`
+    ///   has been skipped to before
     pub fn skip_to(&mut self, index: SymbolIndex) -> Result<Option<Symbol<'t>>> {
+        if let Some(ref mut visited) = self.visited {
+            if !visited.insert(index) {
+                return Err(Error::SymbolCycle(index.0));
+            }
+        }
+
         self.seek(index);
         self.next()
     }
@@ -2907,11 +5716,598 @@ impl<'t> FallibleIterator for SymbolIter<'t> {
     }
 }
 
+/// An iterator over the symbols within a byte budget.
+///
+/// Created by [`SymbolIter::take_bytes`].
+#[derive(Debug)]
+pub struct SymbolBudgetIter<'t> {
+    inner: SymbolIter<'t>,
+    start: usize,
+    limit: usize,
+}
+
+impl<'t> SymbolBudgetIter<'t> {
+    /// Returns the index of the next record that has not been read yet.
+    ///
+    /// Once this iterator has stopped, pass this index to [`SymbolTable::iter_at`] or
+    /// [`ModuleInfo::symbols_at`](crate::ModuleInfo::symbols_at) to resume loading.
+    #[must_use]
+    pub fn position(&self) -> SymbolIndex {
+        self.inner.position()
+    }
+
+    /// Returns the number of bytes consumed so far.
+    #[must_use]
+    pub fn consumed(&self) -> usize {
+        self.inner.buf.pos() - self.start
+    }
+
+    /// Returns whether the end of the symbol stream has been reached.
+    #[must_use]
+    pub fn is_exhausted(&self) -> bool {
+        self.inner.buf.is_empty()
+    }
+
+    /// Returns the underlying iterator, positioned after the last record read.
+    #[must_use]
+    pub fn into_inner(self) -> SymbolIter<'t> {
+        self.inner
+    }
+}
+
+impl<'t> FallibleIterator for SymbolBudgetIter<'t> {
+    type Item = Symbol<'t>;
+    type Error = Error;
+
+    fn next(&mut self) -> Result<Option<Self::Item>> {
+        let consumed = self.consumed();
+        if consumed > 0 && consumed >= self.limit {
+            return Ok(None);
+        }
+
+        self.inner.next()
+    }
+}
+
+/// An iterator over symbols and their scope depth.
+///
+/// Created by [`SymbolIter::with_depth`] or [`SymbolTable::iter_with_depth`].
+#[derive(Debug)]
+pub struct SymbolDepthIter<'t> {
+    inner: SymbolIter<'t>,
+    depth: usize,
+}
+
+impl<'t> SymbolDepthIter<'t> {
+    /// Returns the depth of the next symbol, unless it ends a scope.
+    #[must_use]
+    pub fn depth(&self) -> usize {
+        self.depth
+    }
+
+    /// Returns the underlying iterator, positioned after the last record read.
+    #[must_use]
+    pub fn into_inner(self) -> SymbolIter<'t> {
+        self.inner
+    }
+}
+
+impl<'t> FallibleIterator for SymbolDepthIter<'t> {
+    type Item = (usize, Symbol<'t>);
+    type Error = Error;
+
+    fn next(&mut self) -> Result<Option<Self::Item>> {
+        let symbol = match self.inner.next()? {
+            Some(symbol) => symbol,
+            None => return Ok(None),
+        };
+
+        if symbol.ends_scope() {
+            self.depth = self.depth.saturating_sub(1);
+            return Ok(Some((self.depth, symbol)));
+        }
+
+        let depth = self.depth;
+        if symbol.starts_scope() {
+            self.depth += 1;
+        }
+
+        Ok(Some((depth, symbol)))
+    }
+}
+
+/// Maps the index of every scope-starting symbol to the index of its matching end symbol.
+///
+/// This performs a single forward pass over `symbols`, pairing symbols that
+/// [start a scope](Symbol::starts_scope) with the symbols that [end](Symbol::ends_scope) them.
+/// Afterwards, the extent of any scope can be looked up without seeking the iterator to its `end`
+/// field. The symbol records are not parsed. Scopes that are not closed before the end of the
+/// stream are omitted from the map.
+///
+/// # Example
+///
+/// ```
+/// # use pdb2::FallibleIterator;
+/// #
+/// # fn test() -> pdb2::Result<usize> {
+/// let file = std::fs::File::open("fixtures/self/foo.pdb")?;
+/// let mut pdb = pdb2::PDB::open(file)?;
+///
+/// let dbi = pdb.debug_information()?;
+/// let mut modules = dbi.modules()?;
+/// let mut count = 0;
+/// while let Some(module) = modules.next()? {
+///     if let Some(info) = pdb.module_info(&module)? {
+///         count += pdb2::scope_ends(info.symbols()?)?.len();
+///     }
+/// }
+/// # Ok(count)
+/// # }
+/// # assert!(test().expect("test") > 0);
+/// ```
+///
+/// # Errors
+///
+/// * `Error::SymbolTooShort` if a record has an invalid length
+/// * `Error::UnexpectedEof` if the symbol stream is truncated mid-record
+pub fn scope_ends(mut symbols: SymbolIter<'_>) -> Result<HashMap<SymbolIndex, SymbolIndex>> {
+    let mut ends = HashMap::new();
+    let mut starts = Vec::new();
+
+    while let Some(symbol) = symbols.next()? {
+        if symbol.starts_scope() {
+            starts.push(symbol.index());
+        } else if symbol.ends_scope() {
+            if let Some(start) = starts.pop() {
+                ends.insert(start, symbol.index());
+            }
+        }
+    }
+
+    Ok(ends)
+}
+
+/// Maps the index of every scope-starting symbol to the namespaces imported within that scope.
+///
+/// `S_UNAMESPACE` records carry a `using namespace` directive and apply to the scope they appear
+/// in, including all nested scopes. This performs a single forward pass over `symbols` and
+/// accumulates the [`UsingNamespaceSymbol`] names in effect within each procedure, block or other
+/// scope. Directives outside of any scope apply to the whole module and are inherited by every
+/// scope that follows them. Names are listed from the outermost scope inwards, in the order of
+/// their directives.
+///
+/// Only directives preceding a nested scope are inherited by it, mirroring the lexical rules of
+/// the source language. Scopes that are not closed before the end of the stream are included with
+/// the directives seen so far.
+///
+/// # Errors
+///
+/// * `Error::SymbolTooShort` if a record has an invalid length
+/// * `Error::UnexpectedEof` if the symbol stream is truncated mid-record
+/// * any error returned when parsing a `S_UNAMESPACE` record
+pub fn scope_using_namespaces(
+    mut symbols: SymbolIter<'_>,
+) -> Result<HashMap<SymbolIndex, Vec<String>>> {
+    let mut namespaces = HashMap::new();
+    let mut module = Vec::new();
+    let mut scopes: Vec<(SymbolIndex, Vec<String>)> = Vec::new();
+
+    while let Some(symbol) = symbols.next()? {
+        if symbol.starts_scope() {
+            let inherited = scopes.last().map_or(&module, |(_, names)| names).clone();
+            scopes.push((symbol.index(), inherited));
+        } else if symbol.ends_scope() {
+            if let Some((start, names)) = scopes.pop() {
+                namespaces.insert(start, names);
+            }
+        } else if matches!(symbol.raw_kind(), S_UNAMESPACE | S_UNAMESPACE_ST) {
+            if let SymbolData::UsingNamespace(data) = symbol.parse()? {
+                match scopes.last_mut() {
+                    Some((_, names)) => names.push(data.name),
+                    None => module.push(data.name),
+                }
+            }
+        }
+    }
+
+    namespaces.extend(scopes);
+    Ok(namespaces)
+}
+
+/// Counts the inline sites enclosing the inline site at `index`.
+///
+/// This follows the [`parent`](InlineSiteSymbol::parent) pointers of `S_INLINESITE` records up to
+/// the owning procedure. An inline site that was inlined directly into a procedure has a depth of
+/// `0`, an inline site within that one a depth of `1`, and so on. Blocks and separated code between
+/// inline sites are walked through, but not counted. Use this to order or indent inline frames in
+/// stack traces.
+///
+/// Parents always precede their children in a symbol stream. If a parent pointer does not point
+/// backwards, the chain is malformed and the walk stops there, so the depth is always finite.
+///
+/// Returns `None` if `index` does not refer to an inline site.
+///
+/// # Errors
+///
+/// * `Error::SymbolTooShort` if a record has an invalid length
+/// * `Error::UnexpectedEof` if `index` lies outside of the symbol stream
+pub fn inline_site_depth(mut symbols: SymbolIter<'_>, index: SymbolIndex) -> Result<Option<usize>> {
+    let mut current = match symbols.skip_to(index)? {
+        Some(symbol) if matches!(symbol.raw_kind(), S_INLINESITE | S_INLINESITE2) => symbol,
+        _ => return Ok(None),
+    };
+
+    let mut depth = 0;
+    loop {
+        let parent = match current.parse()? {
+            SymbolData::InlineSite(InlineSiteSymbol { parent, .. }) => parent,
+            SymbolData::Block(BlockSymbol { parent, .. })
+            | SymbolData::SeparatedCode(SeparatedCodeSymbol { parent, .. }) => Some(parent),
+            _ => None,
+        };
+
+        let parent = match parent {
+            Some(parent) if parent.0 != 0 && parent < current.index() => parent,
+            _ => break,
+        };
+
+        current = match symbols.skip_to(parent)? {
+            Some(symbol) => symbol,
+            None => break,
+        };
+
+        if matches!(current.raw_kind(), S_INLINESITE | S_INLINESITE2) {
+            depth += 1;
+        }
+    }
+
+    Ok(Some(depth))
+}
+
+/// Finds the procedure that physically contains an inline site.
+///
+/// This follows the [`parent`](InlineSiteSymbol::parent) pointers from `site` through any number
+/// of enclosing inline sites and blocks until a [`ProcedureSymbol`] is reached. Use this to
+/// attribute inline frames to the function whose code they were inlined into.
+///
+/// Parents always precede their children in a symbol stream, so the walk stops if a parent pointer
+/// does not point backwards. Returns `None` if the chain ends without reaching a procedure.
+///
+/// # Errors
+///
+/// * `Error::SymbolTooShort` if a record has an invalid length
+/// * `Error::UnexpectedEof` if a parent lies outside of the symbol stream
+pub fn enclosing_procedure(
+    mut symbols: SymbolIter<'_>,
+    site: &InlineSiteSymbol,
+) -> Result<Option<ProcedureSymbol>> {
+    let mut next = site.parent;
+
+    while let Some(parent) = next.take() {
+        let symbol = match symbols.skip_to(parent)? {
+            Some(symbol) => symbol,
+            None => break,
+        };
+
+        let grandparent = match symbol.parse()? {
+            SymbolData::Procedure(procedure) => return Ok(Some(procedure)),
+            SymbolData::InlineSite(InlineSiteSymbol { parent, .. }) => parent,
+            SymbolData::Block(BlockSymbol { parent, .. }) => Some(parent),
+            _ => None,
+        };
+
+        next = grandparent.filter(|grandparent| *grandparent < parent);
+    }
+
+    Ok(None)
+}
+
+/// Collects the local variables declared directly within the scope of `block`.
+///
+/// Local variables are `S_LOCAL`, `S_REGREL32` and `S_BPREL32` records. Variables of nested scopes,
+/// such as inner blocks or inline sites, are not included. The symbols are returned in the order of
+/// the symbol stream.
+///
+/// The block record is located by scanning forward from its [`parent`](BlockSymbol::parent), so
+/// the current position of `symbols` does not matter. If the block cannot be found, an empty list
+/// is returned.
+///
+/// # Errors
+///
+/// * `Error::SymbolTooShort` if a record has an invalid length
+/// * `Error::UnexpectedEof` if the symbol stream is truncated mid-record
+/// * any error from parsing a block record within the parent scope
+pub fn collect_block_locals<'t>(
+    mut symbols: SymbolIter<'t>,
+    block: &BlockSymbol,
+) -> Result<Vec<Symbol<'t>>> {
+    symbols.seek(block.parent);
+
+    let mut found = false;
+    while let Some(symbol) = symbols.next()? {
+        if symbol.index() >= block.end {
+            break;
+        }
+
+        if matches!(symbol.raw_kind(), S_BLOCK32 | S_BLOCK32_ST) {
+            if let SymbolData::Block(data) = symbol.parse()? {
+                if data.end == block.end {
+                    found = true;
+                    break;
+                }
+            }
+        }
+    }
+
+    let mut locals = Vec::new();
+    if !found {
+        return Ok(locals);
+    }
+
+    let mut depth = 0usize;
+    while let Some(symbol) = symbols.next()? {
+        if symbol.index() >= block.end {
+            break;
+        }
+
+        if symbol.starts_scope() {
+            depth += 1;
+        } else if symbol.ends_scope() {
+            depth = depth.saturating_sub(1);
+        } else if depth == 0
+            && matches!(
+                symbol.raw_kind(),
+                S_LOCAL | S_REGREL32 | S_BPREL32 | S_BPREL32_ST | S_BPREL32_16T
+            )
+        {
+            locals.push(symbol);
+        }
+    }
+
+    Ok(locals)
+}
+
+/// Collects the parameters of `procedure` in declaration order.
+///
+/// Parameters are `S_LOCAL` records with [`LocalVariableFlags::isparam`] set. Compilers emit them
+/// directly after the procedure record, ahead of its other locals. Collection therefore stops at
+/// the first local that is not a parameter, at the first nested scope, or at the end of the
+/// procedure, whichever comes first. `S_REGREL32` and `S_BPREL32` records carry no such flag and
+/// are not included.
+///
+/// The procedure record is located by scanning forward from its [`parent`](ProcedureSymbol::parent)
+/// if it has one, or from the current position of `symbols` otherwise. If the procedure cannot be
+/// found, an empty list is returned.
+///
+/// # Errors
+///
+/// * `Error::SymbolTooShort` if a record has an invalid length
+/// * `Error::UnexpectedEof` if the symbol stream is truncated mid-record
+/// * any error from parsing a procedure or local record
+pub fn procedure_parameters<'t>(
+    mut symbols: SymbolIter<'t>,
+    procedure: &ProcedureSymbol,
+) -> Result<Vec<Symbol<'t>>> {
+    if let Some(parent) = procedure.parent {
+        symbols.seek(parent);
+    }
+
+    let mut found = false;
+    while let Some(symbol) = symbols.next()? {
+        if symbol.index() >= procedure.end {
+            break;
+        }
+
+        if matches!(
+            symbol.raw_kind(),
+            S_LPROC32
+                | S_LPROC32_ST
+                | S_GPROC32
+                | S_GPROC32_ST
+                | S_LPROC32_ID
+                | S_GPROC32_ID
+                | S_LPROC32_DPC
+                | S_LPROC32_DPC_ID
+        ) {
+            if let SymbolData::Procedure(data) = symbol.parse()? {
+                if data.end == procedure.end && data.offset == procedure.offset {
+                    found = true;
+                    break;
+                }
+            }
+        }
+    }
+
+    let mut parameters = Vec::new();
+    if !found {
+        return Ok(parameters);
+    }
+
+    while let Some(symbol) = symbols.next()? {
+        if symbol.index() >= procedure.end || symbol.starts_scope() || symbol.ends_scope() {
+            break;
+        }
+
+        if symbol.raw_kind() == S_LOCAL {
+            match symbol.parse()? {
+                SymbolData::Local(data) if data.flags.isparam => parameters.push(symbol),
+                _ => break,
+            }
+        }
+    }
+
+    Ok(parameters)
+}
+
+/// An iterator over the symbols of a `SymbolTable` that lie within an address range.
+///
+/// Created by [`SymbolTable::iter_in_range`].
+#[derive(Debug)]
+pub struct SymbolRangeIter<'t> {
+    inner: SymbolIter<'t>,
+    section: u16,
+    offset_range: Range<u32>,
+}
+
+impl<'t> FallibleIterator for SymbolRangeIter<'t> {
+    type Item = Symbol<'t>;
+    type Error = Error;
+
+    fn next(&mut self) -> Result<Option<Self::Item>> {
+        while let Some(symbol) = self.inner.next()? {
+            let offset = match symbol.parse() {
+                Ok(data) => data.offset(),
+                Err(Error::UnimplementedSymbolKind(_)) => None,
+                Err(e) => return Err(e),
+            };
+
+            if let Some(offset) = offset {
+                if offset.section == self.section && self.offset_range.contains(&offset.offset) {
+                    return Ok(Some(symbol));
+                }
+            }
+        }
+
+        Ok(None)
+    }
+}
+
 #[cfg(test)]
 mod tests {
+    mod records {
+        use crate::symbol::*;
+
+        /// Prefixes a record payload with its length and kind.
+        pub fn record(kind: SymbolKind, payload: &[u8]) -> Vec<u8> {
+            let mut data = ((payload.len() + 2) as u16).to_le_bytes().to_vec();
+            data.extend_from_slice(&kind.to_le_bytes());
+            data.extend_from_slice(payload);
+            data
+        }
+
+        /// Iterates a module symbol stream, skipping its 4-byte signature.
+        pub fn module_symbols(data: &[u8]) -> SymbolIter<'_> {
+            let mut buf = ParseBuffer::from(data);
+            buf.seek(4);
+            SymbolIter::new(buf)
+        }
+
+        pub fn procedure(end: u32, next: u32, name: &str) -> Vec<u8> {
+            let mut payload = vec![0; 4]; // parent
+            payload.extend_from_slice(&end.to_le_bytes());
+            payload.extend_from_slice(&next.to_le_bytes());
+            payload.extend_from_slice(&[0; 23]); // len, debug offsets, type, offset, flags
+            payload.extend_from_slice(name.as_bytes());
+            payload.push(0);
+            record(S_GPROC32, &payload)
+        }
+
+        /// A procedure with type `0x1003` at the given offset into section 1.
+        pub fn procedure_at(end: u32, offset: u32, name: &str) -> Vec<u8> {
+            let mut payload = vec![0; 4]; // parent
+            payload.extend_from_slice(&end.to_le_bytes());
+            payload.extend_from_slice(&[0; 16]); // next, len, debug offsets
+            payload.extend_from_slice(&0x1003u32.to_le_bytes()); // type
+            payload.extend_from_slice(&offset.to_le_bytes());
+            payload.extend_from_slice(&[1, 0, 0]); // segment, flags
+            payload.extend_from_slice(name.as_bytes());
+            payload.push(0);
+            record(S_GPROC32, &payload)
+        }
+
+        /// A public function at the given offset into section 1.
+        pub fn public(offset: u32, name: &str) -> Vec<u8> {
+            let mut payload = 2u32.to_le_bytes().to_vec(); // flags
+            payload.extend_from_slice(&offset.to_le_bytes());
+            payload.extend_from_slice(&[1, 0]); // segment
+            payload.extend_from_slice(name.as_bytes());
+            payload.push(0);
+            record(S_PUB32, &payload)
+        }
+
+        pub fn block(parent: u32, end: u32, name: &str) -> Vec<u8> {
+            let mut payload = parent.to_le_bytes().to_vec();
+            payload.extend_from_slice(&end.to_le_bytes());
+            payload.extend_from_slice(&[0; 10]); // len, offset, segment
+            payload.extend_from_slice(name.as_bytes());
+            payload.push(0);
+            record(S_BLOCK32, &payload)
+        }
+
+        pub fn local(name: &str, is_param: bool) -> Vec<u8> {
+            let mut payload = vec![0x74, 0, 0, 0, is_param as u8, 0]; // type, flags
+            payload.extend_from_slice(name.as_bytes());
+            payload.push(0);
+            record(S_LOCAL, &payload)
+        }
+
+        pub fn scope_end() -> Vec<u8> {
+            record(S_END, &[])
+        }
+
+        /// Maps section 1 to the RVA range `0x1000..0x1100`.
+        pub fn address_map() -> AddressMap<'static> {
+            AddressMap {
+                original_sections: vec![ImageSectionHeader {
+                    virtual_address: 0x1000,
+                    virtual_size: 0x100,
+                    size_of_raw_data: 0x100,
+                    ..Default::default()
+                }],
+                ..Default::default()
+            }
+        }
+    }
+
     mod parsing {
         use crate::symbol::*;
 
+        #[test]
+        fn parse_lenient() {
+            // a made-up symbol kind
+            let data = &[0x34, 0x12, 1, 2, 3];
+            let symbol = Symbol {
+                data,
+                index: SymbolIndex(0),
+            };
+            assert!(matches!(
+                symbol.parse(),
+                Err(Error::UnimplementedSymbolKind(0x1234))
+            ));
+            let unknown = symbol.parse_lenient();
+            assert_eq!(
+                unknown,
+                SymbolData::Unknown(UnknownSymbol {
+                    kind: 0x1234,
+                    data: vec![1, 2, 3],
+                })
+            );
+            assert_eq!(unknown.kind(), SymbolKindTag::Unknown);
+            assert_eq!(unknown.heap_size(), 3);
+
+            // a known symbol kind that is truncated
+            let data = &[0x05, 0x11, 0xe0, 0x5f];
+            let symbol = Symbol {
+                data,
+                index: SymbolIndex(0),
+            };
+            assert!(symbol.parse().is_err());
+            assert_eq!(
+                symbol.parse_lenient(),
+                SymbolData::Unknown(UnknownSymbol {
+                    kind: S_LABEL32,
+                    data: vec![0xe0, 0x5f],
+                })
+            );
+
+            // valid symbols parse as usual
+            let symbol = Symbol {
+                data: &[6, 0],
+                index: SymbolIndex(0),
+            };
+            assert_eq!(symbol.parse_lenient(), SymbolData::ScopeEnd);
+        }
+
         #[test]
         fn kind_0006() {
             let data = &[6, 0];
@@ -2940,6 +6336,13 @@ mod tests {
                     name: "* CIL *".into(),
                 })
             );
+
+            let obj_name = match symbol.parse().expect("parse") {
+                SymbolData::ObjName(obj_name) => obj_name,
+                other => panic!("unexpected symbol {:?}", other),
+            };
+            assert_eq!(obj_name.signature_kind(), ObjSignatureKind::Cil);
+            assert!(obj_name.signature_kind().is_synthetic());
         }
 
         #[test]
@@ -3005,6 +6408,24 @@ mod tests {
                     name: "dav1d_w_avg_ssse3".into(),
                 })
             );
+
+            let label = match symbol.parse() {
+                Ok(SymbolData::Label(label)) => label,
+                other => panic!("unexpected symbol {:?}", other),
+            };
+            let text = ImageSectionHeader {
+                name: *b".text\0\0\0",
+                characteristics: SectionCharacteristics(0x6000_0020),
+                ..Default::default()
+            };
+            let data = ImageSectionHeader {
+                name: *b".data\0\0\0",
+                characteristics: SectionCharacteristics(0xc000_0040),
+                ..Default::default()
+            };
+            assert_eq!(label.is_code_label(&[text, data]), Some(true));
+            assert_eq!(label.is_code_label(&[data, text]), Some(false));
+            assert_eq!(label.is_code_label(&[]), None);
         }
 
         #[test]
@@ -3157,6 +6578,65 @@ mod tests {
             );
         }
 
+        #[test]
+        fn kind_1120() {
+            let data = &[
+                32, 17, 1, 0, 0, 0, 3, 0, 0, 2, 16, 0, 0, 0, 1, 0, 0, 0, 105, 0,
+            ];
+            let symbol = Symbol {
+                data,
+                index: SymbolIndex(0),
+            };
+
+            let slot = match symbol.parse().expect("parse") {
+                SymbolData::ManagedSlot(slot) => slot,
+                other => panic!("unexpected symbol {:?}", other),
+            };
+            assert_eq!(slot.slot, 1);
+            assert_eq!(slot.name, "i");
+            assert_eq!(slot.type_token(), COMToken(0x0200_0003));
+            assert_eq!(slot.type_token().table(), MetadataTable::TypeDef);
+            assert_eq!(slot.type_token().rid(), 3);
+        }
+
+        #[test]
+        fn kind_1107_octword() {
+            let mut data = vec![7, 17, 0x78, 0, 0, 0, 0x17, 0x80];
+            data.extend_from_slice(&(-2i128).to_le_bytes());
+            data.extend_from_slice(b"minus_two\0");
+            let symbol = Symbol {
+                data: &data,
+                index: SymbolIndex(0),
+            };
+            assert_eq!(
+                symbol.parse().expect("parse"),
+                SymbolData::Constant(ConstantSymbol {
+                    managed: false,
+                    type_index: TypeIndex(0x78),
+                    value: Variant::I128(-2),
+                    name: "minus_two".into(),
+                })
+            );
+
+            let mut data = vec![7, 17, 0x79, 0, 0, 0, 0x18, 0x80];
+            data.extend_from_slice(&u128::MAX.to_le_bytes());
+            data.extend_from_slice(b"mask\0");
+            let symbol = Symbol {
+                data: &data,
+                index: SymbolIndex(0),
+            };
+            match symbol.parse().expect("parse") {
+                SymbolData::Constant(constant) => {
+                    assert_eq!(constant.value, Variant::U128(u128::MAX));
+                    assert_eq!(
+                        constant.value.to_string(),
+                        "340282366920938463463374607431768211455"
+                    );
+                }
+                other => panic!("unexpected symbol {:?}", other),
+            }
+        }
+
         #[test]
         fn kind_110d() {
             let data = &[
@@ -3283,6 +6763,7 @@ mod tests {
                     dbg_start_offset: 5,
                     dbg_end_offset: 5,
                     type_index: TypeIndex(4103),
+                    type_index_in_id_stream: false,
                     offset: PdbInternalSectionOffset {
                         offset: 21824,
                         section: 1
@@ -3300,6 +6781,20 @@ mod tests {
                     name: "Baz::f_protected".into(),
                 })
             );
+
+            match symbol.parse().expect("parse") {
+                SymbolData::Procedure(procedure) => {
+                    assert_eq!(
+                        procedure.body_start(),
+                        PdbInternalSectionOffset::new(1, 21829)
+                    );
+                    assert_eq!(
+                        procedure.body_end(),
+                        PdbInternalSectionOffset::new(1, 21829)
+                    );
+                }
+                other => panic!("unexpected symbol {:?}", other),
+            }
         }
 
         #[test]
@@ -3328,6 +6823,29 @@ mod tests {
             );
         }
 
+        #[test]
+        fn kind_1147() {
+            let data = &[
+                71, 17, 0, 0, 0, 0, 48, 2, 0, 0, 0, 0, 0, 0, 6, 0, 0, 0, 5, 0, 0, 0, 5, 0, 0, 0, 7,
+                16, 0, 0, 64, 85, 0, 0, 1, 0, 0, 66, 97, 122, 58, 58, 102, 95, 112, 114, 111, 116,
+                101, 99, 116, 101, 100, 0,
+            ];
+            let symbol = Symbol {
+                data,
+                index: SymbolIndex(0),
+            };
+            assert_eq!(symbol.raw_kind(), S_GPROC32_ID);
+            match symbol.parse().expect("parse") {
+                SymbolData::Procedure(procedure) => {
+                    assert!(procedure.global);
+                    assert!(procedure.type_index_in_id_stream);
+                    assert_eq!(procedure.type_index, TypeIndex(4103));
+                    assert_eq!(procedure.name, "Baz::f_protected");
+                }
+                other => panic!("unexpected symbol {:?}", other),
+            }
+        }
+
         #[test]
         fn kind_110f() {
             let data = &[
@@ -3352,6 +6870,7 @@ mod tests {
                     dbg_start_offset: 4,
                     dbg_end_offset: 9,
                     type_index: TypeIndex(4224),
+                    type_index_in_id_stream: false,
                     offset: PdbInternalSectionOffset {
                         offset: 22468,
                         section: 1
@@ -3400,6 +6919,7 @@ mod tests {
                         sdl: false,
                         pgo: false,
                         exp_module: false,
+                        raw: 0,
                     },
                     cpu_type: CPUType::Intel80386,
                     frontend_version: CompilerVersion {
@@ -3551,6 +7071,7 @@ mod tests {
                         sdl: true,
                         pgo: false,
                         exp_module: false,
+                        raw: 0x0224,
                     },
                     cpu_type: CPUType::Pentium3,
                     frontend_version: CompilerVersion {
@@ -3570,6 +7091,33 @@ mod tests {
             );
         }
 
+        #[test]
+        fn kind_113c_raw_flags() {
+            let data = &[
+                60, 17, 1, 36, 2, 0, 7, 0, 19, 0, 13, 0, 6, 102, 0, 0, 19, 0, 13, 0, 6, 102, 0, 0,
+                0,
+            ];
+
+            let symbol = Symbol {
+                data,
+                index: SymbolIndex(0),
+            };
+            let flags = match symbol.parse().expect("parse") {
+                SymbolData::CompileFlags(symbol) => symbol.flags,
+                other => panic!("unexpected symbol {:?}", other),
+            };
+            assert_eq!(flags.raw(), u16::from_le_bytes([36, 2]));
+            assert_eq!(flags.unknown_bits(), 0);
+
+            // /sdl is only defined for S_COMPILE3, and bit 15 is not defined at all.
+            let flags: CompileFlags = [0x00, 0x82, 0x00]
+                .pread_with(0, SymbolKind::from(S_COMPILE2))
+                .expect("parse");
+            assert!(!flags.sdl);
+            assert_eq!(flags.raw(), 0x8200);
+            assert_eq!(flags.unknown_bits(), 0x8200);
+        }
+
         #[test]
         fn kind_113e() {
             let data = &[62, 17, 193, 19, 0, 0, 1, 0, 116, 104, 105, 115, 0, 0];
@@ -3639,6 +7187,11 @@ mod tests {
                     annotations: BinaryAnnotations::new(&[12, 6, 3, 0]),
                 })
             );
+
+            match symbol.parse().expect("parse") {
+                SymbolData::InlineSite(site) => assert_eq!(site.code_length(), 6),
+                _ => unreachable!(),
+            }
         }
 
         #[test]
@@ -3751,9 +7304,38 @@ mod tests {
                         opt_speed: false,
                         guard_cf: false,
                         guard_cfw: false,
+                        raw: 0x0002_a030,
                     },
                 })
             );
+
+            let frame = match symbol.parse().expect("parse") {
+                SymbolData::FrameProcedure(frame) => frame,
+                other => panic!("unexpected symbol {:?}", other),
+            };
+            let layout = frame.frame_layout(CPUType::X64);
+            assert_eq!(
+                layout,
+                FrameLayout {
+                    frame_size: 152,
+                    local_base: FrameBasePointer::FramePointer,
+                    local_base_register: Some(Register::X64_RBP),
+                    param_base: FrameBasePointer::FramePointer,
+                    param_base_register: Some(Register::X64_RBP),
+                    locals: 0..152,
+                    callee_saved: 152..152,
+                    padding: 0..0,
+                    params_offset: 160,
+                }
+            );
+
+            let layout = frame.frame_layout(CPUType::Pentium3);
+            assert_eq!(layout.local_base_register, Some(Register::X86_EBP));
+            assert_eq!(layout.params_offset, 156);
+
+            // the link register is saved with the callee-saved registers on ARM64
+            let layout = frame.frame_layout(CPUType::ARM64);
+            assert_eq!(layout.params_offset, 152);
         }
 
         // S_CALLEES - 0x115a
@@ -3770,8 +7352,8 @@ mod tests {
             assert_eq!(
                 symbol.parse().expect("parse"),
                 SymbolData::Callees(FunctionListSymbol {
-                    functions: vec![TypeIndex(0x48bf), TypeIndex(0x48bf), TypeIndex(0x48bf)],
-                    invocations: vec![18624, 18625, 0]
+                    functions: vec![TypeIndex(0x48bf), TypeIndex(0x48c0), TypeIndex(0x48c1)],
+                    invocations: vec![0, 0, 0]
                 })
             );
         }
@@ -3849,51 +7431,1554 @@ mod tests {
         }
     }
 
-    mod iterator {
+    mod heap_allocation_sites {
         use crate::symbol::*;
+        use crate::tpi::tests::{finder, record, type_information};
 
-        fn create_iter() -> SymbolIter<'static> {
+        #[test]
+        fn test_collect() {
             let data = &[
-                0x00, 0x00, 0x00, 0x00, // module signature (padding)
                 0x02, 0x00, 0x4e, 0x11, // S_INLINESITE_END
+                0x0e, 0x00, 94, 17, 18, 166, 84, 0, 1, 0, 5, 0, 138, 20, 0,
+                0, // S_HEAPALLOCSITE
                 0x02, 0x00, 0x06, 0x00, // S_END
             ];
 
-            let mut buf = ParseBuffer::from(&data[..]);
-            buf.seek(4); // skip the module signature
-            SymbolIter::new(buf)
+            let iter = SymbolIter::new(ParseBuffer::from(&data[..]));
+            let sites = heap_allocation_sites(iter).expect("collect");
+
+            assert_eq!(
+                sites,
+                [HeapAllocationSiteSymbol {
+                    offset: PdbInternalSectionOffset {
+                        section: 0x1,
+                        offset: 0x54a612
+                    },
+                    type_index: TypeIndex(0x148a),
+                    instr_length: 5,
+                }]
+            );
         }
 
         #[test]
-        fn test_iter() {
-            let symbols: Vec<_> = create_iter().collect().expect("collect");
+        fn test_group_by_signature() {
+            // 0x1000: (unsigned __int64), 0x1001: void * __cdecl (unsigned __int64)
+            let mut procedure = 0x603u32.to_le_bytes().to_vec(); // return type
+            procedure.extend_from_slice(&[0, 0, 1, 0]); // attributes, parameter count
+            procedure.extend_from_slice(&0x1000u32.to_le_bytes()); // argument list
+            let mut arguments = 1u32.to_le_bytes().to_vec();
+            arguments.extend_from_slice(&0x23u32.to_le_bytes());
+            let types = type_information(&[record(0x1201, &arguments), record(0x1008, &procedure)]);
+            let finder = finder(&types);
+
+            let site = |type_index| HeapAllocationSiteSymbol {
+                offset: PdbInternalSectionOffset::default(),
+                instr_length: 5,
+                type_index: TypeIndex(type_index),
+            };
+            let sites = [site(0x1001), site(0x74), site(0x1001)];
+            let groups = group_heap_allocation_sites(&sites, &finder).expect("group");
 
-            let expected = [
-                Symbol {
-                    index: SymbolIndex(0x4),
-                    data: &[0x4e, 0x11], // S_INLINESITE_END
+            let keys: Vec<_> = groups.keys().map(String::as_str).collect();
+            assert_eq!(keys, ["I32", "Void* __cdecl(UQuad)"]);
+            assert_eq!(groups["Void* __cdecl(UQuad)"].len(), 2);
+        }
+    }
+
+    mod ranges {
+        use super::records::*;
+        use crate::symbol::*;
+
+        fn def_range_register(register: u8) -> Vec<u8> {
+            let body = [register, 0, 0, 0, 0x46, 0x28, 0, 0, 1, 0, 0x42, 0];
+            record(S_DEFRANGE_REGISTER, &body)
+        }
+
+        #[test]
+        fn test_local_def_ranges() {
+            let mut procedure = vec![0; 35];
+            procedure.extend_from_slice(b"f\0");
+
+            let mut data = vec![0; 4]; // module signature (padding)
+            data.extend(record(S_GPROC32_ID, &procedure));
+            data.extend(record(S_LOCAL, b"\xc1\x13\0\0\x01\0this\0"));
+            data.extend(def_range_register(0x11));
+            data.extend(block(0, 0, "b"));
+            data.extend(local("x", false));
+            data.extend(def_range_register(0x12));
+            data.extend(scope_end());
+            // belongs to "this" again after the block ended
+            data.extend(def_range_register(0x13));
+            data.extend(local("unused", false));
+            data.extend(record(S_PROC_ID_END, &[]));
+            // dropped, there is no local in the outermost scope
+            data.extend(def_range_register(0x14));
+
+            let locals = local_def_ranges(module_symbols(&data)).expect("def ranges");
+
+            let summary: Vec<_> = locals
+                .iter()
+                .map(|(local, ranges)| {
+                    let registers: Vec<_> = ranges
+                        .iter()
+                        .map(|range| match range {
+                            DefRangeData::Register(data) => data.register.0,
+                            other => panic!("unexpected def range {:?}", other),
+                        })
+                        .collect();
+                    (local.name.as_str(), registers)
+                })
+                .collect();
+
+            assert_eq!(
+                summary,
+                vec![
+                    ("this", vec![0x11, 0x13]),
+                    ("x", vec![0x12]),
+                    ("unused", vec![]),
+                ]
+            );
+        }
+
+        #[test]
+        fn test_subtract_gaps() {
+            let range = AddressRange {
+                offset: PdbInternalSectionOffset::new(0x1, 0x100),
+                cb_range: 0x30,
+            };
+
+            // Unordered on purpose.
+            let gaps = [
+                AddressGap {
+                    gap_start_offset: 0x20,
+                    cb_range: 0x4,
                 },
-                Symbol {
-                    index: SymbolIndex(0x8),
-                    data: &[0x06, 0x00], // S_END
+                AddressGap {
+                    gap_start_offset: 0x8,
+                    cb_range: 0x8,
                 },
             ];
 
-            assert_eq!(symbols, expected);
+            assert_eq!(
+                range.subtract_gaps(&gaps),
+                [
+                    (PdbInternalSectionOffset::new(0x1, 0x100), 0x8),
+                    (PdbInternalSectionOffset::new(0x1, 0x110), 0x10),
+                    (PdbInternalSectionOffset::new(0x1, 0x124), 0xc),
+                ]
+            );
         }
 
         #[test]
-        fn test_seek() {
-            let mut symbols = create_iter();
-            symbols.seek(SymbolIndex(0x8));
+        fn test_subtract_gaps_overlapping() {
+            let range = AddressRange {
+                offset: PdbInternalSectionOffset::new(0x1, 0x100),
+                cb_range: 0x10,
+            };
 
-            let symbol = symbols.next().expect("get symbol");
-            let expected = Symbol {
-                index: SymbolIndex(0x8),
-                data: &[0x06, 0x00], // S_END
+            let gaps = [
+                AddressGap {
+                    gap_start_offset: 0x0,
+                    cb_range: 0x6,
+                },
+                AddressGap {
+                    gap_start_offset: 0x4,
+                    cb_range: 0x4,
+                },
+                AddressGap {
+                    gap_start_offset: 0xc,
+                    cb_range: 0x10,
+                },
+            ];
+
+            assert_eq!(
+                range.subtract_gaps(&gaps),
+                [(PdbInternalSectionOffset::new(0x1, 0x108), 0x4)]
+            );
+            assert_eq!(range.subtract_gaps(&[]), [(range.offset, 0x10)]);
+        }
+
+        #[test]
+        fn test_live_ranges() {
+            let symbol = DefRangeRegisterSymbol {
+                register: Register(0x14b),
+                flags: RangeFlags { maybe: true },
+                range: AddressRange {
+                    offset: PdbInternalSectionOffset {
+                        offset: 0x1000,
+                        section: 0x1,
+                    },
+                    cb_range: 0x40,
+                },
+                gaps: vec![AddressGap {
+                    gap_start_offset: 0x10,
+                    cb_range: 0x8,
+                }],
             };
 
-            assert_eq!(symbol, Some(expected));
+            let ranges: Vec<_> = symbol.live_ranges().collect();
+            assert_eq!(
+                ranges,
+                [
+                    (
+                        AddressRange {
+                            offset: PdbInternalSectionOffset::new(0x1, 0x1000),
+                            cb_range: 0x10,
+                        },
+                        true
+                    ),
+                    (
+                        AddressRange {
+                            offset: PdbInternalSectionOffset::new(0x1, 0x1018),
+                            cb_range: 0x28,
+                        },
+                        true
+                    ),
+                ]
+            );
+        }
+
+        #[test]
+        fn test_live_ranges_gap_at_end() {
+            let symbol = DefRangeFramePointerRelativeSymbol {
+                offset: -8,
+                range: AddressRange {
+                    offset: PdbInternalSectionOffset::new(0x1, 0x2000),
+                    cb_range: 0x20,
+                },
+                gaps: vec![AddressGap {
+                    gap_start_offset: 0x18,
+                    cb_range: 0x10,
+                }],
+            };
+
+            let ranges: Vec<_> = symbol.live_ranges().collect();
+            assert_eq!(
+                ranges,
+                [(
+                    AddressRange {
+                        offset: PdbInternalSectionOffset::new(0x1, 0x2000),
+                        cb_range: 0x18,
+                    },
+                    false
+                )]
+            );
+        }
+    }
+
+    mod data {
+        use crate::symbol::*;
+
+        #[test]
+        fn test_variables() {
+            let data = &[
+                13, 17, 116, 0, 0, 0, 16, 0, 0, 0, 3, 0, 95, 95, 105, 115, 97, 95, 97, 118, 97,
+                105, 108, 97, 98, 108, 101, 0, 0, 0,
+            ];
+            let symbol = Symbol {
+                data,
+                index: SymbolIndex(0),
+            };
+            let variable = symbol
+                .parse()
+                .expect("parse")
+                .into_variable()
+                .expect("variable");
+            assert_eq!(variable.storage_class(), StorageClass::Static);
+            assert_eq!(variable.name(), "__isa_available");
+            assert_eq!(variable.type_index(), TypeIndex(116));
+            assert_eq!(variable.offset(), PdbInternalSectionOffset::new(3, 16));
+
+            let data = &[
+                0x12, 0x11, // S_LTHREAD32
+                0x74, 0, 0, 0, // type index
+                8, 0, 0, 0, // offset
+                9, 0, // section
+                b't', b'l', b's', 0,
+            ];
+            let symbol = Symbol {
+                data,
+                index: SymbolIndex(0),
+            };
+            let variable = symbol
+                .parse()
+                .expect("parse")
+                .into_variable()
+                .expect("variable");
+            assert_eq!(variable.storage_class(), StorageClass::Tls);
+            assert!(!variable.global());
+            assert_eq!(variable.name(), "tls");
+            assert_eq!(variable.offset(), PdbInternalSectionOffset::new(9, 8));
+
+            assert_eq!(SymbolData::ScopeEnd.into_variable(), None);
+        }
+
+        fn sections() -> Vec<ImageSectionHeader> {
+            vec![
+                ImageSectionHeader {
+                    name: *b".data\0\0\0",
+                    virtual_size: 0x2000,
+                    virtual_address: 0x1000,
+                    size_of_raw_data: 0x1000,
+                    characteristics: SectionCharacteristics(0xc000_0040),
+                    ..Default::default()
+                },
+                ImageSectionHeader {
+                    name: *b".bss\0\0\0\0",
+                    virtual_size: 0x1000,
+                    virtual_address: 0x3000,
+                    size_of_raw_data: 0,
+                    characteristics: SectionCharacteristics(0xc000_0080),
+                    ..Default::default()
+                },
+            ]
+        }
+
+        #[test]
+        fn test_tls_offset() {
+            let mut sections = sections();
+            sections.push(ImageSectionHeader {
+                name: *b".tls\0\0\0\0",
+                virtual_size: 0x20,
+                virtual_address: 0x4000,
+                size_of_raw_data: 0x200,
+                characteristics: SectionCharacteristics(0xc000_0040),
+                ..Default::default()
+            });
+
+            let tls_at = |section, offset| ThreadStorageSymbol {
+                global: false,
+                type_index: TypeIndex(0x74),
+                offset: PdbInternalSectionOffset { offset, section },
+                name: "tls".into(),
+            };
+
+            assert_eq!(tls_at(3, 0x8).tls_offset(&sections), Some(0x8));
+            assert_eq!(tls_at(3, 0x1f).tls_offset(&sections), Some(0x1f));
+            // within the file alignment padding, but outside of the TLS block
+            assert_eq!(tls_at(3, 0x20).tls_offset(&sections), None);
+            assert_eq!(tls_at(3, 0x1ff).tls_offset(&sections), None);
+            assert_eq!(tls_at(1, 0x8).tls_offset(&sections), None);
+            assert_eq!(tls_at(0, 0x8).tls_offset(&sections), None);
+            assert_eq!(tls_at(4, 0x8).tls_offset(&sections), None);
+
+            // without a virtual size, fall back to the size on disk
+            sections[2].virtual_size = 0;
+            assert_eq!(tls_at(3, 0x1ff).tls_offset(&sections), Some(0x1ff));
+            assert_eq!(tls_at(3, 0x200).tls_offset(&sections), None);
+        }
+
+        fn data_at(section: u16, offset: u32) -> DataSymbol {
+            DataSymbol {
+                global: true,
+                managed: false,
+                type_index: TypeIndex(0x74),
+                offset: PdbInternalSectionOffset { offset, section },
+                name: "g_value".into(),
+            }
+        }
+
+        #[test]
+        fn test_storage() {
+            let sections = sections();
+
+            assert_eq!(
+                data_at(1, 0x10).storage(&sections),
+                Some(DataStorage::Initialized)
+            );
+            assert_eq!(
+                data_at(1, 0x1800).storage(&sections),
+                Some(DataStorage::Uninitialized)
+            );
+            assert_eq!(
+                data_at(2, 0x10).storage(&sections),
+                Some(DataStorage::Uninitialized)
+            );
+            assert_eq!(data_at(0, 0x10).storage(&sections), None);
+            assert_eq!(data_at(3, 0x10).storage(&sections), None);
+        }
+
+        fn section_record(
+            isec: u16,
+            rva: u32,
+            cb: u32,
+            characteristics: u32,
+            name: &str,
+        ) -> Vec<u8> {
+            let mut record = Vec::new();
+            record.extend_from_slice(&S_SECTION.to_le_bytes());
+            record.extend_from_slice(&isec.to_le_bytes());
+            record.extend_from_slice(&[12, 0]); // align, reserved
+            record.extend_from_slice(&rva.to_le_bytes());
+            record.extend_from_slice(&cb.to_le_bytes());
+            record.extend_from_slice(&characteristics.to_le_bytes());
+            record.extend_from_slice(name.as_bytes());
+            record.push(0);
+
+            let mut data = (record.len() as u16).to_le_bytes().to_vec();
+            data.extend(record);
+            data
+        }
+
+        #[test]
+        fn test_validate_sections() {
+            let sections = sections();
+
+            let mut data = Vec::new();
+            data.extend(section_record(1, 0x1000, 0x2000, 0xc000_0040, ".data"));
+            data.extend(section_record(2, 0x3000, 0x1000, 0xc000_0080, ".bss"));
+            let symbols = SymbolIter::new(ParseBuffer::from(&data[..]));
+            assert_eq!(
+                validate_sections(symbols, &sections).expect("validate"),
+                vec![]
+            );
+
+            let mut data = Vec::new();
+            data.extend(section_record(1, 0x1000, 0x2000, 0x4000_0040, ".rdata"));
+            data.extend(section_record(2, 0x4000, 0x800, 0xc000_0080, ".bss"));
+            data.extend(section_record(3, 0x5000, 0x100, 0x4000_0040, ".reloc"));
+            let symbols = SymbolIter::new(ParseBuffer::from(&data[..]));
+            assert_eq!(
+                validate_sections(symbols, &sections).expect("validate"),
+                vec![
+                    SectionMismatch::Name {
+                        section: 1,
+                        symbol: ".rdata".into(),
+                        header: ".data".into(),
+                    },
+                    SectionMismatch::Characteristics {
+                        section: 1,
+                        symbol: SectionCharacteristics(0x4000_0040),
+                        header: SectionCharacteristics(0xc000_0040),
+                    },
+                    SectionMismatch::Rva {
+                        section: 2,
+                        symbol: 0x4000,
+                        header: 0x3000,
+                    },
+                    SectionMismatch::Size {
+                        section: 2,
+                        symbol: 0x800,
+                        header: 0x1000,
+                    },
+                    SectionMismatch::MissingHeader { section: 3 },
+                ]
+            );
+        }
+    }
+
+    mod kind_names {
+        use crate::symbol::*;
+
+        #[test]
+        fn test_symbol_kind_name() {
+            assert_eq!(symbol_kind_name(S_GPROC32), Some("S_GPROC32"));
+            assert_eq!(symbol_kind_name(0x1110), Some("S_GPROC32"));
+            assert_eq!(symbol_kind_name(S_END), Some("S_END"));
+            assert_eq!(symbol_kind_name(S_PUB32_ST), Some("S_PUB32_ST"));
+            assert_eq!(
+                symbol_kind_name(S_DEFRANGE_REGISTER_REL_INDIR),
+                Some("S_DEFRANGE_REGISTER_REL_INDIR")
+            );
+            assert_eq!(symbol_kind_name(S_ST_MAX), None);
+            assert_eq!(symbol_kind_name(0xffff), None);
+
+            let symbol = Symbol {
+                data: &[6, 0],
+                index: SymbolIndex(0),
+            };
+            assert_eq!(symbol.kind_name(), Some("S_END"));
+        }
+    }
+
+    mod env_block {
+        use crate::symbol::*;
+
+        #[test]
+        fn test_entries() {
+            let data = b"\x3d\x11\x00cwd\0C:\\build\0cl\0cl.exe\0\0\0\0";
+            let symbol = Symbol {
+                data,
+                index: SymbolIndex(0),
+            };
+
+            let env = match symbol.parse().expect("parse") {
+                SymbolData::EnvBlock(env) => env,
+                other => panic!("unexpected symbol {:?}", other),
+            };
+            assert_eq!(env.rgsz.len(), 7);
+            assert_eq!(
+                env.entries().collect::<Vec<_>>(),
+                vec![("cwd", "C:\\build"), ("cl", "cl.exe")]
+            );
+        }
+
+        #[test]
+        fn test_entries_odd() {
+            let env = EnvBlockSymbol {
+                edit_and_continue: false,
+                rgsz: vec!["cwd".into(), "C:\\".into(), "cmd".into()],
+            };
+            assert_eq!(
+                env.entries().collect::<Vec<_>>(),
+                vec![("cwd", "C:\\"), ("cmd", "")]
+            );
+        }
+    }
+
+    mod compiler_version {
+        use crate::symbol::*;
+
+        fn version(major: u16, minor: u16, build: u16, qfe: Option<u16>) -> CompilerVersion {
+            CompilerVersion {
+                major,
+                minor,
+                build,
+                qfe,
+            }
+        }
+
+        #[test]
+        fn test_ordering() {
+            let older = version(19, 13, 26118, Some(0));
+            let newer = version(19, 20, 0, Some(0));
+            assert!(older < newer);
+            assert!(newer >= version(19, 20, 0, None));
+            assert!(version(19, 20, 0, Some(1)) > version(19, 20, 0, None));
+            assert!(version(19, 0, 0, None) > version(18, 99, 9999, Some(9)));
+            assert_eq!(version(14, 0, 0, None), version(14, 0, 0, Some(0)));
+        }
+
+        #[test]
+        fn test_display() {
+            assert_eq!(version(19, 13, 26118, Some(0)).to_string(), "19.13.26118.0");
+            assert_eq!(version(14, 10, 25019, None).to_string(), "14.10.25019.0");
+        }
+    }
+
+    mod switch_table {
+        use super::records::*;
+        use crate::symbol::*;
+
+        fn label_at(section: u16, offset: u32, name: &str) -> LabelSymbol {
+            LabelSymbol {
+                offset: PdbInternalSectionOffset { offset, section },
+                flags: ProcedureFlags {
+                    nofpo: false,
+                    int: false,
+                    far: false,
+                    never: false,
+                    notreached: false,
+                    cust_call: false,
+                    noinline: false,
+                    optdbginfo: false,
+                },
+                name: name.into(),
+            }
+        }
+
+        #[test]
+        fn test_labels_in_table() {
+            let table = ArmSwitchTableSymbol {
+                offset_base: PdbInternalSectionOffset::new(1, 0x1000),
+                switch_type: JumpTableEntrySize::Int16,
+                offset_branch: PdbInternalSectionOffset::new(1, 0x0ff0),
+                offset_table: PdbInternalSectionOffset::new(1, 0x1000),
+                num_entries: 4,
+            };
+            assert_eq!(table.table_size(8), Some(8));
+
+            let labels = vec![
+                label_at(1, 0x0ffc, "before"),
+                label_at(1, 0x1000, "case_0"),
+                label_at(1, 0x1006, "case_3"),
+                label_at(1, 0x1008, "after"),
+                label_at(2, 0x1002, "other_section"),
+            ];
+
+            let names: Vec<_> = table
+                .labels_in_table(&labels, 8)
+                .into_iter()
+                .map(|label| label.name.as_str())
+                .collect();
+            assert_eq!(names, vec!["case_0", "case_3"]);
+
+            let table = ArmSwitchTableSymbol {
+                switch_type: JumpTableEntrySize::Pointer,
+                num_entries: 1,
+                ..table
+            };
+            assert_eq!(table.labels_in_table(&labels, 4).len(), 1);
+            assert_eq!(table.labels_in_table(&labels, 8).len(), 2);
+
+            let table = ArmSwitchTableSymbol {
+                switch_type: JumpTableEntrySize::Invalid,
+                ..table
+            };
+            assert_eq!(table.table_size(8), None);
+            assert!(table.labels_in_table(&labels, 8).is_empty());
+        }
+
+        #[test]
+        fn test_jump_table_targets() {
+            let address_map = address_map();
+            let mut image = vec![0u8; 0x1100];
+            for (i, value) in [0x10i32, -0x8, 0x100].iter().enumerate() {
+                let pos = 0x1020 + i * 4;
+                image[pos..pos + 4].copy_from_slice(&value.to_le_bytes());
+            }
+            image[0x1040..0x1043].copy_from_slice(&[0x02, 0x80, 0xff]);
+
+            let table = ArmSwitchTableSymbol {
+                offset_base: PdbInternalSectionOffset::new(1, 0),
+                switch_type: JumpTableEntrySize::Int32,
+                offset_branch: PdbInternalSectionOffset::new(1, 0x18),
+                offset_table: PdbInternalSectionOffset::new(1, 0x20),
+                num_entries: 3,
+            };
+            let targets = table.jump_table_targets(&image, &address_map);
+            assert_eq!(
+                targets.expect("targets"),
+                vec![Rva(0x1010), Rva(0x0ff8), Rva(0x1100)]
+            );
+
+            let table = ArmSwitchTableSymbol {
+                switch_type: JumpTableEntrySize::UInt8ShiftLeft,
+                offset_table: PdbInternalSectionOffset::new(1, 0x40),
+                ..table
+            };
+            let targets = table.jump_table_targets(&image, &address_map);
+            assert_eq!(
+                targets.expect("targets"),
+                vec![Rva(0x1004), Rva(0x1100), Rva(0x11fe)]
+            );
+
+            // the table runs past the end of the image
+            let truncated = table.jump_table_targets(&image[..0x1042], &address_map);
+            assert!(matches!(truncated, Err(Error::UnexpectedEof)));
+        }
+
+        #[test]
+        fn test_jump_table_targets_huge_count() {
+            let table = ArmSwitchTableSymbol {
+                offset_base: PdbInternalSectionOffset::new(1, 0),
+                switch_type: JumpTableEntrySize::Int32,
+                offset_branch: PdbInternalSectionOffset::new(1, 0),
+                offset_table: PdbInternalSectionOffset::new(1, 0x20),
+                num_entries: u32::MAX,
+            };
+            let result = table.jump_table_targets(&[0; 0x1100], &address_map());
+            assert!(matches!(result, Err(Error::UnexpectedEof)));
+        }
+
+        #[test]
+        fn test_jump_table_targets_errors() {
+            let address_map = address_map();
+            let image = vec![0u8; 0x1100];
+
+            let table = ArmSwitchTableSymbol {
+                offset_base: PdbInternalSectionOffset::new(1, 0),
+                switch_type: JumpTableEntrySize::Int16,
+                offset_branch: PdbInternalSectionOffset::new(1, 0x18),
+                offset_table: PdbInternalSectionOffset::new(5, 0x20),
+                num_entries: 1,
+            };
+            let result = table.jump_table_targets(&image, &address_map);
+            assert!(matches!(result, Err(Error::UnmappedAddress(5, 0x20))));
+
+            let table = ArmSwitchTableSymbol {
+                switch_type: JumpTableEntrySize::Pointer,
+                offset_table: PdbInternalSectionOffset::new(1, 0x20),
+                ..table
+            };
+            let result = table.jump_table_targets(&image, &address_map);
+            assert!(matches!(result, Err(Error::UnimplementedFeature(_))));
+        }
+    }
+
+    mod constants {
+        use std::convert::TryFrom;
+
+        use crate::symbol::*;
+
+        #[test]
+        fn test_cpu_type_round_trip() {
+            let mut known = 0;
+            for value in 0..=u16::MAX {
+                match CPUType::try_from(value) {
+                    Ok(cpu) => {
+                        assert_eq!(u16::from(cpu), value);
+                        known += 1;
+                    }
+                    Err(Error::UnknownCpuType(v)) => assert_eq!(v, value),
+                    Err(e) => panic!("unexpected error {:?}", e),
+                }
+            }
+
+            assert_eq!(known, 60);
+            // `Unknown` carries a value, so `CPUType::X64 as u16` no longer compiles
+            assert_eq!(u16::from(CPUType::X64), 0xd0);
+            assert_eq!(CPUType::ARM_XMAC.to_string(), "ARM_XMAC");
+            assert_eq!(u16::from(CPUType::Unknown(0x1234)), 0x1234);
+            assert_eq!(CPUType::Unknown(0x1234).to_string(), "Unknown(0x1234)");
+            assert_eq!(
+                CPUType::Unknown(0x1234).instruction_set(),
+                InstructionSet::Other
+            );
+        }
+
+        #[test]
+        fn test_instruction_set() {
+            assert_eq!(CPUType::Pentium3.instruction_set(), InstructionSet::X86);
+            assert_eq!(CPUType::X64.instruction_set(), InstructionSet::X64);
+            assert_eq!(CPUType::ARM64.instruction_set(), InstructionSet::Arm64);
+            assert_eq!(CPUType::ARM7.instruction_set(), InstructionSet::Arm);
+            assert_eq!(CPUType::ARMNT.instruction_set(), InstructionSet::Thumb);
+            assert_eq!(CPUType::MIPS.instruction_set(), InstructionSet::Other);
+
+            assert!(CPUType::Thumb.instruction_set().is_thumb());
+            assert!(!CPUType::ARM5T.instruction_set().is_thumb());
+            assert_eq!(InstructionSet::Thumb.pointer_size(), Some(4));
+            assert_eq!(InstructionSet::X64.pointer_size(), Some(8));
+        }
+
+        #[test]
+        fn test_source_language_round_trip() {
+            let mut known = 0;
+            for value in 0..=u8::MAX {
+                match SourceLanguage::try_from(value) {
+                    Ok(language) => {
+                        assert_eq!(u8::from(language), value);
+                        known += 1;
+                    }
+                    Err(Error::UnknownSourceLanguage(v)) => assert_eq!(v, value),
+                    Err(e) => panic!("unexpected error {:?}", e),
+                }
+            }
+
+            assert_eq!(known, 24);
+            assert_eq!(SourceLanguage::Rust as u8, 0x15);
+        }
+
+        #[test]
+        fn test_cast_variant() {
+            assert_eq!(
+                cast_variant(Variant::U32(0xffff_ffff), PrimitiveKind::I32),
+                Variant::I32(-1)
+            );
+            assert_eq!(
+                cast_variant(Variant::U16(5), PrimitiveKind::Long),
+                Variant::I32(5)
+            );
+            assert_eq!(
+                cast_variant(Variant::I8(-1), PrimitiveKind::UQuad),
+                Variant::U64(u64::MAX)
+            );
+            assert_eq!(
+                cast_variant(Variant::U8(1), PrimitiveKind::Bool8),
+                Variant::U8(1)
+            );
+        }
+
+        #[test]
+        fn test_unknown_values_parse_leniently() {
+            let cpu: CPUType = [0xff, 0xff].pread_with(0, LE).expect("parse");
+            assert_eq!(cpu, CPUType::Unknown(0xffff));
+
+            let cpu: CPUType = [0xd0, 0x00].pread_with(0, LE).expect("parse");
+            assert_eq!(cpu, CPUType::X64);
+
+            let language: SourceLanguage = [0x80].pread_with(0, LE).expect("parse");
+            assert_eq!(language, SourceLanguage::Masm);
+        }
+    }
+
+    mod constant_types {
+        use crate::symbol::*;
+        use crate::tpi::tests::{finder, modifier, type_information};
+
+        fn constant(type_index: u32) -> ConstantSymbol {
+            ConstantSymbol {
+                managed: false,
+                type_index: TypeIndex(type_index),
+                value: Variant::U8(1),
+                name: "VALUE".into(),
+            }
+        }
+
+        #[test]
+        fn test_enum_member_modifier_cycle() {
+            // 0x1000 -> 0x1001 -> 0x1000
+            let types = type_information(&[modifier(0x1001), modifier(0x1000)]);
+            let finder = finder(&types);
+
+            assert_eq!(constant(0x1000).enum_member(&finder).expect("enum"), None);
+            assert_eq!(constant(0x74).enum_member(&finder).expect("int"), None);
+        }
+
+        #[test]
+        fn test_display_value_modifier_cycle() {
+            let types = type_information(&[modifier(0x1000)]);
+            let finder = finder(&types);
+
+            assert_eq!(
+                constant(0x1000).display_value(&finder).expect("display"),
+                "1"
+            );
+        }
+    }
+
+    mod inline_depth {
+        use super::records::*;
+        use crate::symbol::*;
+
+        fn inline_site(parent: u32) -> Vec<u8> {
+            let mut payload = parent.to_le_bytes().to_vec();
+            payload.extend_from_slice(&0u32.to_le_bytes()); // end
+            payload.extend_from_slice(&0x1000u32.to_le_bytes()); // inlinee
+            record(S_INLINESITE, &payload)
+        }
+
+        #[test]
+        fn test_inline_site_depth() {
+            let mut data = vec![0, 0, 0, 0]; // module signature
+            data.extend_from_slice(&[0x06, 0x00, 0x06, 0x00, 0x00, 0x00, 0x00, 0x00]); // S_END at 0x4
+            data.extend(inline_site(0x4)); // 0xc
+            data.extend(inline_site(0xc)); // 0x1c
+            data.extend(inline_site(0x1c)); // 0x2c
+            data.extend(inline_site(0x3c)); // 0x3c, points to itself
+            data.extend(inline_site(0x0)); // 0x4c, without parent
+
+            let symbols = || module_symbols(&data);
+
+            let depth = |index| inline_site_depth(symbols(), SymbolIndex(index)).expect("depth");
+            assert_eq!(depth(0xc), Some(0));
+            assert_eq!(depth(0x1c), Some(1));
+            assert_eq!(depth(0x2c), Some(2));
+            assert_eq!(depth(0x3c), Some(0));
+            assert_eq!(depth(0x4c), Some(0));
+            assert_eq!(depth(0x4), None);
+            assert_eq!(depth(0x5c), None);
+        }
+
+        #[test]
+        fn test_inline_site_depth_through_block() {
+            // main() { inline { block { inline } } }
+            let outer = 4 + procedure(0, 0, "main").len() as u32;
+            let block_index = outer + inline_site(0).len() as u32;
+            let inner = block_index + block(0, 0, "").len() as u32;
+
+            let mut data = vec![0, 0, 0, 0]; // module signature
+            data.extend(procedure(0, 0, "main"));
+            data.extend(inline_site(4));
+            data.extend(block(outer, 0, ""));
+            data.extend(inline_site(block_index));
+
+            let depth = |index| inline_site_depth(module_symbols(&data), SymbolIndex(index));
+            assert_eq!(depth(outer).expect("depth"), Some(0));
+            assert_eq!(depth(inner).expect("depth"), Some(1));
+            assert_eq!(depth(block_index).expect("depth"), None);
+        }
+
+        #[test]
+        fn test_enclosing_procedure() {
+            let mut data = vec![0, 0, 0, 0]; // module signature
+            data.extend(procedure(0, 0, "main")); // 0x4
+            data.extend(inline_site(0x4)); // 0x30
+            data.extend(inline_site(0x30)); // 0x40
+            data.extend(inline_site(0x50)); // 0x50, points to itself
+            data.extend(inline_site(0x0)); // 0x60, without parent
+
+            let symbols = || module_symbols(&data);
+
+            let site = |index| match symbols().skip_to(SymbolIndex(index)) {
+                Ok(Some(symbol)) => match symbol.parse().expect("parse") {
+                    SymbolData::InlineSite(site) => site,
+                    other => panic!("unexpected symbol {:?}", other),
+                },
+                other => panic!("no symbol at {:#x}: {:?}", index, other),
+            };
+
+            let procedure = enclosing_procedure(symbols(), &site(0x40))
+                .expect("enclosing procedure")
+                .expect("procedure");
+            assert_eq!(procedure.name, "main");
+
+            let procedure = enclosing_procedure(symbols(), &site(0x30))
+                .expect("enclosing procedure")
+                .expect("procedure");
+            assert_eq!(procedure.name, "main");
+
+            assert_eq!(
+                enclosing_procedure(symbols(), &site(0x50)).expect("self-referential"),
+                None
+            );
+            assert_eq!(
+                enclosing_procedure(symbols(), &site(0x60)).expect("without parent"),
+                None
+            );
+        }
+    }
+
+    mod frame_variables {
+        use crate::symbol::*;
+
+        fn frame_procedure(flags: [u8; 4]) -> FrameProcedureSymbol {
+            let mut data = vec![
+                18, 16, 152, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0,
+            ];
+            data.extend_from_slice(&flags);
+            data.extend_from_slice(&[0, 0]);
+
+            let symbol = Symbol {
+                data: &data,
+                index: SymbolIndex(0),
+            };
+            match symbol.parse().expect("parse") {
+                SymbolData::FrameProcedure(frame) => frame,
+                other => panic!("unexpected symbol {:?}", other),
+            }
+        }
+
+        fn variable(offset: i32) -> BasePointerRelativeSymbol {
+            let mut data = S_BPREL32.to_le_bytes().to_vec();
+            data.extend_from_slice(&offset.to_le_bytes());
+            data.extend_from_slice(&0x74u32.to_le_bytes());
+            data.extend_from_slice(b"x\0");
+
+            let symbol = Symbol {
+                data: &data,
+                index: SymbolIndex(0),
+            };
+            match symbol.parse().expect("parse") {
+                SymbolData::BasePointerRelative(variable) => variable,
+                other => panic!("unexpected symbol {:?}", other),
+            }
+        }
+
+        #[test]
+        fn test_frame_pointer() {
+            let frame = frame_procedure([48, 160, 2, 0]);
+            assert_eq!(
+                frame.flags.local_base_pointer(),
+                FrameBasePointer::FramePointer
+            );
+            assert_eq!(
+                frame.flags.param_base_pointer(),
+                FrameBasePointer::FramePointer
+            );
+
+            assert_eq!(
+                variable(8).frame_variable(&frame),
+                FrameVariable {
+                    kind: FrameVariableKind::Parameter,
+                    base: FrameBasePointer::FramePointer,
+                    offset: Some(8),
+                }
+            );
+            assert_eq!(
+                variable(-4).frame_variable(&frame),
+                FrameVariable {
+                    kind: FrameVariableKind::Local,
+                    base: FrameBasePointer::FramePointer,
+                    offset: Some(-4),
+                }
+            );
+        }
+
+        #[test]
+        fn test_stack_pointer() {
+            let frame = frame_procedure([48, 96, 2, 0]);
+            assert_eq!(
+                frame.flags.local_base_pointer(),
+                FrameBasePointer::StackPointer
+            );
+
+            assert_eq!(
+                variable(-4).frame_variable(&frame),
+                FrameVariable {
+                    kind: FrameVariableKind::Local,
+                    base: FrameBasePointer::StackPointer,
+                    offset: Some(148),
+                }
+            );
+            assert_eq!(
+                variable(12).frame_variable(&frame).base,
+                FrameBasePointer::FramePointer
+            );
+        }
+    }
+
+    mod next_procedures {
+        use super::records::*;
+        use crate::symbol::*;
+
+        #[test]
+        fn test_chain() {
+            let first = procedure(0, 0, "first");
+            let second = procedure(0, 0, "second");
+            let second_index = 4 + first.len() as u32;
+            let third_index = second_index + second.len() as u32;
+
+            let mut data = vec![0, 0, 0, 0]; // module signature
+            data.extend(procedure(0, second_index, "first"));
+            data.extend(procedure(0, third_index, "second"));
+            data.extend(procedure(0, second_index, "third")); // loops back to second
+
+            let symbols = || module_symbols(&data);
+
+            let start = match symbols().next().expect("next").expect("symbol").parse() {
+                Ok(SymbolData::Procedure(procedure)) => procedure,
+                other => panic!("unexpected symbol {:?}", other),
+            };
+            let names: Vec<_> = start
+                .next_procedures(symbols())
+                .map(|procedure| Ok(procedure.name))
+                .collect()
+                .expect("chain");
+            assert_eq!(names, vec!["second", "third"]);
+
+            let last = ProcedureSymbol {
+                next: None,
+                ..start
+            };
+            assert_eq!(last.next_procedures(symbols()).count().expect("chain"), 0);
+        }
+    }
+
+    mod block_locals {
+        use super::records::*;
+        use crate::symbol::*;
+
+        fn register_relative(name: &str) -> Vec<u8> {
+            let mut payload = vec![8, 0, 0, 0, 0x74, 0, 0, 0, 0x4f, 1]; // offset, type, register
+            payload.extend_from_slice(name.as_bytes());
+            payload.push(0);
+            record(S_REGREL32, &payload)
+        }
+
+        fn symbol_names(symbols: &[Symbol<'_>]) -> Vec<String> {
+            symbols
+                .iter()
+                .map(|symbol| match symbol.parse() {
+                    Ok(SymbolData::Local(data)) => data.name.to_string(),
+                    Ok(SymbolData::RegisterRelative(data)) => data.name.to_string(),
+                    other => panic!("unexpected symbol {:?}", other),
+                })
+                .collect()
+        }
+
+        #[test]
+        fn test_nested_blocks() {
+            // f() { a; block outer { b; block inner { c; } d; } e; }
+            let local = |name| local(name, false);
+            let procedure_len = procedure(0, 0, "f").len() as u32;
+            let outer = 4 + procedure_len + local("a").len() as u32;
+            let b = outer + block(0, 0, "outer").len() as u32;
+            let inner = b + local("b").len() as u32;
+            let c = inner + block(0, 0, "inner").len() as u32;
+            let inner_end = c + local("c").len() as u32;
+            let d = inner_end + 4;
+            let outer_end = d + register_relative("d").len() as u32;
+            let procedure_end = outer_end + 4 + local("e").len() as u32;
+
+            let mut data = vec![0, 0, 0, 0]; // module signature
+            data.extend(procedure(procedure_end, 0, "f"));
+            data.extend(local("a"));
+            data.extend(block(4, outer_end, "outer"));
+            data.extend(local("b"));
+            data.extend(block(outer, inner_end, "inner"));
+            data.extend(local("c"));
+            data.extend(scope_end());
+            data.extend(register_relative("d"));
+            data.extend(scope_end());
+            data.extend(local("e"));
+            data.extend(scope_end());
+
+            let symbols = || module_symbols(&data);
+
+            let parse_block = |index: u32| match symbols().skip_to(SymbolIndex(index)) {
+                Ok(Some(symbol)) => match symbol.parse() {
+                    Ok(SymbolData::Block(block)) => block,
+                    other => panic!("unexpected symbol {:?}", other),
+                },
+                other => panic!("unexpected symbol {:?}", other),
+            };
+
+            let outer_block = parse_block(outer);
+            let locals = collect_block_locals(symbols(), &outer_block).expect("locals");
+            assert_eq!(symbol_names(&locals), vec!["b", "d"]);
+            assert_eq!(locals[0].index(), SymbolIndex(b));
+
+            let inner_block = parse_block(inner);
+            let locals = collect_block_locals(symbols(), &inner_block).expect("locals");
+            assert_eq!(symbol_names(&locals), vec!["c"]);
+            assert_eq!(locals[0].index(), SymbolIndex(c));
+        }
+
+        #[test]
+        fn test_missing_block() {
+            let mut data = vec![0, 0, 0, 0];
+            data.extend(local("a", false));
+
+            let block = BlockSymbol {
+                parent: SymbolIndex(4),
+                end: SymbolIndex(data.len() as u32),
+                len: 0,
+                offset: PdbInternalSectionOffset::default(),
+                name: "missing".into(),
+            };
+
+            let locals = collect_block_locals(module_symbols(&data), &block).expect("locals");
+            assert!(locals.is_empty());
+        }
+    }
+
+    mod procedure_parameters {
+        use super::records::*;
+        use crate::symbol::*;
+
+        fn parse_procedure(mut symbols: SymbolIter<'_>) -> ProcedureSymbol {
+            match symbols.next().expect("next").map(|symbol| symbol.parse()) {
+                Some(Ok(SymbolData::Procedure(procedure))) => procedure,
+                other => panic!("unexpected symbol {:?}", other),
+            }
+        }
+
+        #[test]
+        fn test_parameter_order() {
+            // f(a, b) { c; d; block { e; } }
+            let parameters = [local("a", true), local("b", true)];
+            let rest = [local("c", false), local("d", true)];
+            let body_len: usize = parameters.iter().chain(&rest).map(Vec::len).sum();
+            let block_start = 4 + procedure(0, 0, "f").len() + body_len;
+            let block_end = block_start + block(0, 0, "").len() + local("e", true).len();
+            let procedure_end = block_end + 4;
+
+            let mut data = vec![0, 0, 0, 0]; // module signature
+            data.extend(procedure(procedure_end as u32, 0, "f"));
+            parameters.iter().chain(&rest).for_each(|l| data.extend(l));
+            data.extend(block(4, block_end as u32, ""));
+            data.extend(local("e", true));
+            data.extend(scope_end());
+            data.extend(scope_end());
+
+            let symbols = || module_symbols(&data);
+
+            let symbol = parse_procedure(symbols());
+            let parameters = procedure_parameters(symbols(), &symbol).expect("parameters");
+            let names: Vec<_> = parameters
+                .iter()
+                .map(|symbol| match symbol.parse() {
+                    Ok(SymbolData::Local(data)) => data.name.to_string(),
+                    other => panic!("unexpected symbol {:?}", other),
+                })
+                .collect();
+            assert_eq!(names, vec!["a", "b"]);
+            assert_eq!(
+                parameters[0].index(),
+                SymbolIndex(4 + procedure(0, 0, "f").len() as u32)
+            );
+        }
+    }
+
+    mod separated_code {
+        use super::records::*;
+        use crate::symbol::*;
+
+        fn separated_code(parent: u32, end: u32, offset: u32, parent_offset: u32) -> Vec<u8> {
+            let mut payload = parent.to_le_bytes().to_vec();
+            payload.extend_from_slice(&end.to_le_bytes());
+            payload.extend_from_slice(&[0x10, 0, 0, 0, 0, 0, 0, 0]); // len, flags
+            payload.extend_from_slice(&offset.to_le_bytes());
+            payload.extend_from_slice(&parent_offset.to_le_bytes());
+            payload.extend_from_slice(&[1, 0, 1, 0]); // sections
+            record(S_SEPCODE, &payload)
+        }
+
+        #[test]
+        fn test_parent_procedure() {
+            // hot() { block { sepcode { } } sepcode { } }
+            let end = scope_end();
+            let block_index = 4 + procedure_at(0, 0, "hot").len();
+            let nested_index = block_index + block(0, 0, "").len();
+            let block_end = nested_index + separated_code(0, 0, 0, 0).len() + end.len();
+            let cold_index = block_end + end.len();
+            let procedure_end = cold_index + separated_code(0, 0, 0, 0).len() + end.len();
+
+            let mut data = vec![0, 0, 0, 0]; // module signature
+            data.extend(procedure_at(procedure_end as u32, 0x100, "hot"));
+            data.extend(block(4, block_end as u32, ""));
+            data.extend(separated_code(
+                block_index as u32,
+                (block_end - end.len()) as u32,
+                0x900,
+                0x100,
+            ));
+            data.extend(&end);
+            data.extend(&end);
+            data.extend(separated_code(
+                4,
+                (procedure_end - end.len()) as u32,
+                0x800,
+                0x100,
+            ));
+            data.extend(&end);
+            data.extend(&end);
+
+            let symbols = || module_symbols(&data);
+
+            for index in [nested_index, cold_index] {
+                let symbol = symbols().skip_to(SymbolIndex(index as u32));
+                let code = match symbol.expect("skip").map(|symbol| symbol.parse()) {
+                    Some(Ok(SymbolData::SeparatedCode(code))) => code,
+                    other => panic!("unexpected symbol {:?}", other),
+                };
+
+                let parent = code.parent_procedure(symbols()).expect("parent");
+                let parent = parent.expect("parent procedure");
+                assert_eq!(parent.name, "hot");
+                assert_eq!(parent.offset, code.parent_offset);
+            }
+
+            let orphan = SeparatedCodeSymbol {
+                parent: SymbolIndex(0),
+                end: SymbolIndex(0),
+                len: 0,
+                flags: SeparatedCodeFlags {
+                    islexicalscope: false,
+                    returnstoparent: false,
+                },
+                offset: PdbInternalSectionOffset::default(),
+                parent_offset: PdbInternalSectionOffset::default(),
+            };
+            assert_eq!(orphan.parent_procedure(symbols()).expect("parent"), None);
+        }
+    }
+
+    mod using_namespaces {
+        use super::records::*;
+        use crate::symbol::*;
+
+        fn using(name: &str) -> Vec<u8> {
+            let mut payload = name.as_bytes().to_vec();
+            payload.push(0);
+            record(S_UNAMESPACE, &payload)
+        }
+
+        #[test]
+        fn test_accumulation() {
+            // using namespace outer; f() { using namespace std; { using namespace chrono; } }
+            let procedure_index = 4 + using("outer").len();
+            let block_index = procedure_index + procedure(0, 0, "f").len() + using("std").len();
+
+            let mut data = vec![0, 0, 0, 0]; // module signature
+            data.extend(using("outer"));
+            data.extend(procedure(0, 0, "f"));
+            data.extend(using("std"));
+            data.extend(block(0, 0, ""));
+            data.extend(using("chrono"));
+            data.extend(scope_end());
+            data.extend(scope_end());
+
+            let namespaces = scope_using_namespaces(module_symbols(&data)).expect("namespaces");
+
+            assert_eq!(namespaces.len(), 2);
+            assert_eq!(
+                namespaces[&SymbolIndex(procedure_index as u32)],
+                vec!["outer", "std"]
+            );
+            assert_eq!(
+                namespaces[&SymbolIndex(block_index as u32)],
+                vec!["outer", "std", "chrono"]
+            );
+        }
+    }
+
+    mod scope_depth {
+        use super::records::*;
+        use crate::symbol::*;
+
+        #[test]
+        fn test_depth() {
+            // f() { x; { x; } } x;
+            let mut data = vec![0, 0, 0, 0]; // module signature
+            data.extend(procedure(0, 0, "f"));
+            data.extend(local("x", false));
+            data.extend(block(0, 0, ""));
+            data.extend(local("x", false));
+            data.extend(scope_end());
+            data.extend(scope_end());
+            data.extend(local("x", false));
+            data.extend(scope_end()); // unbalanced
+
+            let symbols: Vec<_> = module_symbols(&data)
+                .with_depth()
+                .map(|(depth, symbol)| Ok((depth, symbol.raw_kind())))
+                .collect()
+                .expect("collect");
+
+            let expected = [
+                (0, S_GPROC32),
+                (1, S_LOCAL),
+                (1, S_BLOCK32),
+                (2, S_LOCAL),
+                (1, S_END),
+                (0, S_END),
+                (0, S_LOCAL),
+                (0, S_END),
+            ];
+            assert_eq!(symbols, expected);
+        }
+    }
+
+    mod rva_map {
+        use super::records::*;
+        use crate::symbol::*;
+
+        #[test]
+        fn test_procedure_over_public() {
+            let address_map = address_map();
+            let procedure = procedure_at(0, 0x10, "f");
+            let public = public(0x10, "_f");
+
+            for records in [[procedure.clone(), public.clone()], [public, procedure]] {
+                let table = SymbolTable::new(Stream::from_bytes(records.concat()));
+                let map = table.to_rva_map(&address_map).expect("rva map");
+                assert_eq!(map.len(), 1);
+                assert_eq!(map[&Rva(0x1010)].name(), Some("f"));
+                assert_eq!(map[&Rva(0x1010)].kind(), SymbolKindTag::Procedure);
+            }
+        }
+
+        #[test]
+        fn test_procedure_reference_over_public() {
+            let address_map = address_map();
+
+            // the global table refers to the procedure in the module at symbol index 4
+            let mut payload = vec![0; 4]; // sum_name
+            payload.extend_from_slice(&4u32.to_le_bytes());
+            payload.extend_from_slice(&1u16.to_le_bytes()); // module
+            payload.extend_from_slice(b"f\0");
+            let reference = record(S_PROCREF, &payload);
+            let public = public(0x10, "_f");
+
+            let mut module = vec![0, 0, 0, 0]; // module signature
+            module.extend(procedure_at(0, 0x10, "f"));
+
+            for records in [[reference.clone(), public.clone()], [public, reference]] {
+                let table = SymbolTable::new(Stream::from_bytes(records.concat()));
+
+                let map = table.to_rva_map(&address_map).expect("rva map");
+                assert_eq!(map[&Rva(0x1010)].kind(), SymbolKindTag::Public);
+
+                let map = table
+                    .to_rva_map_with(&address_map, |reference| {
+                        assert_eq!(reference.module, Some(0));
+                        let mut symbols = module_symbols(&module);
+                        let symbol = symbols.skip_to(reference.symbol_index)?;
+                        Ok(symbol.map(|symbol| symbol.parse_lenient()))
+                    })
+                    .expect("rva map");
+                assert_eq!(map.len(), 1);
+                assert_eq!(map[&Rva(0x1010)].name(), Some("f"));
+                assert_eq!(map[&Rva(0x1010)].kind(), SymbolKindTag::Procedure);
+            }
+        }
+    }
+
+    mod cycle_guard {
+        use super::records::*;
+        use crate::symbol::*;
+
+        fn next_index(symbol: &Symbol<'_>) -> SymbolIndex {
+            match symbol.parse() {
+                Ok(SymbolData::Procedure(ProcedureSymbol {
+                    next: Some(next), ..
+                })) => next,
+                other => panic!("unexpected symbol {:?}", other),
+            }
+        }
+
+        #[test]
+        fn test_cycle() {
+            // two procedures whose `next` fields point at each other
+            let second = 4 + procedure(0, 0, "f").len() as u32;
+            let mut data = vec![0, 0, 0, 0]; // module signature
+            data.extend(procedure(0, second, "f"));
+            data.extend(procedure(0, 4, "f"));
+
+            let mut symbols = module_symbols(&data).with_cycle_guard();
+
+            let mut symbol = symbols.next().expect("next").expect("symbol");
+            let mut hops = 0;
+            let error = loop {
+                match symbols.skip_to(next_index(&symbol)) {
+                    Ok(next) => symbol = next.expect("symbol"),
+                    Err(error) => break error,
+                }
+                hops += 1;
+            };
+
+            // the first procedure is only reached via `next`, so the guard trips on the second
+            // visit of the second procedure
+            assert_eq!(hops, 2);
+            assert!(matches!(error, Error::SymbolCycle(index) if index == second));
+        }
+
+        #[test]
+        fn test_without_guard() {
+            let mut data = vec![0, 0, 0, 0];
+            data.extend(procedure(0, 4, "f"));
+
+            let mut symbols = module_symbols(&data);
+            for _ in 0..3 {
+                let symbol = symbols.skip_to(SymbolIndex(4)).expect("skip");
+                assert!(symbol.is_some());
+            }
+        }
+    }
+
+    mod heap_size {
+        use crate::symbol::*;
+
+        #[test]
+        fn test_named_symbol() {
+            let data = &[1, 17, 0, 0, 0, 0, 42, 32, 67, 73, 76, 32, 42, 0];
+            let symbol = Symbol {
+                data,
+                index: SymbolIndex(0),
+            };
+
+            let parsed = symbol.parse().expect("parse");
+            assert_eq!(parsed.heap_size(), "* CIL *".len());
+            assert_eq!(
+                mem::size_of_val(&parsed) + parsed.heap_size(),
+                mem::size_of::<SymbolData>() + 7
+            );
+        }
+
+        #[test]
+        fn test_string_list() {
+            let env = SymbolData::EnvBlock(EnvBlockSymbol {
+                edit_and_continue: false,
+                rgsz: vec!["cwd".into(), "C:\\".into()],
+            });
+            assert_eq!(env.heap_size(), 2 * mem::size_of::<String>() + 3 + 3);
+        }
+
+        #[test]
+        fn test_without_heap() {
+            assert_eq!(SymbolData::ScopeEnd.heap_size(), 0);
+        }
+    }
+
+    mod kind_tag {
+        use crate::symbol::*;
+
+        #[test]
+        fn test_public() {
+            let data = &[
+                14, 17, 2, 0, 0, 0, 192, 85, 0, 0, 1, 0, 95, 95, 108, 111, 99, 97, 108, 95, 115,
+                116, 100, 105, 111, 95, 112, 114, 105, 110, 116, 102, 95, 111, 112, 116, 105, 111,
+                110, 115, 0, 0,
+            ];
+            let symbol = Symbol {
+                data,
+                index: SymbolIndex(0),
+            };
+
+            let parsed = symbol.parse().expect("parse");
+            assert_eq!(parsed.kind(), SymbolKindTag::Public);
+            assert_eq!(SymbolData::ScopeEnd.kind(), SymbolKindTag::ScopeEnd);
+        }
+    }
+
+    mod equality {
+        use crate::symbol::*;
+
+        #[test]
+        fn test_data_eq() {
+            let data = &[1, 17, 0, 0, 0, 0, 42, 32, 67, 73, 76, 32, 42, 0];
+            let first = Symbol {
+                data,
+                index: SymbolIndex(0x4),
+            };
+            let second = Symbol {
+                data: &data.clone(),
+                index: SymbolIndex(0x40),
+            };
+
+            assert!(first != second);
+            assert!(first.data_eq(&second));
+            assert!(!first.data_eq(&Symbol {
+                data: &[6, 0],
+                index: SymbolIndex(0x4),
+            }));
+        }
+    }
+
+    mod iterator {
+        use crate::symbol::*;
+
+        fn create_iter() -> SymbolIter<'static> {
+            let data = &[
+                0x00, 0x00, 0x00, 0x00, // module signature (padding)
+                0x02, 0x00, 0x4e, 0x11, // S_INLINESITE_END
+                0x02, 0x00, 0x06, 0x00, // S_END
+            ];
+
+            let mut buf = ParseBuffer::from(&data[..]);
+            buf.seek(4); // skip the module signature
+            SymbolIter::new(buf)
+        }
+
+        #[test]
+        fn test_iter() {
+            let symbols: Vec<_> = create_iter().collect().expect("collect");
+
+            let expected = [
+                Symbol {
+                    index: SymbolIndex(0x4),
+                    data: &[0x4e, 0x11], // S_INLINESITE_END
+                },
+                Symbol {
+                    index: SymbolIndex(0x8),
+                    data: &[0x06, 0x00], // S_END
+                },
+            ];
+
+            assert_eq!(symbols, expected);
+        }
+
+        #[test]
+        fn test_seek() {
+            let mut symbols = create_iter();
+            symbols.seek(SymbolIndex(0x8));
+
+            let symbol = symbols.next().expect("get symbol");
+            let expected = Symbol {
+                index: SymbolIndex(0x8),
+                data: &[0x06, 0x00], // S_END
+            };
+
+            assert_eq!(symbol, Some(expected));
+        }
+
+        #[test]
+        fn test_position() {
+            let mut symbols = create_iter();
+            assert_eq!(symbols.position(), SymbolIndex(0x4));
+
+            let mut position = symbols.position();
+            while let Some(symbol) = symbols.next().expect("get symbol") {
+                assert_eq!(symbol.index(), position);
+                let record_len = symbol.raw_bytes().len() as u32;
+                assert_eq!(symbols.position(), SymbolIndex(position.0 + 2 + record_len));
+                position = symbols.position();
+            }
+
+            assert_eq!(symbols.position(), SymbolIndex(0xc));
         }
 
         #[test]
@@ -7,13 +7,17 @@
 
 #![allow(missing_docs)]
 
+use std::fmt;
+
 use crate::common::*;
 use crate::tpi::constants::*;
 use crate::tpi::primitive::*;
+use crate::tpi::{type_size, TypeFinder};
 
 /// Encapsulates parsed data about a `Type`.
 #[non_exhaustive]
 #[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
 pub enum TypeData {
     Primitive(PrimitiveType),
     Class(ClassType),
@@ -61,6 +65,82 @@ impl TypeData {
 
         Some(name)
     }
+
+    /// Returns the indices of all types referenced directly by this type.
+    ///
+    /// For field lists, this includes the types referenced by each field and the continuation.
+    /// Referenced types are not resolved recursively, see
+    /// [`TypeInformation::dependencies`](crate::ItemInformation::dependencies) for that.
+    #[must_use]
+    pub fn referenced_types(&self) -> Vec<TypeIndex> {
+        let mut types = Vec::new();
+        self.collect_referenced_types(&mut types);
+        types
+    }
+
+    fn collect_referenced_types(&self, types: &mut Vec<TypeIndex>) {
+        match self {
+            Self::Primitive(_) => (),
+            Self::Class(data) => {
+                types.extend(data.fields);
+                types.extend(data.derived_from);
+                types.extend(data.vtable_shape);
+            }
+            Self::Member(data) => types.push(data.field_type),
+            Self::MemberFunction(data) => {
+                types.push(data.return_type);
+                types.push(data.class_type);
+                types.extend(data.this_pointer_type);
+                types.push(data.argument_list);
+            }
+            Self::OverloadedMethod(data) => types.push(data.method_list),
+            Self::Method(data) => types.push(data.method_type),
+            Self::StaticMember(data) => types.push(data.field_type),
+            Self::Nested(data) => types.push(data.nested_type),
+            Self::BaseClass(data) => types.push(data.base_class),
+            Self::VirtualBaseClass(data) => {
+                types.push(data.base_class);
+                types.push(data.base_pointer);
+            }
+            Self::VirtualFunctionTable(data) => {
+                types.push(data.owner);
+                types.push(data.base);
+            }
+            Self::VirtualTableShape(_) => (),
+            Self::VirtualFunctionTablePointer(data) => types.push(data.table),
+            Self::Procedure(data) => {
+                types.extend(data.return_type);
+                types.push(data.argument_list);
+            }
+            Self::Pointer(data) => {
+                types.push(data.underlying_type);
+                types.extend(data.containing_class);
+            }
+            Self::Modifier(data) => types.push(data.underlying_type),
+            Self::Enumeration(data) => {
+                types.push(data.underlying_type);
+                types.push(data.fields);
+            }
+            Self::Enumerate(_) => (),
+            Self::Array(data) => {
+                types.push(data.element_type);
+                types.push(data.indexing_type);
+            }
+            Self::Union(data) => types.push(data.fields),
+            Self::Alias(data) => types.push(data.underlying_type),
+            Self::Bitfield(data) => types.push(data.underlying_type),
+            Self::FieldList(data) => {
+                for field in &data.fields {
+                    field.collect_referenced_types(types);
+                }
+                types.extend(data.continuation);
+            }
+            Self::ArgumentList(data) => types.extend(&data.arguments),
+            Self::MethodList(data) => {
+                types.extend(data.methods.iter().map(|method| method.method_type));
+            }
+        }
+    }
 }
 
 /// Parse a type out of a `ParseBuffer`.
@@ -291,33 +371,16 @@ pub(crate) fn parse_type_data<'t>(buf: &mut ParseBuffer<'t>) -> Result<TypeData>
                 None
             };
 
-            let mut dimensions: Vec<u32> = Vec::new();
-
-            loop {
-                let dim = parse_unsigned(buf)?;
-                if dim > u64::from(u32::MAX) {
-                    return Err(Error::UnimplementedFeature("u64 array sizes"));
-                }
-                dimensions.push(dim as u32);
-
-                if buf.is_empty() {
-                    // shouldn't run out here
-                    return Err(Error::UnexpectedEof);
-                }
-
-                if buf.peek_u8()? == 0x00 {
-                    // end of dimensions
-                    buf.parse_u8()?;
-                    break;
-                }
+            let size = parse_unsigned(buf)?;
+            if size > u64::from(u32::MAX) {
+                return Err(Error::UnimplementedFeature("u64 array sizes"));
             }
 
+            let name = parse_string(leaf, buf)?;
+
             // eat any padding
             parse_padding(buf)?;
 
-            //println!("array: {:x}", buf);
-            //println!("dimensions: {:?}", dimensions);
-
             // Vector35/binaryninja-api#6076: NativeAOT PDBs have extra data here
             // of unknown purpose
             //assert!(buf.is_empty());
@@ -326,7 +389,12 @@ pub(crate) fn parse_type_data<'t>(buf: &mut ParseBuffer<'t>) -> Result<TypeData>
                 element_type,
                 indexing_type,
                 stride,
-                dimensions,
+                dimensions: vec![size as u32],
+                name: if name.is_empty() {
+                    None
+                } else {
+                    Some(name.to_string().into_owned())
+                },
             }))
         }
 
@@ -386,7 +454,7 @@ pub(crate) fn parse_type_data<'t>(buf: &mut ParseBuffer<'t>) -> Result<TypeData>
             };
             let count = buf.parse_u16()? as usize;
             // These are packed 4-bit values
-            for _ in 0..((count + 1) / 2) {
+            for _ in 0..count.div_ceil(2) {
                 let desc: u8 = buf.parse()?;
 
                 vtshape
@@ -580,6 +648,7 @@ unsigned short  mocom       :2;     // CV_MOCOM_UDT_e
 } CV_prop_t;
 */
 #[derive(Debug, Copy, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
 pub struct TypeProperties(u16);
 impl TypeProperties {
     /// Indicates if a type is packed via `#pragma pack` or similar.
@@ -687,6 +756,7 @@ typedef enum CV_methodprop_e {
 
 */
 #[derive(Debug, Copy, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
 pub struct FieldAttributes(u16);
 impl FieldAttributes {
     #[inline]
@@ -778,6 +848,7 @@ typedef struct CV_funcattr_t {
 } CV_funcattr_t;
 */
 #[derive(Debug, Copy, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
 pub struct FunctionAttributes(u16);
 impl FunctionAttributes {
     #[must_use]
@@ -801,6 +872,139 @@ impl FunctionAttributes {
     }
 }
 
+// https://github.com/Microsoft/microsoft-pdb/blob/082c5290e5aff028ae84e43affa8be717aa7af73/include/cvinfo.h#L1004-L1035
+/// The calling convention of a function type, decoded from `CV_call_e`.
+#[non_exhaustive]
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum CallingConvention {
+    /// Near right to left push, caller pops stack (`__cdecl`).
+    NearC,
+    /// Far right to left push, caller pops stack.
+    FarC,
+    /// Near left to right push, callee pops stack (`__pascal`).
+    NearPascal,
+    /// Far left to right push, callee pops stack.
+    FarPascal,
+    /// Near left to right push with regs, callee pops stack (`__fastcall`).
+    NearFast,
+    /// Far left to right push with regs, callee pops stack.
+    FarFast,
+    /// Skipped (unused) call index.
+    Skipped,
+    /// Near standard call (`__stdcall`).
+    NearStd,
+    /// Far standard call.
+    FarStd,
+    /// Near sys call (`__syscall`).
+    NearSys,
+    /// Far sys call.
+    FarSys,
+    /// This call, with `this` passed in a register (`__thiscall`).
+    ThisCall,
+    /// MIPS call.
+    MipsCall,
+    /// Generic call sequence.
+    Generic,
+    /// Alpha call.
+    AlphaCall,
+    /// PowerPC call.
+    PpcCall,
+    /// Hitachi SuperH call.
+    ShCall,
+    /// ARM call.
+    ArmCall,
+    /// AM33 call.
+    Am33Call,
+    /// TriCore call.
+    TriCall,
+    /// Hitachi SuperH-5 call.
+    Sh5Call,
+    /// M32R call.
+    M32rCall,
+    /// CLR call.
+    ClrCall,
+    /// Marker for routines always inlined and thus lacking a convention.
+    Inline,
+    /// Near left to right push with regs, callee pops stack (`__vectorcall`).
+    NearVector,
+    /// Swift calling convention.
+    Swift,
+    /// An unknown calling convention.
+    Unknown(u8),
+}
+
+impl From<u8> for CallingConvention {
+    fn from(value: u8) -> Self {
+        match value {
+            0x00 => Self::NearC,
+            0x01 => Self::FarC,
+            0x02 => Self::NearPascal,
+            0x03 => Self::FarPascal,
+            0x04 => Self::NearFast,
+            0x05 => Self::FarFast,
+            0x06 => Self::Skipped,
+            0x07 => Self::NearStd,
+            0x08 => Self::FarStd,
+            0x09 => Self::NearSys,
+            0x0a => Self::FarSys,
+            0x0b => Self::ThisCall,
+            0x0c => Self::MipsCall,
+            0x0d => Self::Generic,
+            0x0e => Self::AlphaCall,
+            0x0f => Self::PpcCall,
+            0x10 => Self::ShCall,
+            0x11 => Self::ArmCall,
+            0x12 => Self::Am33Call,
+            0x13 => Self::TriCall,
+            0x14 => Self::Sh5Call,
+            0x15 => Self::M32rCall,
+            0x16 => Self::ClrCall,
+            0x17 => Self::Inline,
+            0x18 => Self::NearVector,
+            0x19 => Self::Swift,
+            other => Self::Unknown(other),
+        }
+    }
+}
+
+impl fmt::Display for CallingConvention {
+    /// Formats the calling convention as the keyword used in C/C++ declarations, such as
+    /// `__stdcall`. Conventions without a keyword are formatted descriptively.
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let str_repr = match self {
+            Self::NearC => "__cdecl",
+            Self::FarC => "__far __cdecl",
+            Self::NearPascal => "__pascal",
+            Self::FarPascal => "__far __pascal",
+            Self::NearFast => "__fastcall",
+            Self::FarFast => "__far __fastcall",
+            Self::Skipped => "skipped",
+            Self::NearStd => "__stdcall",
+            Self::FarStd => "__far __stdcall",
+            Self::NearSys => "__syscall",
+            Self::FarSys => "__far __syscall",
+            Self::ThisCall => "__thiscall",
+            Self::MipsCall => "mips",
+            Self::Generic => "generic",
+            Self::AlphaCall => "alpha",
+            Self::PpcCall => "ppc",
+            Self::ShCall => "sh",
+            Self::ArmCall => "arm",
+            Self::Am33Call => "am33",
+            Self::TriCall => "tricore",
+            Self::Sh5Call => "sh5",
+            Self::M32rCall => "m32r",
+            Self::ClrCall => "__clrcall",
+            Self::Inline => "inline",
+            Self::NearVector => "__vectorcall",
+            Self::Swift => "swift",
+            Self::Unknown(value) => return write!(f, "unknown({value:#04x})"),
+        };
+
+        write!(f, "{str_repr}")
+    }
+}
+
 /// The kind of a `PointerType`.
 #[derive(Clone, Copy, Debug, PartialEq, Eq)]
 pub enum PointerKind {
@@ -865,6 +1069,7 @@ struct lfPointerAttr {
 */
 
 #[derive(Clone, Copy, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
 pub struct PointerAttributes(u32);
 
 impl PointerAttributes {
@@ -987,6 +1192,7 @@ typedef enum CV_VTS_desc_e {
 #[allow(unused)]
 #[repr(u8)]
 #[derive(Copy, Clone, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
 pub enum VirtualTableShapeDescriptor {
     Near = 0x00,
     Far = 0x01,
@@ -1021,6 +1227,7 @@ impl VirtualTableShapeDescriptor {
 /// `LF_CLASS`, `LF_CLASS_ST`, `LF_STRUCTURE`, `LF_STRUCTURE_ST` or `LF_INTERFACE`.
 // https://github.com/Microsoft/microsoft-pdb/blob/082c5290e5aff028ae84e43affa8be717aa7af73/include/cvinfo.h#L1631
 #[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
 pub struct ClassType {
     pub kind: ClassKind,
 
@@ -1048,6 +1255,7 @@ pub struct ClassType {
 
 /// Used by `ClassType` to distinguish class-like concepts.
 #[derive(Debug, Copy, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
 pub enum ClassKind {
     Class,
     Struct,
@@ -1056,6 +1264,7 @@ pub enum ClassKind {
 
 /// The information parsed from a type record with kind `LF_MEMBER` or `LF_MEMBER_ST`.
 #[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
 pub struct MemberType {
     pub attributes: FieldAttributes,
     pub field_type: TypeIndex,
@@ -1063,8 +1272,26 @@ pub struct MemberType {
     pub name: String,
 }
 
+impl MemberType {
+    /// Returns the bit layout of this member if it is a bitfield.
+    ///
+    /// Bitfield members refer to an `LF_BITFIELD` type, which holds the underlying type along with
+    /// the position and length of the bits within the storage unit at [`offset`](Self::offset).
+    ///
+    /// # Errors
+    ///
+    /// * `Error::TypeNotFound` or `Error::TypeNotIndexed` if the field type cannot be found
+    pub fn bitfield(&self, type_finder: &TypeFinder<'_>) -> Result<Option<BitfieldType>> {
+        match type_finder.find(self.field_type)?.parse()? {
+            TypeData::Bitfield(bitfield) => Ok(Some(bitfield)),
+            _ => Ok(None),
+        }
+    }
+}
+
 /// The information parsed from a type record with kind `LF_MFUNCTION`.
 #[derive(Debug, Copy, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
 pub struct MemberFunctionType {
     pub return_type: TypeIndex,
     pub class_type: TypeIndex,
@@ -1075,8 +1302,17 @@ pub struct MemberFunctionType {
     pub this_adjustment: u32,
 }
 
+impl MemberFunctionType {
+    /// Returns the calling convention of this member function.
+    #[must_use]
+    pub fn calling_convention(&self) -> CallingConvention {
+        CallingConvention::from(self.attributes.calling_convention())
+    }
+}
+
 /// The information parsed from a type record with kind `LF_METHOD` or `LF_METHOD_ST`.
 #[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
 pub struct OverloadedMethodType {
     pub count: u16,
     pub method_list: TypeIndex,
@@ -1085,6 +1321,7 @@ pub struct OverloadedMethodType {
 
 /// The information parsed from a type record with kind `LF_ONEMETHOD` or `LF_ONEMETHOD_ST`.
 #[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
 pub struct MethodType {
     pub attributes: FieldAttributes,
     pub method_type: TypeIndex,
@@ -1094,6 +1331,7 @@ pub struct MethodType {
 
 /// The information parsed from a type record with kind `LF_STMEMBER` or `LF_STMEMBER_ST`.
 #[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
 pub struct StaticMemberType {
     pub attributes: FieldAttributes,
     pub field_type: TypeIndex,
@@ -1103,6 +1341,7 @@ pub struct StaticMemberType {
 /// The information parsed from a type record with kind
 /// `LF_NESTTYPE`, `LF_NESTTYPE_ST`, `LF_NESTTYPEEX`, or `LF_NESTTYPEEX_ST`.
 #[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
 pub struct NestedType {
     pub attributes: FieldAttributes,
     pub nested_type: TypeIndex,
@@ -1111,6 +1350,7 @@ pub struct NestedType {
 
 /// The information parsed from a type record with kind `LF_BCLASS` or `LF_BINTERFACE`.
 #[derive(Debug, Copy, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
 pub struct BaseClassType {
     pub kind: ClassKind,
     pub attributes: FieldAttributes,
@@ -1122,30 +1362,96 @@ pub struct BaseClassType {
 
 /// The information parsed from a type record with kind `LF_VBCLASS` or `LF_IVBCLASS`.
 #[derive(Debug, Copy, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
 pub struct VirtualBaseClassType {
+    /// Whether this is a direct virtual base (`LF_VBCLASS`) as opposed to an indirect one
+    /// (`LF_IVBCLASS`) inherited through another base.
     pub direct: bool,
     pub attributes: FieldAttributes,
     pub base_class: TypeIndex,
+    /// The type of the virtual base pointer.
     pub base_pointer: TypeIndex,
 
+    /// Offset of the virtual base pointer from the address point of the class.
     pub base_pointer_offset: u32,
+    /// Index of the displacement of this base within the virtual base table.
     pub virtual_base_offset: u32,
 }
 
+/// A base class of a class, either at a fixed offset or virtual.
+///
+/// Non-virtual bases are located at a fixed offset within the derived class. The location of
+/// virtual bases is only known at runtime: the virtual base pointer at
+/// [`base_pointer_offset`](VirtualBaseClassType::base_pointer_offset) points to a table of
+/// displacements, and the entry at
+/// [`virtual_base_offset`](VirtualBaseClassType::virtual_base_offset) holds the displacement of
+/// the base relative to the virtual base pointer.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum BaseClass {
+    /// A non-virtual base class, from `LF_BCLASS` or `LF_BINTERFACE`.
+    Direct(BaseClassType),
+    /// A virtual base class, from `LF_VBCLASS` or `LF_IVBCLASS`.
+    Virtual(VirtualBaseClassType),
+}
+
+impl BaseClass {
+    /// Returns the base class described by a field, or `None` if the field is not a base class.
+    #[must_use]
+    pub fn from_field(field: &TypeData) -> Option<Self> {
+        match *field {
+            TypeData::BaseClass(data) => Some(Self::Direct(data)),
+            TypeData::VirtualBaseClass(data) => Some(Self::Virtual(data)),
+            _ => None,
+        }
+    }
+
+    /// The type of the base class.
+    #[must_use]
+    pub fn base_class(&self) -> TypeIndex {
+        match self {
+            Self::Direct(data) => data.base_class,
+            Self::Virtual(data) => data.base_class,
+        }
+    }
+
+    /// The attributes of the base class, including its access.
+    #[must_use]
+    pub fn attributes(&self) -> FieldAttributes {
+        match self {
+            Self::Direct(data) => data.attributes,
+            Self::Virtual(data) => data.attributes,
+        }
+    }
+
+    /// The offset of the base class subobject within the derived class.
+    ///
+    /// Returns `None` for virtual bases, whose offset must be read from the virtual base table.
+    #[must_use]
+    pub fn offset(&self) -> Option<u32> {
+        match self {
+            Self::Direct(data) => Some(data.offset),
+            Self::Virtual(_) => None,
+        }
+    }
+}
+
 /// The information parsed from a type record with kind `LF_VFUNCTAB`.
 #[derive(Debug, Copy, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
 pub struct VirtualFunctionTablePointerType {
     pub table: TypeIndex,
 }
 
 /// The information parsed from a type record with kind `LF_VTSHAPE`.
 #[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
 pub struct VirtualTableShapeType {
     pub descriptors: Vec<VirtualTableShapeDescriptor>,
 }
 
 /// The information parsed from a type record with kind `LF_VFTABLE`.
 #[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
 pub struct VirtualFunctionTableType {
     pub owner: TypeIndex,
     pub base: TypeIndex,
@@ -1155,6 +1461,7 @@ pub struct VirtualFunctionTableType {
 
 /// The information parsed from a type record with kind `LF_PROCEDURE`.
 #[derive(Debug, Copy, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
 pub struct ProcedureType {
     pub return_type: Option<TypeIndex>,
     pub attributes: FunctionAttributes,
@@ -1162,8 +1469,17 @@ pub struct ProcedureType {
     pub argument_list: TypeIndex,
 }
 
+impl ProcedureType {
+    /// Returns the calling convention of this procedure.
+    #[must_use]
+    pub fn calling_convention(&self) -> CallingConvention {
+        CallingConvention::from(self.attributes.calling_convention())
+    }
+}
+
 /// The information parsed from a type record with kind `LF_POINTER`.
 #[derive(Debug, Copy, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
 pub struct PointerType {
     pub underlying_type: TypeIndex,
     pub attributes: PointerAttributes,
@@ -1172,6 +1488,7 @@ pub struct PointerType {
 
 /// The information parsed from a type record with kind `LF_MODIFIER`.
 #[derive(Debug, Copy, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
 pub struct ModifierType {
     pub underlying_type: TypeIndex,
     pub constant: bool,
@@ -1181,6 +1498,7 @@ pub struct ModifierType {
 
 /// The information parsed from a type record with kind `LF_ENUM` or `LF_ENUM_ST`.
 #[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
 pub struct EnumerationType {
     pub count: u16,
     pub properties: TypeProperties,
@@ -1192,6 +1510,7 @@ pub struct EnumerationType {
 
 /// The information parsed from a type record with kind `LF_ENUMERATE` or `LF_ENUMERATE_ST`.
 #[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
 pub struct EnumerateType {
     pub attributes: FieldAttributes,
     pub value: Variant,
@@ -1201,6 +1520,7 @@ pub struct EnumerateType {
 /// The information parsed from a type record with kind
 /// `LF_ARRAY`, `LF_ARRAY_ST` or `LF_STRIDED_ARRAY`.
 #[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
 pub struct ArrayType {
     pub element_type: TypeIndex,
     pub indexing_type: TypeIndex,
@@ -1209,17 +1529,46 @@ pub struct ArrayType {
     /// Contains array dimensions as specified in the PDB. This is not what you expect:
     ///
     /// * Dimensions are specified in terms of byte sizes, not element counts.
-    /// * Multidimensional arrays aggregate the lower dimensions into the sizes of the higher
-    ///   dimensions.
+    /// * Multidimensional arrays are emitted as arrays of arrays, where the size of the outer
+    ///   array aggregates the sizes of the inner dimensions.
     ///
-    /// Thus a `float[4][4]` has `dimensions: [16, 64]`. Determining array dimensions in terms
-    /// of element counts requires determining the size of the `element_type` and iteratively
-    /// dividing.
+    /// Thus a `float[4][4]` is an array with `dimensions: [64]` of an array with
+    /// `dimensions: [16]`. Use [`element_count`](Self::element_count) to determine the number of
+    /// elements.
     pub dimensions: Vec<u32>,
+    /// The name of the array type, if any.
+    pub name: Option<String>,
+}
+
+impl ArrayType {
+    /// Returns the total size of the array in bytes.
+    #[must_use]
+    pub fn len_bytes(&self) -> u64 {
+        self.dimensions.last().copied().map_or(0, u64::from)
+    }
+
+    /// Returns the number of elements in the array.
+    ///
+    /// The count is computed by dividing the [size of the array](Self::len_bytes) by the size of the
+    /// element type, which is looked up through the `type_finder`. Returns `None` if the size of the
+    /// element type is not known, for example for forward references to classes.
+    ///
+    /// # Errors
+    ///
+    /// * `Error::TypeNotFound` or `Error::TypeNotIndexed` if the element type cannot be found
+    pub fn element_count(&self, type_finder: &TypeFinder<'_>) -> Result<Option<u64>> {
+        let element_size = match type_size(type_finder, self.element_type, None)? {
+            Some(size) if size != 0 => size,
+            _ => return Ok(None),
+        };
+
+        Ok(Some(self.len_bytes() / element_size))
+    }
 }
 
 /// The information parsed from a type record with kind `LF_UNION` or `LF_UNION_ST`.
 #[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
 pub struct UnionType {
     pub count: u16,
     pub properties: TypeProperties,
@@ -1231,6 +1580,7 @@ pub struct UnionType {
 
 /// The information parsed from a type record with kind `LF_ALIAS` or `LF_ALIAS_ST`.
 #[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
 pub struct AliasType {
     pub underlying_type: TypeIndex,
     pub name: String,
@@ -1238,14 +1588,33 @@ pub struct AliasType {
 
 /// The information parsed from a type record with kind `LF_BITFIELD`.
 #[derive(Debug, Copy, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
 pub struct BitfieldType {
+    /// The type of the storage unit containing the bits.
     pub underlying_type: TypeIndex,
+    /// The number of bits.
     pub length: u8,
+    /// The index of the lowest bit within the storage unit.
     pub position: u8,
 }
 
+impl BitfieldType {
+    /// Returns the mask of the bits within the storage unit.
+    #[must_use]
+    pub fn mask(&self) -> u64 {
+        let bits = match self.length {
+            0 => 0,
+            length if length >= 64 => u64::MAX,
+            length => (1u64 << length) - 1,
+        };
+
+        bits.checked_shl(u32::from(self.position)).unwrap_or(0)
+    }
+}
+
 /// The information parsed from a type record with kind `LF_FIELDLIST`.
 #[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
 pub struct FieldList {
     pub fields: Vec<TypeData>,
 
@@ -1256,18 +1625,21 @@ pub struct FieldList {
 
 /// The information parsed from a type record with kind `LF_ARGLIST`.
 #[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
 pub struct ArgumentList {
     pub arguments: Vec<TypeIndex>,
 }
 
 /// The information parsed from a type record with kind `LF_METHODLIST`.
 #[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
 pub struct MethodList {
     pub methods: Vec<MethodListEntry>,
 }
 
 /// An entry in a `MethodList`.
 #[derive(Debug, Copy, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
 pub struct MethodListEntry {
     pub attributes: FieldAttributes,
     pub method_type: TypeIndex,
@@ -1312,3 +1684,72 @@ fn kind_1609() {
         })
     );
 }
+
+#[test]
+fn kind_1008() {
+    // int (__stdcall *)(int, int)
+    let data = &[8, 16, 116, 0, 0, 0, 7, 0, 2, 0, 0, 16, 0, 0][..];
+
+    let procedure = match parse_type_data(&mut ParseBuffer::from(data)).expect("parse") {
+        TypeData::Procedure(procedure) => procedure,
+        other => panic!("unexpected type {:?}", other),
+    };
+    assert_eq!(procedure.return_type, Some(TypeIndex(0x74)));
+    assert_eq!(procedure.parameter_count, 2);
+    assert_eq!(procedure.calling_convention(), CallingConvention::NearStd);
+    assert_eq!(procedure.calling_convention().to_string(), "__stdcall");
+}
+
+#[test]
+fn kind_1503() {
+    // int[10] named "arr", followed by padding
+    let data = &[
+        3, 21, 116, 0, 0, 0, 35, 0, 0, 0, 40, 0, 97, 114, 114, 0, 0xf2, 0xf1,
+    ][..];
+
+    assert_eq!(
+        parse_type_data(&mut ParseBuffer::from(data)).expect("parse"),
+        TypeData::Array(ArrayType {
+            element_type: TypeIndex(0x74),
+            indexing_type: TypeIndex(0x23),
+            stride: None,
+            dimensions: vec![40],
+            name: Some("arr".to_owned()),
+        })
+    );
+}
+
+#[test]
+fn kind_1203_base_classes() {
+    // class C : public A, public B, virtual public V
+    let data = &[
+        3, 18, // LF_FIELDLIST
+        0, 20, 3, 0, 0, 16, 0, 0, 0, 0, // LF_BCLASS A at 0
+        0, 20, 3, 0, 1, 16, 0, 0, 8, 0, // LF_BCLASS B at 8
+        1, 20, 3, 0, 2, 16, 0, 0, 3, 16, 0, 0, 16, 0, 1, 0, // LF_VBCLASS V
+    ][..];
+
+    let fields = match parse_type_data(&mut ParseBuffer::from(data)).expect("parse") {
+        TypeData::FieldList(list) => list.fields,
+        other => panic!("unexpected type {:?}", other),
+    };
+    let bases: Vec<_> = fields.iter().filter_map(BaseClass::from_field).collect();
+
+    assert_eq!(bases.len(), 3);
+    assert_eq!(bases[0].base_class(), TypeIndex(0x1000));
+    assert_eq!(bases[0].offset(), Some(0));
+    assert_eq!(bases[1].base_class(), TypeIndex(0x1001));
+    assert_eq!(bases[1].offset(), Some(8));
+    assert_eq!(bases[2].offset(), None);
+    assert_eq!(
+        bases[2],
+        BaseClass::Virtual(VirtualBaseClassType {
+            direct: true,
+            attributes: FieldAttributes(3),
+            base_class: TypeIndex(0x1002),
+            base_pointer: TypeIndex(0x1003),
+            base_pointer_offset: 16,
+            virtual_base_offset: 1,
+        })
+    );
+}
@@ -5,6 +5,7 @@
 // http://opensource.org/licenses/MIT>, at your option. This file may not be
 // copied, modified, or distributed except according to those terms.
 
+use std::collections::{BTreeSet, HashMap};
 use std::fmt;
 use std::marker::PhantomData;
 use std::result;
@@ -24,7 +25,7 @@ use self::header::*;
 
 pub use self::data::*;
 pub use self::id::*;
-pub use self::primitive::{Indirection, PrimitiveKind, PrimitiveType, type_data_for_primitive};
+pub use self::primitive::{type_data_for_primitive, Indirection, PrimitiveKind, PrimitiveType};
 
 /// Zero-copy access to a PDB type or id stream.
 ///
@@ -165,6 +166,37 @@ where
         }
     }
 
+    /// Returns an iterator that starts at the given index and continues in sequential order.
+    ///
+    /// The start of the record is located through the offsets recorded in `finder`, so the finder
+    /// must have been populated up to `index` first. Indexes below the first stored item, such as
+    /// primitive types, start at the beginning of the stream.
+    ///
+    /// This allows splitting the stream into ranges of indexes that are processed independently.
+    ///
+    /// # Errors
+    ///
+    /// * `Error::TypeNotFound(index)` if the index lies beyond the end of the stream.
+    /// * `Error::TypeNotIndexed(index, max_index)` if the finder has not been populated up to
+    ///   `index`.
+    pub fn iter_at(&self, index: I, finder: &ItemFinder<'_, I>) -> Result<ItemIter<'_, I>> {
+        let index: u32 = index.into();
+        if index <= self.header.minimum_index {
+            return Ok(self.iter());
+        } else if index > self.header.maximum_index {
+            return Err(Error::TypeNotFound(index));
+        }
+
+        let mut buf = self.stream.parse_buffer();
+        buf.take(finder.seek(index)?.pos())?;
+
+        Ok(ItemIter {
+            buf,
+            index,
+            _ph: PhantomData,
+        })
+    }
+
     /// Returns the number of items contained in this `ItemInformation`.
     ///
     /// Note that in the case of the type stream ([`TypeInformation`]) primitive types are not
@@ -409,6 +441,19 @@ where
             return Err(Error::TypeNotFound(index));
         }
 
+        let mut buf = self.seek(index)?;
+
+        // read the type
+        let length = buf.parse_u16()?;
+
+        Ok(Item {
+            index: I::from(index),
+            data: buf.take(length as usize)?,
+        })
+    }
+
+    /// Returns a buffer positioned at the length prefix of the record with the given index.
+    fn seek(&self, index: u32) -> Result<ParseBuffer<'t>> {
         // figure out where we'd find this
         let (vec_index, iteration_count) = self.resolve(index);
 
@@ -425,13 +470,7 @@ where
                 buf.take(length as usize)?;
             }
 
-            // read the type
-            let length = buf.parse_u16()?;
-
-            Ok(Item {
-                index: I::from(index),
-                data: buf.take(length as usize)?,
-            })
+            Ok(buf)
         } else {
             // miss
             Err(Error::TypeNotIndexed(index, self.max_index().into()))
@@ -524,6 +563,400 @@ impl<'t> Item<'t, TypeIndex> {
     }
 }
 
+impl<'t> ItemFinder<'t, TypeIndex> {
+    /// Returns an iterator over the fields of the field list at `index`.
+    ///
+    /// Field lists that do not fit into a single record are split into a chain of `LF_FIELDLIST`
+    /// records linked by their [`continuation`](FieldList::continuation). The iterator follows this
+    /// chain and yields the fields of all records in order. Use [`MemberType::bitfield`] to obtain
+    /// the bit layout of bitfield members.
+    ///
+    /// The iterator fails with `Error::NotAFieldList` if a type in the chain is not a field list.
+    #[must_use]
+    pub fn fields(&self, index: TypeIndex) -> FieldIter<'_, 't> {
+        FieldIter {
+            finder: self,
+            fields: Vec::new().into_iter(),
+            next: Some(index),
+            visited: BTreeSet::new(),
+        }
+    }
+
+    /// Returns the direct and virtual base classes of a class in declaration order.
+    ///
+    /// `fields` is the field list of the class. Virtual bases include indirect virtual bases that
+    /// are inherited through another base, see [`VirtualBaseClassType::direct`].
+    ///
+    /// # Errors
+    ///
+    /// * `Error::NotAFieldList` if `fields` is not a field list
+    pub fn base_classes(&self, fields: TypeIndex) -> Result<Vec<BaseClass>> {
+        let mut bases = Vec::new();
+
+        let mut iter = self.fields(fields);
+        while let Some(field) = iter.next()? {
+            bases.extend(BaseClass::from_field(&field));
+        }
+
+        Ok(bases)
+    }
+
+    /// Returns the parameter types of the function type at `index`.
+    ///
+    /// The type must be an `LF_PROCEDURE` or `LF_MFUNCTION` record, or a pointer or modifier
+    /// thereof. Parameters are read from the `LF_ARGLIST` referenced by the function type. For
+    /// member functions with a [`this_pointer_type`](MemberFunctionType::this_pointer_type), the
+    /// implicit `this` is returned as the first parameter. Static member functions have no `this`.
+    ///
+    /// # Errors
+    ///
+    /// * `Error::NotAFunctionType` if the type is not a function type, its argument list is not an
+    ///   `LF_ARGLIST`, or the pointers and modifiers leading to it form a cycle
+    /// * `Error::TypeNotFound` or `Error::TypeNotIndexed` if a type cannot be found
+    pub fn function_parameters(&self, index: TypeIndex) -> Result<Vec<TypeIndex>> {
+        let (this, argument_list) = match resolve_function_type(self, index)? {
+            TypeData::Procedure(data) => (None, data.argument_list),
+            TypeData::MemberFunction(data) => (data.this_pointer_type, data.argument_list),
+            _ => return Err(Error::NotAFunctionType(index.0)),
+        };
+
+        let arguments = match self.find(argument_list)?.parse()? {
+            TypeData::ArgumentList(list) => list.arguments,
+            _ => return Err(Error::NotAFunctionType(index.0)),
+        };
+
+        Ok(this.into_iter().chain(arguments).collect())
+    }
+
+    /// Returns the virtual methods introduced by a class in vtable order.
+    ///
+    /// `fields` is the field list of the class. Only methods that introduce a new vtable slot carry
+    /// a [`vtable_offset`](MethodType::vtable_offset). Methods overriding a virtual method of a
+    /// base class reuse the slot of the base method and are therefore not returned. Methods that
+    /// share a slot, such as a destructor and the compiler-generated `__vecDelDtor`, are returned
+    /// in declaration order. The layout of the slots is described by the
+    /// [`vtable_shape`](ClassType::vtable_shape) of the class.
+    ///
+    /// # Errors
+    ///
+    /// * `Error::NotAFieldList` if `fields` is not a field list
+    /// * `Error::TypeNotFound` or `Error::TypeNotIndexed` if a method list cannot be found
+    pub fn virtual_methods(&self, fields: TypeIndex) -> Result<Vec<VirtualMethod>> {
+        let mut methods = Vec::new();
+
+        let mut iter = self.fields(fields);
+        while let Some(field) = iter.next()? {
+            match field {
+                TypeData::Method(method) => {
+                    if let Some(vtable_offset) = method.vtable_offset {
+                        methods.push(VirtualMethod {
+                            name: method.name,
+                            attributes: method.attributes,
+                            method_type: method.method_type,
+                            vtable_offset,
+                        });
+                    }
+                }
+                TypeData::OverloadedMethod(overloads) => {
+                    let list = match self.find(overloads.method_list)?.parse()? {
+                        TypeData::MethodList(list) => list,
+                        _ => continue,
+                    };
+
+                    for method in list.methods {
+                        if let Some(vtable_offset) = method.vtable_offset {
+                            methods.push(VirtualMethod {
+                                name: overloads.name.clone(),
+                                attributes: method.attributes,
+                                method_type: method.method_type,
+                                vtable_offset,
+                            });
+                        }
+                    }
+                }
+                _ => (),
+            }
+        }
+
+        methods.sort_by_key(|method| method.vtable_offset);
+        Ok(methods)
+    }
+}
+
+/// A virtual method occupying a vtable slot, as returned by
+/// [`TypeFinder::virtual_methods`](ItemFinder::virtual_methods).
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct VirtualMethod {
+    /// The name of the method.
+    pub name: String,
+    /// The attributes of the method.
+    pub attributes: FieldAttributes,
+    /// The `LF_MFUNCTION` type of the method.
+    pub method_type: TypeIndex,
+    /// The byte offset of the slot within the vtable.
+    pub vtable_offset: u32,
+}
+
+/// An iterator over the fields of a chain of field lists.
+///
+/// Created by [`TypeFinder::fields`](ItemFinder::fields).
+#[derive(Debug)]
+pub struct FieldIter<'f, 't> {
+    finder: &'f TypeFinder<'t>,
+    fields: std::vec::IntoIter<TypeData>,
+    next: Option<TypeIndex>,
+    visited: BTreeSet<TypeIndex>,
+}
+
+impl FallibleIterator for FieldIter<'_, '_> {
+    type Item = TypeData;
+    type Error = Error;
+
+    fn next(&mut self) -> Result<Option<Self::Item>> {
+        loop {
+            if let Some(field) = self.fields.next() {
+                return Ok(Some(field));
+            }
+
+            // stop at the end of the chain, or if the chain loops
+            let index = match self.next.take() {
+                Some(index) if self.visited.insert(index) => index,
+                _ => return Ok(None),
+            };
+
+            match self.finder.find(index)?.parse()? {
+                TypeData::FieldList(list) => {
+                    self.fields = list.fields.into_iter();
+                    self.next = list.continuation;
+                }
+                _ => return Err(Error::NotAFieldList(index.0)),
+            }
+        }
+    }
+}
+
+impl ItemInformation<'_, TypeIndex> {
+    /// Returns the size of a value of the type at `index` in bytes, like `sizeof(T)`.
+    ///
+    /// This supports primitive types, classes, structs and unions, enumerations, pointers, arrays,
+    /// and modifiers and bitfields of those. Forward references to classes and unions are resolved
+    /// to their complete definition by name. The size of pointers is taken from the pointer record.
+    ///
+    /// Returns `None` if the type has no size, such as `void` or function types, if a forward
+    /// reference has no definition in this stream, or if the underlying types form a cycle.
+    ///
+    /// This parses the entire stream on every call to index all types and definitions.
+    ///
+    /// # Errors
+    ///
+    /// * `Error::TypeNotFound` if the type is not in this stream
+    /// * `Error::UnexpectedEof` if a type record is malformed
+    pub fn size_of(&self, index: TypeIndex) -> Result<Option<u64>> {
+        let mut finder = self.finder();
+        let mut definitions = HashMap::new();
+        let mut iter = self.iter();
+        while let Some(item) = iter.next()? {
+            finder.update(&iter);
+
+            let (properties, size, name, unique_name) = match item.parse() {
+                Ok(TypeData::Class(data)) => {
+                    (data.properties, data.size, data.name, data.unique_name)
+                }
+                Ok(TypeData::Union(data)) => {
+                    (data.properties, data.size, data.name, data.unique_name)
+                }
+                _ => continue,
+            };
+
+            if !properties.forward_reference() {
+                definitions
+                    .entry(unique_name.unwrap_or(name))
+                    .or_insert(size);
+            }
+        }
+
+        type_size(&finder, index, Some(&definitions))
+    }
+
+    /// Computes the transitive closure of the types referenced by `seed`.
+    ///
+    /// Starting from the given types, this follows all references returned by
+    /// [`TypeData::referenced_types`], such as member and base class types, pointees, modifiers,
+    /// and the return and parameter types of functions, until no new types are found. The result
+    /// contains the seed types themselves. Primitive types are included, but have no dependencies.
+    ///
+    /// This builds a [`TypeFinder`] over the entire stream first. Types that cannot be parsed
+    /// because their kind is not implemented are included, but not followed.
+    ///
+    /// # Errors
+    ///
+    /// * `Error::TypeNotFound` if a referenced type is not in this stream
+    /// * `Error::UnexpectedEof` if a type record is malformed
+    pub fn dependencies(&self, seed: &[TypeIndex]) -> Result<BTreeSet<TypeIndex>> {
+        let mut finder = self.finder();
+        let mut iter = self.iter();
+        while iter.next()?.is_some() {
+            finder.update(&iter);
+        }
+
+        let mut dependencies = BTreeSet::new();
+        let mut pending = seed.to_vec();
+        while let Some(index) = pending.pop() {
+            if !dependencies.insert(index) || index < TypeIndex(0x1000) {
+                continue;
+            }
+
+            match finder.find(index)?.parse() {
+                Ok(data) => pending.extend(data.referenced_types()),
+                Err(Error::UnimplementedTypeKind(_)) => (),
+                Err(e) => return Err(e),
+            }
+        }
+
+        Ok(dependencies)
+    }
+}
+
+/// Parses the type at `index`, following the underlying type of every record for which `follow`
+/// returns one.
+///
+/// Returns `None` if the chain of underlying types contains a cycle.
+fn parse_through<F>(
+    type_finder: &TypeFinder<'_>,
+    mut index: TypeIndex,
+    follow: F,
+) -> Result<Option<TypeData>>
+where
+    F: Fn(&TypeData) -> Option<TypeIndex>,
+{
+    let mut visited = BTreeSet::new();
+    while visited.insert(index) {
+        let data = type_finder.find(index)?.parse()?;
+        match follow(&data) {
+            Some(underlying_type) => index = underlying_type,
+            None => return Ok(Some(data)),
+        }
+    }
+
+    Ok(None)
+}
+
+/// Parses the type at `index`, looking through modifiers such as `const`.
+///
+/// Returns `None` if the modifiers form a cycle.
+pub(crate) fn strip_modifiers(
+    type_finder: &TypeFinder<'_>,
+    index: TypeIndex,
+) -> Result<Option<TypeData>> {
+    parse_through(type_finder, index, |data| match data {
+        TypeData::Modifier(modifier) => Some(modifier.underlying_type),
+        _ => None,
+    })
+}
+
+/// Parses the type at `index`, looking through pointers and modifiers.
+fn resolve_function_type(type_finder: &TypeFinder<'_>, index: TypeIndex) -> Result<TypeData> {
+    let data = parse_through(type_finder, index, |data| match data {
+        TypeData::Pointer(pointer) => Some(pointer.underlying_type),
+        TypeData::Modifier(modifier) => Some(modifier.underlying_type),
+        _ => None,
+    })?;
+
+    data.ok_or(Error::NotAFunctionType(index.0))
+}
+
+/// Resolves a function type to its parameters, return type and calling convention.
+///
+/// The type at `index` must be an `LF_PROCEDURE` or `LF_MFUNCTION` record, or a pointer or modifier
+/// thereof as used by indirect call sites. Parameters are read from the referenced argument list
+/// and do not include the implicit `this` of member functions. A missing return type is reported as
+/// `TypeIndex(0)`.
+pub(crate) fn resolve_signature(
+    type_finder: &TypeFinder<'_>,
+    index: TypeIndex,
+) -> Result<(Vec<TypeIndex>, TypeIndex, CallingConvention)> {
+    let (return_type, attributes, argument_list) = match resolve_function_type(type_finder, index)?
+    {
+        TypeData::Procedure(data) => (
+            data.return_type.unwrap_or_default(),
+            data.attributes,
+            data.argument_list,
+        ),
+        TypeData::MemberFunction(data) => (data.return_type, data.attributes, data.argument_list),
+        _ => return Err(Error::NotAFunctionType(index.0)),
+    };
+
+    let parameters = match type_finder.find(argument_list)?.parse()? {
+        TypeData::ArgumentList(list) => list.arguments,
+        _ => return Err(Error::NotAFunctionType(index.0)),
+    };
+
+    let calling_convention = CallingConvention::from(attributes.calling_convention());
+    Ok((parameters, return_type, calling_convention))
+}
+
+/// Determines the size of a value of the type at `index` in bytes.
+///
+/// Forward references to classes and unions are resolved through `definitions`, which maps the
+/// unique name or name of complete definitions to their size. Returns `None` if the size cannot be
+/// determined, such as for unresolved forward references or cycles of underlying types, or if the
+/// type has no size, such as `void` or function types.
+pub(crate) fn type_size(
+    type_finder: &TypeFinder<'_>,
+    index: TypeIndex,
+    definitions: Option<&HashMap<String, u64>>,
+) -> Result<Option<u64>> {
+    type_size_inner(type_finder, index, definitions, &mut BTreeSet::new())
+}
+
+fn type_size_inner(
+    type_finder: &TypeFinder<'_>,
+    index: TypeIndex,
+    definitions: Option<&HashMap<String, u64>>,
+    visited: &mut BTreeSet<TypeIndex>,
+) -> Result<Option<u64>> {
+    if !visited.insert(index) {
+        return Ok(None);
+    }
+
+    let lookup = |properties: TypeProperties, size: u64, name: &str, unique_name: Option<&str>| {
+        if !properties.forward_reference() {
+            return Some(size);
+        }
+        definitions.and_then(|definitions| definitions.get(unique_name.unwrap_or(name)).copied())
+    };
+
+    Ok(match type_finder.find(index)?.parse()? {
+        TypeData::Primitive(data) => data.size(),
+        TypeData::Class(data) => lookup(
+            data.properties,
+            data.size,
+            &data.name,
+            data.unique_name.as_deref(),
+        ),
+        TypeData::Union(data) => lookup(
+            data.properties,
+            data.size,
+            &data.name,
+            data.unique_name.as_deref(),
+        ),
+        TypeData::Enumeration(data) => {
+            type_size_inner(type_finder, data.underlying_type, definitions, visited)?
+        }
+        TypeData::Pointer(data) => {
+            Some(u64::from(data.attributes.size())).filter(|&size| size != 0)
+        }
+        TypeData::Modifier(data) => {
+            type_size_inner(type_finder, data.underlying_type, definitions, visited)?
+        }
+        TypeData::Bitfield(data) => {
+            type_size_inner(type_finder, data.underlying_type, definitions, visited)?
+        }
+        TypeData::Array(data) => Some(data.len_bytes()),
+        _ => None,
+    })
+}
+
 /// Zero-copy access to the PDB type stream (TPI).
 ///
 /// This stream exposes types, the variants of which are enumerated by [`IdData`]. See
@@ -554,3 +987,121 @@ impl<'t> Item<'t, IdIndex> {
         ParseBuffer::from(self.data).parse()
     }
 }
+
+#[cfg(test)]
+pub(crate) mod tests {
+    use crate::msf::Stream;
+    use crate::tpi::*;
+
+    /// Encodes a type record with the given kind and payload, including its length prefix.
+    pub(crate) fn record(kind: u16, payload: &[u8]) -> Vec<u8> {
+        let mut data = ((payload.len() + 2) as u16).to_le_bytes().to_vec();
+        data.extend_from_slice(&kind.to_le_bytes());
+        data.extend_from_slice(payload);
+        data
+    }
+
+    /// Encodes an `LF_MODIFIER` record.
+    pub(crate) fn modifier(underlying_type: u32) -> Vec<u8> {
+        let mut payload = underlying_type.to_le_bytes().to_vec();
+        payload.extend_from_slice(&1u16.to_le_bytes()); // const
+        record(0x1001, &payload)
+    }
+
+    /// Encodes an `LF_POINTER` record to a 64-bit near pointer.
+    pub(crate) fn pointer(underlying_type: u32) -> Vec<u8> {
+        let mut payload = underlying_type.to_le_bytes().to_vec();
+        payload.extend_from_slice(&0x0001_000cu32.to_le_bytes());
+        record(0x1002, &payload)
+    }
+
+    /// Builds a type stream from the given records, numbered from `0x1000`.
+    pub(crate) fn type_information(records: &[Vec<u8>]) -> TypeInformation<'static> {
+        let body: Vec<u8> = records.concat();
+
+        let mut data = Vec::new();
+        for value in [
+            20_040_203u32,                 // version
+            56,                            // header size
+            0x1000,                        // minimum index
+            0x1000 + records.len() as u32, // maximum index
+            body.len() as u32,             // record bytes
+        ] {
+            data.extend_from_slice(&value.to_le_bytes());
+        }
+        data.extend_from_slice(&[0xff; 4]); // hash streams
+        data.resize(56, 0);
+        data.extend(body);
+
+        TypeInformation::parse(Stream::from_bytes(data)).expect("type information")
+    }
+
+    /// Builds a finder covering all types of `type_information`.
+    pub(crate) fn finder<'t>(type_information: &'t TypeInformation<'_>) -> TypeFinder<'t> {
+        let mut finder = type_information.finder();
+        let mut iter = type_information.iter();
+        while iter.next().expect("next type").is_some() {
+            finder.update(&iter);
+        }
+        finder
+    }
+
+    mod size {
+        use super::*;
+
+        #[test]
+        fn test_modifier_cycle() {
+            // 0x1000 points to itself, 0x1001 -> 0x1002 -> 0x1001, 0x1003 is a const int
+            let types = type_information(&[
+                modifier(0x1000),
+                modifier(0x1002),
+                modifier(0x1001),
+                modifier(0x74),
+            ]);
+            let finder = finder(&types);
+
+            assert_eq!(
+                type_size(&finder, TypeIndex(0x1000), None).expect("self"),
+                None
+            );
+            assert_eq!(
+                type_size(&finder, TypeIndex(0x1001), None).expect("cycle"),
+                None
+            );
+            assert_eq!(
+                type_size(&finder, TypeIndex(0x1003), None).expect("int"),
+                Some(4)
+            );
+            assert_eq!(types.size_of(TypeIndex(0x1000)).expect("size_of"), None);
+        }
+    }
+
+    mod signature {
+        use super::*;
+
+        #[test]
+        fn test_pointer_cycle() {
+            // 0x1000 -> 0x1001 -> 0x1000, 0x1002 points to itself
+            let types = type_information(&[pointer(0x1001), modifier(0x1000), pointer(0x1002)]);
+            let finder = finder(&types);
+
+            for index in [0x1000, 0x1001, 0x1002] {
+                assert!(matches!(
+                    resolve_signature(&finder, TypeIndex(index)),
+                    Err(Error::NotAFunctionType(i)) if i == index
+                ));
+            }
+        }
+
+        #[test]
+        fn test_function_parameters_cycle() {
+            let types = type_information(&[modifier(0x1001), pointer(0x1000)]);
+            let finder = finder(&types);
+
+            assert!(matches!(
+                finder.function_parameters(TypeIndex(0x1001)),
+                Err(Error::NotAFunctionType(0x1001))
+            ));
+        }
+    }
+}
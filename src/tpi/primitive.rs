@@ -24,6 +24,7 @@ use crate::tpi::data::TypeData;
 
 /// Represents a primitive type like `void` or `char *`.
 #[derive(Debug, Copy, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
 pub struct PrimitiveType {
     /// The kind of the primitive type.
     pub kind: PrimitiveKind,
@@ -32,9 +33,74 @@ pub struct PrimitiveType {
     pub indirection: Option<Indirection>,
 }
 
+impl PrimitiveType {
+    /// Returns the size of a value of this type in bytes.
+    ///
+    /// For pointers, this is the size of the pointer. Returns `None` for `void` and types without a
+    /// type.
+    #[must_use]
+    pub fn size(&self) -> Option<u64> {
+        if let Some(indirection) = self.indirection {
+            return Some(match indirection {
+                Indirection::Near16 => 2,
+                Indirection::Far16 | Indirection::Huge16 | Indirection::Near32 => 4,
+                Indirection::Far32 => 6,
+                Indirection::Near64 => 8,
+                Indirection::Near128 => 16,
+            });
+        }
+
+        Some(match self.kind {
+            PrimitiveKind::NoType | PrimitiveKind::Void => return None,
+            PrimitiveKind::Char
+            | PrimitiveKind::UChar
+            | PrimitiveKind::RChar
+            | PrimitiveKind::Char8
+            | PrimitiveKind::I8
+            | PrimitiveKind::U8
+            | PrimitiveKind::Bool8 => 1,
+            PrimitiveKind::WChar
+            | PrimitiveKind::RChar16
+            | PrimitiveKind::Short
+            | PrimitiveKind::UShort
+            | PrimitiveKind::I16
+            | PrimitiveKind::U16
+            | PrimitiveKind::F16
+            | PrimitiveKind::Bool16 => 2,
+            PrimitiveKind::RChar32
+            | PrimitiveKind::Long
+            | PrimitiveKind::ULong
+            | PrimitiveKind::I32
+            | PrimitiveKind::U32
+            | PrimitiveKind::F32
+            | PrimitiveKind::F32PP
+            | PrimitiveKind::Bool32
+            | PrimitiveKind::HRESULT => 4,
+            PrimitiveKind::F48 => 6,
+            PrimitiveKind::Quad
+            | PrimitiveKind::UQuad
+            | PrimitiveKind::I64
+            | PrimitiveKind::U64
+            | PrimitiveKind::F64
+            | PrimitiveKind::Complex32
+            | PrimitiveKind::Bool64 => 8,
+            PrimitiveKind::F80 => 10,
+            PrimitiveKind::Octa
+            | PrimitiveKind::UOcta
+            | PrimitiveKind::I128
+            | PrimitiveKind::U128
+            | PrimitiveKind::F128
+            | PrimitiveKind::Complex64 => 16,
+            PrimitiveKind::Complex80 => 20,
+            PrimitiveKind::Complex128 => 32,
+        })
+    }
+}
+
 /// A simple type.
 #[non_exhaustive]
 #[derive(Debug, Copy, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
 pub enum PrimitiveKind {
     /// Uncharacterized type (no type)
     NoType,
@@ -174,6 +240,7 @@ pub enum PrimitiveKind {
 /// definitions. While `PointerKind` can specify many more pointer types, including relative
 /// pointers, `Indirection` also contains a 128-bit variant.
 #[derive(Debug, Copy, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
 pub enum Indirection {
     /// 16-bit ("near") pointer.
     Near16,
@@ -191,6 +258,7 @@ pub enum Indirection {
     Near128,
 }
 
+/// Returns the [`TypeData`] describing a primitive type index below `0x1000`.
 pub fn type_data_for_primitive(index: TypeIndex) -> Result<TypeData> {
     // https://github.com/Microsoft/microsoft-pdb/blob/082c5290e5aff028ae84e43affa8be717aa7af73/include/cvinfo.h#L326-L750
 
@@ -1,5 +1,8 @@
 use pdb2 as pdb;
 
+use pdb::FallibleIterator;
+use scroll::Pread;
+
 #[test]
 fn pdb_info() {
     let file = std::fs::File::open("fixtures/self/foo.pdb").expect("opening file");
@@ -12,3 +15,441 @@ fn pdb_info() {
         pdb::MachineType::Amd64
     );
 }
+
+#[test]
+fn module_count() {
+    let file = std::fs::File::open("fixtures/self/foo.pdb").expect("opening file");
+
+    let mut pdb = pdb::PDB::open(file).expect("opening pdb");
+    let count = pdb.debug_module_count().expect("module count");
+
+    let dbi = pdb.debug_information().expect("debug information");
+    let modules = dbi.modules().expect("modules");
+    assert_eq!(count, modules.clone().count().expect("count modules"));
+    assert_eq!(count, dbi.module_count().expect("module count"));
+
+    // a cloned iterator resumes from the same position
+    let mut first = modules;
+    first.next().expect("first module");
+    let rest = first.clone().count().expect("count rest");
+    assert_eq!(rest + 1, count);
+    assert_eq!(first.count().expect("count rest"), rest);
+}
+
+#[test]
+fn all_procedures() {
+    let file = std::fs::File::open("fixtures/self/foo.pdb").expect("opening file");
+    let mut pdb = pdb::PDB::open(file).expect("opening pdb");
+
+    // count procedures by walking every module by hand
+    let dbi = pdb.debug_information().expect("debug information");
+    let mut modules = dbi.modules().expect("modules");
+    let mut expected = Vec::new();
+    let mut module_index = 0;
+    while let Some(module) = modules.next().expect("next module") {
+        if let Some(info) = pdb.module_info(&module).expect("module info") {
+            let mut symbols = info.symbols().expect("symbols");
+            while let Some(symbol) = symbols.next().expect("next symbol") {
+                if let Ok(pdb::SymbolData::Procedure(procedure)) = symbol.parse() {
+                    expected.push((module_index, procedure));
+                }
+            }
+        }
+        module_index += 1;
+    }
+
+    let procedures: Vec<_> = pdb
+        .all_procedures()
+        .expect("all procedures")
+        .collect()
+        .expect("collect procedures");
+
+    assert_eq!(procedures.len(), 2768);
+    assert_eq!(procedures, expected);
+    assert!(procedures
+        .iter()
+        .any(|(_, procedure)| procedure.name == "main"));
+}
+
+#[test]
+fn validate_sections() {
+    let file = std::fs::File::open("fixtures/self/foo.pdb").expect("opening file");
+    let mut pdb = pdb::PDB::open(file).expect("opening pdb");
+    let sections = pdb.sections().expect("sections").expect("section headers");
+
+    let dbi = pdb.debug_information().expect("debug information");
+    let mut modules = dbi.modules().expect("modules");
+    let mut mismatches = Vec::new();
+    while let Some(module) = modules.next().expect("next module") {
+        if let Some(info) = pdb.module_info(&module).expect("module info") {
+            let symbols = info.symbols().expect("symbols");
+            mismatches.extend(pdb::validate_sections(symbols, &sections).expect("validate"));
+        }
+    }
+
+    // the linker drops the write flag of .idata after emitting the S_SECTION record
+    assert_eq!(
+        mismatches,
+        vec![pdb::SectionMismatch::Characteristics {
+            section: 5,
+            symbol: pdb::SectionCharacteristics(0xc000_0040),
+            header: pdb::SectionCharacteristics(0x4000_0040),
+        }]
+    );
+}
+
+#[test]
+fn external_debug_sources() {
+    let data = std::fs::read("fixtures/self/foo.pdb").expect("reading file");
+
+    // foo.pdb carries its full debug information
+    let mut pdb = pdb::PDB::open(std::io::Cursor::new(data.clone())).expect("opening pdb");
+    let sources = pdb
+        .external_debug_sources()
+        .expect("external debug sources");
+    assert!(sources.is_empty());
+
+    // Turn it into a mini PDB by replacing the trailing VC140 feature signature of the PDB info
+    // stream with the minimal debug info signature.
+    let info = pdb
+        .raw_stream(pdb::StreamIndex(1))
+        .expect("pdb info stream")
+        .expect("pdb info stream present");
+    let tail = &info[info.len() - 16..];
+    assert_eq!(tail[12..], 20_140_508u32.to_le_bytes());
+    // The MSF may keep stale copies of the stream around, so patch every one of them.
+    let positions: Vec<_> = data
+        .windows(tail.len())
+        .enumerate()
+        .filter(|(_, window)| *window == tail)
+        .map(|(position, _)| position)
+        .collect();
+    assert!(!positions.is_empty());
+
+    let mut data = data;
+    for position in positions {
+        data[position + 12..position + 16].copy_from_slice(&0x494E_494Du32.to_le_bytes());
+    }
+
+    let mut pdb = pdb::PDB::open(std::io::Cursor::new(data)).expect("opening pdb");
+    let features = pdb.pdb_information().unwrap().features().unwrap();
+    assert!(features.minimal_debug_info);
+
+    let sources = pdb
+        .external_debug_sources()
+        .expect("external debug sources");
+    let sources: Vec<_> = sources.iter().map(|path| path.to_string_lossy()).collect();
+    assert_eq!(
+        sources,
+        [
+            r"c:\Users\User\Desktop\self\foo.obj",
+            r"C:\Program Files (x86)\Microsoft Visual Studio 14.0\VC\LIB\amd64\LIBCMT.lib",
+            r"C:\Program Files (x86)\Windows Kits\10\lib\10.0.14393.0\um\x64\kernel32.lib",
+            r"C:\Program Files (x86)\Microsoft Visual Studio 14.0\VC\LIB\amd64\libvcruntime.lib",
+            r"C:\Program Files (x86)\Windows Kits\10\lib\10.0.14393.0\ucrt\x64\libucrt.lib",
+        ]
+    );
+}
+
+#[test]
+fn resolve_token_reference() {
+    let mut data = std::fs::read("fixtures/self/foo.pdb").expect("reading file");
+
+    // foo.pdb has no managed code. Find the first procedure in the first module and craft a
+    // managed procedure from it by changing its kind to S_GMANPROC. The managed procedure has an
+    // additional return register field, which takes the first two bytes of the name.
+    let (module_index, symbol_index, record, procedure) = {
+        let mut pdb = pdb::PDB::open(std::io::Cursor::new(&data)).expect("opening pdb");
+        let dbi = pdb.debug_information().expect("debug information");
+        let mut modules = dbi.modules().expect("modules").enumerate();
+
+        'modules: loop {
+            let (module_index, module) = modules.next().expect("next module").expect("module");
+            let info = match pdb.module_info(&module).expect("module info") {
+                Some(info) => info,
+                None => continue,
+            };
+
+            let mut symbols = info.symbols().expect("symbols");
+            while let Some(symbol) = symbols.next().expect("next symbol") {
+                if let Ok(pdb::SymbolData::Procedure(procedure)) = symbol.parse() {
+                    let mut record = (symbol.raw_bytes().len() as u16).to_le_bytes().to_vec();
+                    record.extend_from_slice(symbol.raw_bytes());
+                    break 'modules (module_index, symbol.index(), record, procedure);
+                }
+            }
+        }
+    };
+
+    let positions: Vec<_> = data
+        .windows(record.len())
+        .enumerate()
+        .filter(|(_, window)| *window == &record[..])
+        .map(|(position, _)| position)
+        .collect();
+    assert!(!positions.is_empty());
+    for position in positions {
+        data[position + 2..position + 4].copy_from_slice(&0x112au16.to_le_bytes());
+    }
+
+    let mut pdb = pdb::PDB::open(std::io::Cursor::new(data)).expect("opening pdb");
+    let mut reference = pdb::TokenReferenceSymbol {
+        sum_name: 0,
+        symbol_index,
+        module: Some(module_index),
+        name: procedure.name.clone(),
+    };
+
+    let managed = pdb
+        .resolve_token_reference(&reference)
+        .expect("resolve token reference")
+        .expect("managed procedure");
+    assert!(managed.global);
+    assert_eq!(managed.len, procedure.len);
+    assert_eq!(managed.token, pdb::COMToken(procedure.type_index.0));
+    assert_eq!(managed.offset, procedure.offset);
+    assert_eq!(managed.name.as_deref(), Some(&procedure.name[2..]));
+
+    // references to symbols that are not managed procedures do not resolve
+    reference.symbol_index = pdb::SymbolIndex(4);
+    assert_eq!(pdb.resolve_token_reference(&reference).unwrap(), None);
+    reference.module = None;
+    assert_eq!(pdb.resolve_token_reference(&reference).unwrap(), None);
+}
+
+#[test]
+fn resolve_data_reference() {
+    let file = std::fs::File::open("fixtures/self/foo.pdb").expect("opening file");
+    let mut pdb = pdb::PDB::open(file).expect("opening pdb");
+
+    // find a data symbol in the symbols of some module other than the first
+    let (module_index, symbol_index, data) = {
+        let dbi = pdb.debug_information().expect("debug information");
+        let mut modules = dbi.modules().expect("modules").enumerate().skip(1);
+
+        'modules: loop {
+            let (module_index, module) = modules.next().expect("next module").expect("module");
+            let info = match pdb.module_info(&module).expect("module info") {
+                Some(info) => info,
+                None => continue,
+            };
+
+            let mut symbols = info.symbols().expect("symbols");
+            while let Some(symbol) = symbols.next().expect("next symbol") {
+                if let Ok(pdb::SymbolData::Data(data)) = symbol.parse() {
+                    break 'modules (module_index, symbol.index(), data);
+                }
+            }
+        }
+    };
+
+    // S_DATAREF records store 1-based module indices
+    let mut record = 0x1126u16.to_le_bytes().to_vec();
+    record.extend_from_slice(&0u32.to_le_bytes()); // sum_name
+    record.extend_from_slice(&symbol_index.0.to_le_bytes());
+    record.extend_from_slice(&(module_index as u16 + 1).to_le_bytes());
+    record.extend_from_slice(data.name.as_bytes());
+    record.push(0);
+
+    let reference = match record.pread_with(0, ()).expect("parse data reference") {
+        pdb::SymbolData::DataReference(reference) => reference,
+        other => panic!("unexpected symbol {:?}", other),
+    };
+    assert_eq!(reference.module, Some(module_index));
+
+    let resolved = pdb
+        .resolve_data_reference(&reference)
+        .expect("resolve data reference");
+    assert_eq!(resolved, Some(data));
+
+    // a module index of 0 does not refer to any module
+    record[10..12].copy_from_slice(&0u16.to_le_bytes());
+    let reference = match record.pread_with(0, ()).expect("parse data reference") {
+        pdb::SymbolData::DataReference(reference) => reference,
+        other => panic!("unexpected symbol {:?}", other),
+    };
+    assert_eq!(reference.module, None);
+    assert_eq!(pdb.resolve_data_reference(&reference).unwrap(), None);
+}
+
+#[test]
+fn section_symbol_translation() {
+    let file = std::fs::File::open("fixtures/self/foo.pdb").expect("opening file");
+    let mut pdb = pdb::PDB::open(file).expect("opening pdb");
+    let address_map = pdb.address_map().expect("address map");
+
+    let dbi = pdb.debug_information().expect("debug information");
+    let mut modules = dbi.modules().expect("modules");
+    let mut sections = Vec::new();
+    while let Some(module) = modules.next().expect("next module") {
+        if let Some(info) = pdb.module_info(&module).expect("module info") {
+            let symbols = info.symbols().expect("symbols");
+            sections.extend(pdb::section_symbols(symbols).expect("section symbols"));
+        }
+    }
+    assert!(!sections.is_empty());
+
+    // foo.pdb has no OMAP, so both paths agree for every public symbol
+    let global_symbols = pdb.global_symbols().expect("global symbols");
+    let mut symbols = global_symbols.iter();
+    let mut count = 0;
+    while let Some(symbol) = symbols.next().expect("next symbol") {
+        if let Ok(pdb::SymbolData::Public(data)) = symbol.parse() {
+            if data.offset.section == 0 {
+                continue;
+            }
+            assert_eq!(
+                data.offset.to_internal_rva_from_sections(&sections),
+                data.offset.to_internal_rva(&address_map),
+                "{}",
+                data.name
+            );
+            count += 1;
+        }
+    }
+    assert!(count > 0);
+
+    let missing = pdb::PdbInternalSectionOffset::new(0x7f, 0);
+    assert_eq!(missing.to_internal_rva_from_sections(&sections), None);
+}
+
+#[test]
+fn module_map_lookup() {
+    let file = std::fs::File::open("fixtures/self/foo.pdb").expect("opening file");
+    let mut pdb = pdb::PDB::open(file).expect("opening pdb");
+    let module_map = pdb.module_map().expect("module map");
+    let address_map = pdb.address_map().expect("address map");
+
+    // Baz::f_public is defined in foo.obj
+    let offset = pdb::PdbInternalSectionOffset::new(1, 0x54f0);
+    let module = module_map
+        .module_for_internal_offset(offset)
+        .expect("module for offset");
+    assert!(module.object_file_name.ends_with("foo.obj"), "{:?}", module);
+    assert_eq!(module_map.modules()[module.index], *module);
+
+    let rva = offset.to_rva(&address_map).expect("rva");
+    assert_eq!(module_map.module_for_rva(rva), Some(module));
+
+    let missing = pdb::PdbInternalSectionOffset::new(0x7f, 0);
+    assert_eq!(module_map.module_for_internal_offset(missing), None);
+}
+
+/// Opens the fixture and reads the module information of its first module.
+fn with_first_module<F>(func: F)
+where
+    F: FnOnce(&mut pdb::PDB<'_, std::fs::File>, &pdb::Module<'_>, &pdb::ModuleInfo<'_>),
+{
+    let file = std::fs::File::open("fixtures/self/foo.pdb").expect("opening file");
+    let mut pdb = pdb::PDB::open(file).expect("opening pdb");
+
+    let dbi = pdb.debug_information().expect("debug information");
+    let mut modules = dbi.modules().expect("modules");
+    let module = modules.next().expect("next module").expect("first module");
+    let info = pdb
+        .module_info(&module)
+        .expect("module info")
+        .expect("info");
+
+    func(&mut pdb, &module, &info);
+}
+
+#[test]
+fn module_instruction_set() {
+    with_first_module(|_pdb, _module, info| {
+        let mut symbols = info.symbols().expect("symbols");
+        let mut compile_flags = None;
+        while let Some(symbol) = symbols.next().expect("next symbol") {
+            if let Ok(pdb::SymbolData::CompileFlags(flags)) = symbol.parse() {
+                compile_flags = Some(flags);
+                break;
+            }
+        }
+
+        let compile_flags = compile_flags.expect("compile flags");
+        assert_eq!(compile_flags.cpu_type, pdb::CPUType::X64);
+        assert_eq!(compile_flags.instruction_set(), pdb::InstructionSet::X64);
+        assert!(!compile_flags.instruction_set().is_thumb());
+    });
+}
+
+#[test]
+fn module_global_refs() {
+    with_first_module(|pdb, _module, info| {
+        let global_symbols = pdb.global_symbols().expect("global symbols");
+
+        let global_refs = info.global_refs().expect("global refs");
+        assert!(!global_refs.is_empty());
+        for index in global_refs {
+            let mut symbols = global_symbols.iter_at(index);
+            let symbol = symbols.next().expect("next symbol").expect("symbol");
+            assert_eq!(symbol.index(), index);
+            symbol.parse().expect("parse");
+        }
+    });
+}
+
+#[test]
+fn module_obj_name() {
+    with_first_module(|_pdb, module, info| {
+        let obj_name = info.obj_name().expect("obj name").expect("S_OBJNAME");
+        assert_eq!(obj_name.name, module.object_file_name());
+        assert_eq!(obj_name.signature_kind(), pdb::ObjSignatureKind::None);
+    });
+}
+
+#[test]
+fn module_procedure_index() {
+    with_first_module(|_pdb, _module, info| {
+        let index =
+            pdb::ModuleProcedureIndex::new(info.symbols().expect("symbols")).expect("index");
+        assert!(!index.procedures().is_empty());
+
+        let f_public = index
+            .procedures()
+            .iter()
+            .find(|procedure| procedure.name == "Baz::f_public")
+            .expect("Baz::f_public");
+        assert_eq!(
+            f_public.offset,
+            pdb::PdbInternalSectionOffset::new(1, 0x54f0)
+        );
+
+        for delta in [0, f_public.len - 1] {
+            let offset = f_public.offset.saturating_add(delta);
+            let procedure = index.procedure_at(offset).expect("procedure");
+            assert_eq!(procedure.name, "Baz::f_public");
+        }
+
+        let past_end = f_public.offset.saturating_add(f_public.len);
+        assert_ne!(
+            index.procedure_at(past_end).map(|p| p.name.as_str()),
+            Some("Baz::f_public")
+        );
+        assert!(index
+            .procedure_at(pdb::PdbInternalSectionOffset::new(2, 0))
+            .is_none());
+    });
+}
+
+#[test]
+fn build_summary() {
+    let file = std::fs::File::open("fixtures/self/foo.pdb").expect("opening file");
+    let mut pdb = pdb::PDB::open(file).expect("opening pdb");
+
+    let summary = pdb.build_summary().expect("build summary");
+    assert!(summary.modules > 0);
+    assert_eq!(summary.languages.values().sum::<usize>(), summary.modules);
+    assert_eq!(summary.compilers.values().sum::<usize>(), summary.modules);
+    assert!(summary.optimized_for_speed <= summary.modules);
+    assert!(summary.profile_guided <= summary.modules);
+
+    let c_modules = [pdb::SourceLanguage::C, pdb::SourceLanguage::Cpp]
+        .iter()
+        .filter_map(|language| summary.languages.get(language))
+        .sum::<usize>();
+    assert!(c_modules > 0);
+
+    let versions = summary.backend_version_range().expect("versions");
+    assert!(versions.start() <= versions.end());
+}
@@ -36,3 +36,61 @@ fn test_symbol_depth() -> Result<()> {
 
     Ok(())
 }
+
+#[test]
+fn test_scope_ends() -> Result<()> {
+    let file = File::open("fixtures/self/foo.pdb")?;
+    let mut pdb = PDB::open(file)?;
+
+    let dbi = pdb.debug_information()?;
+    let mut modules = dbi.modules()?;
+
+    let mut procedures = 0;
+    while let Some(module) = modules.next()? {
+        let module_info = match pdb.module_info(&module)? {
+            Some(module_info) => module_info,
+            None => continue,
+        };
+
+        let ends = pdb::scope_ends(module_info.symbols()?)?;
+
+        let mut symbols = module_info.symbols()?;
+        while let Some(symbol) = symbols.next()? {
+            if let Ok(pdb::SymbolData::Procedure(procedure)) = symbol.parse() {
+                assert_eq!(ends.get(&symbol.index()), Some(&procedure.end));
+                procedures += 1;
+            }
+        }
+    }
+
+    assert!(procedures > 0);
+    Ok(())
+}
+
+#[test]
+fn test_symbols_end_at_symbol_region() -> Result<()> {
+    let file = File::open("fixtures/self/foo.pdb")?;
+    let mut pdb = PDB::open(file)?;
+
+    let dbi = pdb.debug_information()?;
+    let mut modules = dbi.modules()?;
+
+    while let Some(module) = modules.next()? {
+        let module_info = match pdb.module_info(&module)? {
+            Some(module_info) => module_info,
+            None => continue,
+        };
+
+        // The symbol region is followed by line information, which must not be parsed as symbols.
+        let mut end = 4;
+        let mut symbols = module_info.symbols()?;
+        while let Some(symbol) = symbols.next()? {
+            assert_eq!(symbol.index().0 as usize, end, "{}", module.module_name());
+            end += 2 + symbol.raw_bytes().len();
+        }
+
+        assert_eq!(end, module_info.symbols_size(), "{}", module.module_name());
+    }
+
+    Ok(())
+}
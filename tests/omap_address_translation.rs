@@ -34,14 +34,13 @@ fn test_omap_symbol() {
 
     // find the target symbol
     let target_symbol = {
-        let target_name = pdb::RawString::from("NtWaitForSingleObject");
+        let target_name = "NtWaitForSingleObject";
         let mut iter = global_symbols.iter();
         iter.find(|sym| {
             let matches = sym
                 .parse()
                 .ok()
-                .and_then(|d| d.name())
-                .map_or(false, |n| n == target_name);
+                .is_some_and(|d| d.name() == Some(target_name));
             Ok(matches)
         })
         .expect("iterate symbols")
@@ -164,3 +163,17 @@ fn test_omap_range() {
         vec![], // last record targets 0, thus the range is omitted
     );
 }
+
+#[test]
+fn test_omap_batch() {
+    let mut pdb = pdb::PDB::open(open_file()).expect("opening pdb");
+    let address_map = pdb.address_map().expect("address map");
+
+    let rvas: Vec<_> = (0..0x0060_0000).step_by(0x11).map(Rva).collect();
+    let batch = address_map.rva_to_internal_batch(&rvas);
+
+    assert_eq!(batch.len(), rvas.len());
+    for (rva, internal) in rvas.iter().zip(batch) {
+        assert_eq!(internal, rva.to_internal_offset(&address_map), "{rva}");
+    }
+}
@@ -0,0 +1,35 @@
+#![cfg(feature = "rayon")]
+
+use pdb2 as pdb;
+
+use pdb::FallibleIterator;
+
+#[test]
+fn par_modules_matches_sequential() {
+    let file = std::fs::File::open("fixtures/self/foo.pdb").expect("opening file");
+    let mut pdb = pdb::PDB::open(file).expect("opening pdb");
+
+    let dbi = pdb.debug_information().expect("debug information");
+    let mut modules = dbi.modules().expect("modules");
+    let mut expected = Vec::new();
+    while let Some(module) = modules.next().expect("next module") {
+        let count = match pdb.module_info(&module).expect("module info") {
+            Some(info) => info.symbols().expect("symbols").count().expect("count"),
+            None => 0,
+        };
+        expected.push((module.module_name().into_owned(), count));
+    }
+
+    let actual = pdb
+        .par_modules(|module, info| {
+            let count = match info {
+                Some(info) => info.symbols().expect("symbols").count().expect("count"),
+                None => 0,
+            };
+            (module.module_name().into_owned(), count)
+        })
+        .expect("par modules");
+
+    assert_eq!(actual.len(), 194);
+    assert_eq!(actual, expected);
+}
@@ -14,3 +14,103 @@ fn pdb_info() {
     );
     assert_eq!(pdb_info.signature, 0x587B_A621);
 }
+
+#[test]
+fn pdb_features() {
+    let file = std::fs::File::open("fixtures/self/foo.pdb").expect("opening file");
+
+    let mut pdb = pdb::PDB::open(file).expect("opening pdb");
+    let pdb_info = pdb.pdb_information().expect("pdb information");
+    let features = pdb_info.features().expect("features");
+
+    assert!(!features.vc110);
+    assert!(features.vc140);
+    assert!(!features.no_type_merge);
+    assert!(!features.minimal_debug_info);
+    assert!(features.has_id_stream());
+    pdb.id_information().expect("id information");
+}
+
+#[test]
+fn link_info_empty() {
+    let file = std::fs::File::open("fixtures/self/foo.pdb").expect("opening file");
+    let mut pdb = pdb::PDB::open(file).expect("opening pdb");
+
+    // foo.pdb declares a /LinkInfo stream, but like most PDBs written by modern linkers, it is empty.
+    assert!(pdb.named_stream(b"/LinkInfo").is_ok());
+    assert_eq!(pdb.link_info().expect("link info"), None);
+}
+
+#[test]
+fn injected_sources_empty() {
+    let file = std::fs::File::open("fixtures/self/foo.pdb").expect("opening file");
+    let mut pdb = pdb::PDB::open(file).expect("opening pdb");
+
+    // foo.pdb declares a /src/headerblock stream, but does not contain any injected sources.
+    assert!(pdb.named_stream(b"/src/headerblock").is_ok());
+    let sources = pdb.injected_sources().expect("injected sources");
+    assert_eq!(sources.count(), 0);
+}
+
+#[test]
+fn content_hash_stable() {
+    let file = std::fs::File::open("fixtures/self/foo.pdb").expect("opening file");
+    let mut pdb = pdb::PDB::open(file).expect("opening pdb");
+
+    let bytes = std::fs::read("fixtures/self/foo.pdb").expect("reading file");
+    let mut copy = pdb::PDB::open(std::io::Cursor::new(bytes)).expect("opening copy");
+
+    assert_eq!(
+        pdb.content_hash().expect("content hash"),
+        copy.content_hash().expect("content hash")
+    );
+
+    let dbi = pdb::StreamIndex(3);
+    let stream = pdb
+        .raw_stream(dbi)
+        .expect("raw stream")
+        .expect("dbi stream");
+    let copied = copy
+        .raw_stream(dbi)
+        .expect("raw stream")
+        .expect("dbi stream");
+    assert_eq!(stream.fingerprint(), copied.fingerprint());
+
+    let tpi = pdb::StreamIndex(2);
+    let other = pdb
+        .raw_stream(tpi)
+        .expect("raw stream")
+        .expect("tpi stream");
+    assert_ne!(stream.fingerprint(), other.fingerprint());
+}
+
+#[test]
+fn stream_directory() {
+    let file = std::fs::File::open("fixtures/self/foo.pdb").expect("opening file");
+    let mut pdb = pdb::PDB::open(file).expect("opening pdb");
+
+    let streams = pdb.stream_directory().expect("stream directory");
+    assert!(streams.len() > 208);
+
+    for (number, stream) in streams.iter().enumerate() {
+        assert_eq!(stream.index, pdb::StreamIndex(number as u16));
+        if !stream.present {
+            continue;
+        }
+
+        let raw = pdb.raw_stream(stream.index).expect("raw stream");
+        let raw = raw.expect("stream exists");
+        assert_eq!(raw.byte_len(), stream.byte_size as usize);
+        assert_eq!(
+            stream.page_count,
+            (stream.byte_size as usize).div_ceil(4096)
+        );
+    }
+
+    // the directory covers exactly the streams of the MSF
+    let past_end = pdb::StreamIndex(streams.len() as u16);
+    assert!(matches!(
+        pdb.raw_stream(past_end),
+        Err(pdb::Error::StreamNotFound(_))
+    ));
+}
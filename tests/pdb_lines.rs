@@ -36,3 +36,125 @@ fn test_module_lines() {
     assert_eq!(rva, Rva(0x64f0));
     assert_eq!(file_name, "c:\\users\\user\\desktop\\self\\foo.cpp");
 }
+
+#[test]
+fn test_has_line_info() {
+    let file = std::fs::File::open("fixtures/self/foo.pdb").expect("opening file");
+    let mut pdb = PDB::open(file).expect("parse pdb");
+
+    let dbi = pdb.debug_information().expect("dbi");
+    let mut modules = dbi.modules().expect("modules");
+    let mut with_lines = 0;
+    let mut without_lines = 0;
+    while let Some(module) = modules.next().expect("parse module") {
+        let module_info = match pdb.module_info(&module).expect("parse module info") {
+            Some(module_info) => module_info,
+            None => continue,
+        };
+
+        let line_program = module_info.line_program().expect("line program");
+        let has_lines = line_program
+            .lines()
+            .next()
+            .expect("parse line info")
+            .is_some();
+        assert_eq!(
+            module_info.has_line_info(),
+            has_lines,
+            "{}",
+            module.module_name()
+        );
+
+        if has_lines {
+            with_lines += 1;
+        } else {
+            without_lines += 1;
+        }
+    }
+
+    assert!(with_lines > 0);
+    assert!(without_lines > 0);
+}
+
+#[test]
+fn test_debug_subsections() {
+    let file = std::fs::File::open("fixtures/self/foo.pdb").expect("opening file");
+    let mut pdb = PDB::open(file).expect("parse pdb");
+
+    let dbi = pdb.debug_information().expect("dbi");
+    let mut modules = dbi.modules().expect("modules");
+    let module = modules.next().expect("parse module").expect("no module");
+    let module_info = pdb
+        .module_info(&module)
+        .expect("parse module info")
+        .expect("module info");
+
+    let kinds: Vec<_> = module_info
+        .debug_subsections()
+        .expect("debug subsections")
+        .map(|(kind, _data)| Ok(kind))
+        .collect()
+        .expect("collect subsections");
+
+    let lines = kinds
+        .iter()
+        .filter(|kind| **kind == pdb::DebugSubsectionKind::Lines)
+        .count();
+    assert_eq!(lines, 10);
+    assert_eq!(kinds[6], pdb::DebugSubsectionKind::FileChecksums);
+    assert_eq!(kinds.len(), 11);
+}
+
+#[test]
+fn test_symbol_location() {
+    let file = std::fs::File::open("fixtures/self/foo.pdb").expect("opening file");
+    let mut pdb = PDB::open(file).expect("parse pdb");
+
+    let procedures: Vec<_> = pdb
+        .all_procedures()
+        .expect("procedures")
+        .collect()
+        .expect("collect procedures");
+    let (_, procedure) = procedures
+        .iter()
+        .find(|(_, procedure)| procedure.name == "main")
+        .expect("main");
+
+    let location = pdb
+        .symbol_location(procedure)
+        .expect("symbol location")
+        .expect("location");
+    assert_eq!(location.file, "c:\\users\\user\\desktop\\self\\foo.cpp");
+    assert_eq!(location.line, 45);
+}
+
+#[test]
+fn test_symbol_locations() {
+    let file = std::fs::File::open("fixtures/self/foo.pdb").expect("opening file");
+    let mut pdb = PDB::open(file).expect("parse pdb");
+
+    let procedures: Vec<_> = pdb
+        .all_procedures()
+        .expect("procedures")
+        .collect()
+        .expect("collect procedures");
+    let locations = pdb
+        .symbol_locations(procedures.iter().map(|(_, procedure)| procedure))
+        .expect("symbol locations");
+    assert_eq!(locations.len(), procedures.len());
+
+    let main = procedures
+        .iter()
+        .position(|(_, procedure)| procedure.name == "main")
+        .expect("main");
+    let location = locations[main].as_ref().expect("location");
+    assert_eq!(location.line, 45);
+
+    // Resolving every procedure individually is slow, so spot check the batch results.
+    for index in [0, main, procedures.len() - 1] {
+        let single = pdb
+            .symbol_location(&procedures[index].1)
+            .expect("symbol location");
+        assert_eq!(locations[index], single);
+    }
+}
@@ -0,0 +1,187 @@
+//! A smoke test that feeds corrupted symbol records to the parser and checks it never panics.
+//!
+//! Records are seeded from the fixtures, then truncated at every length and mutated with a
+//! deterministic pseudo-random generator. See `fuzz/` for an open-ended `cargo fuzz` target.
+
+use std::collections::BTreeMap;
+
+use pdb2 as pdb;
+
+use pdb::FallibleIterator;
+use scroll::Pread;
+
+/// Number of distinct seed records used per symbol kind.
+const SEEDS_PER_KIND: usize = 32;
+/// Number of mutations applied to every seed record.
+const MUTATIONS_PER_SEED: usize = 256;
+
+fn collect_seeds() -> BTreeMap<u16, Vec<Vec<u8>>> {
+    let file = std::fs::File::open("fixtures/self/foo.pdb").expect("opening file");
+    let mut pdb = pdb::PDB::open(file).expect("opening pdb");
+
+    let mut seeds: BTreeMap<u16, Vec<Vec<u8>>> = BTreeMap::new();
+    let mut add = |symbol: pdb::Symbol<'_>| {
+        let records = seeds.entry(symbol.raw_kind()).or_default();
+        if records.len() < SEEDS_PER_KIND && !records.iter().any(|r| r == symbol.raw_bytes()) {
+            records.push(symbol.raw_bytes().to_vec());
+        }
+    };
+
+    let global_symbols = pdb.global_symbols().expect("global symbols");
+    let mut symbols = global_symbols.iter();
+    while let Some(symbol) = symbols.next().expect("next symbol") {
+        add(symbol);
+    }
+
+    let dbi = pdb.debug_information().expect("debug information");
+    let mut modules = dbi.modules().expect("modules");
+    while let Some(module) = modules.next().expect("next module") {
+        if let Some(info) = pdb.module_info(&module).expect("module info") {
+            let mut symbols = info.symbols().expect("symbols");
+            while let Some(symbol) = symbols.next().expect("next symbol") {
+                add(symbol);
+            }
+        }
+    }
+
+    seeds
+}
+
+/// A xorshift generator, so that failures are reproducible.
+struct Rng(u64);
+
+impl Rng {
+    fn next(&mut self) -> u64 {
+        self.0 ^= self.0 << 13;
+        self.0 ^= self.0 >> 7;
+        self.0 ^= self.0 << 17;
+        self.0
+    }
+
+    fn below(&mut self, n: usize) -> usize {
+        (self.next() % n as u64) as usize
+    }
+}
+
+fn parse(record: &[u8]) {
+    // Only panics are of interest, errors are expected.
+    let _ = record.pread_with::<pdb::SymbolData>(0, ());
+}
+
+fn mutate(rng: &mut Rng, record: &mut Vec<u8>) {
+    match rng.below(4) {
+        // overwrite a byte, but keep the symbol kind
+        0 if record.len() > 2 => {
+            let index = 2 + rng.below(record.len() - 2);
+            record[index] = rng.next() as u8;
+        }
+        // saturate a byte to trigger overflows in lengths and counts
+        1 if record.len() > 2 => {
+            let index = 2 + rng.below(record.len() - 2);
+            record[index] = 0xff;
+        }
+        // truncate the record
+        2 => {
+            let len = rng.below(record.len() + 1);
+            record.truncate(len.max(2));
+        }
+        // append garbage
+        _ => {
+            for _ in 0..rng.below(8) {
+                record.push(rng.next() as u8);
+            }
+        }
+    }
+}
+
+#[test]
+fn truncated_records() {
+    for records in collect_seeds().values() {
+        for record in records {
+            for len in 0..record.len() {
+                parse(&record[..len]);
+            }
+        }
+    }
+}
+
+#[test]
+fn mutated_records() {
+    let mut rng = Rng(0x2545_f491_4f6c_dd1d);
+
+    for records in collect_seeds().values() {
+        for record in records {
+            let mut mutated = record.clone();
+            for _ in 0..MUTATIONS_PER_SEED {
+                mutate(&mut rng, &mut mutated);
+                parse(&mutated);
+
+                if rng.below(4) == 0 {
+                    mutated.clone_from(record);
+                }
+            }
+        }
+    }
+}
+
+#[test]
+fn retyped_records() {
+    let seeds = collect_seeds();
+
+    // parse the payload of every record as every other kind of record
+    for &kind in seeds.keys() {
+        for records in seeds.values() {
+            for record in records {
+                let mut retyped = record.clone();
+                retyped[..2].copy_from_slice(&kind.to_le_bytes());
+                parse(&retyped);
+            }
+        }
+    }
+}
+
+/// Encodes a data symbol into a record, including the symbol kind.
+#[cfg(feature = "arbitrary")]
+fn encode_data_symbol(symbol: &pdb::DataSymbol) -> Vec<u8> {
+    let kind: u16 = match (symbol.global, symbol.managed) {
+        (false, false) => 0x110c, // S_LDATA32
+        (true, false) => 0x110d,  // S_GDATA32
+        (false, true) => 0x111c,  // S_LMANDATA
+        (true, true) => 0x111d,   // S_GMANDATA
+    };
+
+    let mut record = kind.to_le_bytes().to_vec();
+    record.extend_from_slice(&symbol.type_index.0.to_le_bytes());
+    record.extend_from_slice(&symbol.offset.offset.to_le_bytes());
+    record.extend_from_slice(&symbol.offset.section.to_le_bytes());
+    record.extend_from_slice(symbol.name.as_bytes());
+    record.push(0);
+    record
+}
+
+#[test]
+#[cfg(feature = "arbitrary")]
+fn arbitrary_data_symbols() {
+    use arbitrary::{Arbitrary, Unstructured};
+
+    let mut rng = Rng(0x9e37_79b9_7f4a_7c15);
+    let mut round_trips = 0;
+
+    for _ in 0..64 {
+        let bytes: Vec<u8> = (0..64).map(|_| rng.next() as u8).collect();
+        let mut unstructured = Unstructured::new(&bytes);
+        let symbol = pdb::DataSymbol::arbitrary(&mut unstructured).expect("arbitrary");
+
+        // names are null-terminated in records
+        if symbol.name.contains('\0') {
+            continue;
+        }
+
+        let record = encode_data_symbol(&symbol);
+        let parsed = record.pread_with::<pdb::SymbolData>(0, ()).expect("parse");
+        assert_eq!(parsed, pdb::SymbolData::Data(symbol));
+        round_trips += 1;
+    }
+
+    assert!(round_trips > 0);
+}
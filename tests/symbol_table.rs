@@ -1,4 +1,4 @@
-use std::collections::hash_map::{Entry, HashMap};
+use std::collections::HashMap;
 
 use pdb2 as pdb;
 
@@ -72,6 +72,76 @@ fn count_symbols() {
     })
 }
 
+#[test]
+fn count_matches_iteration() {
+    setup(|global_symbols, is_fixture| {
+        let count = global_symbols.count().expect("count symbols");
+        let iterated = global_symbols.iter().count().expect("iterate symbols");
+        assert_eq!(count, iterated);
+        assert!(!global_symbols.is_empty());
+
+        if is_fixture {
+            assert_eq!(count, 7436);
+        }
+    })
+}
+
+#[test]
+fn take_bytes_resumes() {
+    setup(|global_symbols, _is_fixture| {
+        let mut chunk = global_symbols.iter().take_bytes(10);
+        let first = chunk.next().expect("next symbol").expect("first symbol");
+        assert_eq!(chunk.next().expect("next symbol"), None);
+
+        // the budget stops on a record boundary, right after the first record
+        let position = chunk.position();
+        assert!(chunk.consumed() >= 10);
+        assert_eq!(
+            position.0 as usize,
+            first.index().0 as usize + first.raw_bytes().len() + 2
+        );
+        let resumed = global_symbols.iter_at(position).next().expect("resume");
+        assert_eq!(resumed.map(|symbol| symbol.index()), Some(position));
+
+        // loading in chunks visits every symbol exactly once
+        let mut count = 0;
+        let mut position = pdb::SymbolIndex(0);
+        loop {
+            let mut chunk = global_symbols.iter_at(position).take_bytes(1000);
+            while let Some(symbol) = chunk.next().expect("next symbol") {
+                assert!(symbol.index() >= position);
+                count += 1;
+            }
+            if chunk.is_exhausted() {
+                break;
+            }
+            position = chunk.position();
+        }
+        assert_eq!(count, global_symbols.count().expect("count symbols"));
+    })
+}
+
+#[test]
+fn take_bytes_zero() {
+    setup(|global_symbols, _is_fixture| {
+        // a zero budget still yields one record, so chunked loading makes progress
+        let mut count = 0;
+        let mut position = pdb::SymbolIndex(0);
+        loop {
+            let mut chunk = global_symbols.iter_at(position).take_bytes(0);
+            let symbol = chunk.next().expect("next symbol");
+            assert_eq!(chunk.next().expect("next symbol"), None);
+            if symbol.is_none() {
+                assert!(chunk.is_exhausted());
+                break;
+            }
+            count += 1;
+            position = chunk.position();
+        }
+        assert_eq!(count, global_symbols.count().expect("count symbols"));
+    })
+}
+
 #[test]
 fn find_symbols() {
     setup(|global_symbols, is_fixture| {
@@ -80,7 +150,7 @@ fn find_symbols() {
             return;
         }
 
-        let mut map: HashMap<&[u8], Option<pdb::SymbolData<'_>>> = HashMap::new();
+        let mut map: HashMap<&[u8], Option<pdb::SymbolData>> = HashMap::new();
 
         // look for:
         // main(), defined in the program
@@ -102,12 +172,12 @@ fn find_symbols() {
             let data = sym.parse().expect("symbol parsing");
 
             // get symbol name
-            let name = data.name().unwrap_or_default();
+            let name = data.name().unwrap_or_default().to_owned();
 
-            if let Entry::Occupied(mut e) = map.entry(name.as_bytes()) {
+            if let Some(slot) = map.get_mut(name.as_bytes()) {
                 // this is a symbol we wanted to find
                 // store our data
-                e.insert(Some(data));
+                *slot = Some(data);
             }
         }
 
@@ -123,3 +193,440 @@ fn find_symbols() {
         }
     })
 }
+
+#[test]
+fn iter_in_range() {
+    setup(|global_symbols, is_fixture| {
+        if !is_fixture {
+            return;
+        }
+
+        let main = global_symbols
+            .iter()
+            .filter_map(|sym| Ok(sym.parse().ok()))
+            .find(|data| Ok(data.name() == Some("main")))
+            .expect("iterate symbols")
+            .expect("find main");
+        let offset = main.offset().expect("main offset");
+
+        let found: Vec<_> = global_symbols
+            .iter_in_range(offset.section, offset.offset..offset.offset + 1)
+            .map(|sym| sym.parse())
+            .collect()
+            .expect("iterate range");
+        assert!(found.contains(&main));
+        assert!(found.iter().all(|data| data.offset() == Some(offset)));
+
+        // the whole section should match a manual filter over all symbols
+        let ranged = global_symbols
+            .iter_in_range(offset.section, 0..u32::MAX)
+            .count()
+            .expect("count range");
+        let filtered = global_symbols
+            .iter()
+            .filter_map(|sym| Ok(sym.parse().ok().and_then(|data| data.offset())))
+            .filter(|o| Ok(o.section == offset.section && o.offset < u32::MAX))
+            .count()
+            .expect("count filtered");
+        assert_eq!(ranged, filtered);
+        assert!(ranged > found.len());
+    })
+}
+
+#[test]
+fn owned_symbols_outlive_table() {
+    let file = std::fs::File::open("fixtures/self/foo.pdb").expect("opening file");
+    let mut pdb = pdb::PDB::open(file).expect("opening pdb");
+
+    let (owned, expected) = {
+        let global_symbols = pdb.global_symbols().expect("global symbols");
+        let mut iter = global_symbols.iter();
+        let mut owned = Vec::new();
+        let mut expected = Vec::new();
+        while let Some(sym) = iter.next().expect("next symbol") {
+            if owned.len() == 16 {
+                break;
+            }
+            owned.push(sym.to_owned());
+            expected.push((sym.index(), sym.raw_kind(), sym.parse().expect("parse")));
+        }
+        (owned, expected)
+    };
+    drop(pdb);
+
+    let parsed: Vec<_> = owned
+        .iter()
+        .map(|sym| (sym.index(), sym.raw_kind(), sym.parse().expect("parse")))
+        .collect();
+    assert_eq!(parsed, expected);
+}
+
+#[test]
+fn public_rvas_batch() {
+    let file = std::fs::File::open("fixtures/self/foo.pdb").expect("opening file");
+    let mut pdb = pdb::PDB::open(file).expect("opening pdb");
+    let address_map = pdb.address_map().expect("address map");
+    let symbol_table = pdb.global_symbols().expect("global symbols");
+
+    let mut offsets = Vec::new();
+    let mut symbols = symbol_table.iter();
+    while let Some(symbol) = symbols.next().expect("next symbol") {
+        if let Ok(pdb::SymbolData::Public(public)) = symbol.parse() {
+            offsets.push(public.offset);
+        }
+    }
+
+    let mut rvas: Vec<_> = offsets
+        .iter()
+        .filter_map(|offset| offset.to_rva(&address_map))
+        .collect();
+    rvas.sort();
+    rvas.push(pdb::Rva(u32::MAX));
+
+    let batch = address_map.rva_to_internal_batch(&rvas);
+    for (rva, internal) in rvas.iter().zip(&batch) {
+        assert_eq!(*internal, rva.to_internal_offset(&address_map), "{rva}");
+    }
+
+    assert_eq!(batch.last(), Some(&None));
+}
+
+#[test]
+fn public_thunks() {
+    let file = std::fs::File::open("fixtures/self/foo.pdb").expect("opening file");
+    let mut pdb = pdb::PDB::open(file).expect("opening pdb");
+    let symbol_table = pdb.global_symbols().expect("global symbols");
+
+    let find_public = |name: &str| {
+        let mut symbols = symbol_table.iter();
+        while let Some(symbol) = symbols.next().expect("next symbol") {
+            match symbol.parse() {
+                Ok(pdb::SymbolData::Public(public)) if public.name == name => return public,
+                _ => continue,
+            }
+        }
+        panic!("missing public {:?}", name);
+    };
+
+    let import_thunk = find_public("WriteFile");
+    let function = find_public("main");
+    assert!(import_thunk.function);
+    assert!(function.function);
+
+    let debug_info = pdb.debug_information().expect("debug information");
+    let mut modules = debug_info.modules().expect("modules");
+    let module = loop {
+        let module = modules
+            .next()
+            .expect("next module")
+            .expect("kernel32 import module");
+        if module.module_name() == "Import:KERNEL32.dll" {
+            break module;
+        }
+    };
+    let module_info = pdb
+        .module_info(&module)
+        .expect("module info")
+        .expect("module symbols");
+
+    let thunks = || Some(module_info.symbols().expect("symbols"));
+    assert!(!import_thunk.looks_like_thunk(None).expect("thunk"));
+    assert!(import_thunk.looks_like_thunk(thunks()).expect("thunk"));
+    assert!(!function.looks_like_thunk(thunks()).expect("thunk"));
+
+    let incremental = pdb::PublicSymbol {
+        code: true,
+        function: false,
+        ..function
+    };
+    assert!(incremental.looks_like_thunk(None).expect("thunk"));
+}
+
+#[test]
+fn public_functions_in_text() {
+    let file = std::fs::File::open("fixtures/self/foo.pdb").expect("opening file");
+    let mut pdb = pdb::PDB::open(file).expect("opening pdb");
+    let sections = pdb.sections().expect("sections").expect("section headers");
+    let address_map = pdb.address_map().expect("address map");
+    let symbol_table = pdb.global_symbols().expect("global symbols");
+
+    let (text, text_header) = address_map.section_by_name(".text").expect(".text");
+    let (data, data_header) = address_map.section_by_name(".data").expect(".data");
+    assert_eq!(sections[usize::from(text) - 1], *text_header);
+    assert_eq!(sections[usize::from(data) - 1], *data_header);
+    assert!(address_map.section_by_name(".missing").is_none());
+    assert!(address_map.section_range(0).is_none());
+
+    let text_range = address_map.section_range(text).expect("text range");
+    let data_range = address_map.section_range(data).expect("data range");
+    assert_eq!(text_range.start, pdb::Rva(text_header.virtual_address));
+    assert!(text_range.end <= data_range.start || data_range.end <= text_range.start);
+
+    let mut functions = 0;
+    let mut symbols = symbol_table.iter();
+    while let Some(symbol) = symbols.next().expect("next symbol") {
+        if let Ok(pdb::SymbolData::Public(public)) = symbol.parse() {
+            let in_text = public
+                .offset
+                .to_rva(&address_map)
+                .is_some_and(|rva| text_range.contains(&rva));
+            assert_eq!(in_text, public.offset.section == text, "{}", public.name);
+            if public.function {
+                assert!(in_text, "{}", public.name);
+                functions += 1;
+            }
+        }
+    }
+    assert!(functions > 0);
+}
+
+#[test]
+fn diff_modified_copy() {
+    fn first_module(bytes: Vec<u8>) -> pdb::ModuleInfo<'static> {
+        let mut pdb = pdb::PDB::open(std::io::Cursor::new(bytes)).expect("opening pdb");
+        let dbi = pdb.debug_information().expect("dbi");
+        let mut modules = dbi.modules().expect("modules");
+        let module = modules.next().expect("next module").expect("module");
+        pdb.module_info(&module)
+            .expect("module info")
+            .expect("module info")
+    }
+
+    let original = std::fs::read("fixtures/self/foo.pdb").expect("reading file");
+    let old = first_module(original.clone());
+
+    // Patch procedure records in place: grow `Baz::f_public`, move `Baz::static_f_public` and
+    // rename `Baz::Baz` to `Caz::Baz`. After the kind, a procedure record stores its parent, end
+    // and next (12 bytes), the length (4), debug start and end, type (12) and offset (4), segment
+    // and flags (3), and the name.
+    let mut modified = original;
+    let mut patch = |name: &str, patch: &dyn Fn(&mut [u8])| {
+        let mut symbols = old.symbols().expect("symbols");
+        while let Some(symbol) = symbols.next().expect("next symbol") {
+            if let Ok(pdb::SymbolData::Procedure(data)) = symbol.parse() {
+                if data.name == name {
+                    let raw = symbol.raw_bytes();
+                    let position = modified
+                        .windows(raw.len())
+                        .position(|window| window == raw)
+                        .expect("record in file");
+                    patch(&mut modified[position..position + raw.len()]);
+                    return;
+                }
+            }
+        }
+        panic!("procedure {:?} not found", name);
+    };
+
+    patch("Baz::f_public", &|raw| raw[14] += 1);
+    patch("Baz::static_f_public", &|raw| raw[30] += 4);
+    patch("Baz::Baz", &|raw| raw[37] = b'C');
+
+    let new = first_module(modified);
+    let diff = pdb::diff_symbols(
+        old.symbols().expect("symbols"),
+        new.symbols().expect("symbols"),
+    )
+    .expect("diff");
+
+    let names = |symbols: &[pdb::SymbolData]| {
+        symbols
+            .iter()
+            .map(|data| data.name().expect("name").to_owned())
+            .collect::<Vec<_>>()
+    };
+    let pair_names = |pairs: &[(pdb::SymbolData, pdb::SymbolData)]| {
+        pairs
+            .iter()
+            .map(|(old, new)| {
+                assert_eq!(old.name(), new.name());
+                old.name().expect("name").to_owned()
+            })
+            .collect::<Vec<_>>()
+    };
+
+    assert_eq!(names(&diff.added), vec!["Caz::Baz"]);
+    assert_eq!(names(&diff.removed), vec!["Baz::Baz"]);
+    assert_eq!(pair_names(&diff.moved), vec!["Baz::static_f_public"]);
+    assert_eq!(pair_names(&diff.changed), vec!["Baz::f_public"]);
+
+    let (old, new) = &diff.moved[0];
+    assert_eq!(
+        new.offset().expect("offset").offset,
+        old.offset().expect("offset").offset + 4
+    );
+}
+
+#[test]
+fn section_symbol_histogram() {
+    let file = std::fs::File::open("fixtures/self/foo.pdb").expect("opening file");
+    let mut pdb = pdb::PDB::open(file).expect("opening pdb");
+    let address_map = pdb.address_map().expect("address map");
+    let (text, _) = address_map.section_by_name(".text").expect(".text");
+
+    let dbi = pdb.debug_information().expect("dbi");
+    let mut modules = dbi.modules().expect("modules");
+    let mut histogram = HashMap::new();
+    while let Some(module) = modules.next().expect("next module") {
+        if let Some(info) = pdb.module_info(&module).expect("module info") {
+            let symbols = info.symbols().expect("symbols");
+            for (key, count) in pdb::section_symbol_histogram(symbols).expect("histogram") {
+                *histogram.entry(key).or_insert(0) += count;
+            }
+        }
+    }
+
+    assert!(histogram[&(text, pdb::SymbolKindTag::Procedure)] > 0);
+    assert!(histogram
+        .keys()
+        .filter(|(_, kind)| *kind == pdb::SymbolKindTag::Procedure)
+        .all(|(section, _)| *section == text));
+}
+
+#[test]
+#[cfg(feature = "json")]
+fn write_ndjson() {
+    let file = std::fs::File::open("fixtures/self/foo.pdb").expect("opening file");
+    let mut pdb = pdb::PDB::open(file).expect("opening pdb");
+    let address_map = pdb.address_map().expect("address map");
+    let symbol_table = pdb.global_symbols().expect("global symbols");
+
+    let mut output = Vec::new();
+    symbol_table
+        .write_ndjson(&mut output, Some(&address_map))
+        .expect("write ndjson");
+    let output = String::from_utf8(output).expect("utf-8");
+
+    let mut symbols = symbol_table.iter();
+    let mut lines = output.lines();
+    while let Some(symbol) = symbols.next().expect("next symbol") {
+        let line = lines.next().expect("line per symbol");
+        let value: serde_json::Value = serde_json::from_str(line).expect("json");
+        assert_eq!(value["index"], symbol.index().0);
+        assert_eq!(value["kind"], symbol.raw_kind());
+
+        if let Ok(pdb::SymbolData::Public(public)) = symbol.parse() {
+            assert_eq!(value["tag"], "Public");
+            assert_eq!(value["name"], public.name.to_string().as_ref());
+            assert_eq!(value["section"], public.offset.section);
+            assert_eq!(value["offset"], public.offset.offset);
+            match public.offset.to_rva(&address_map) {
+                Some(rva) => assert_eq!(value["rva"], rva.0),
+                None => assert!(value.get("rva").is_none()),
+            }
+        }
+    }
+    assert_eq!(lines.next(), None);
+}
+
+#[test]
+fn merge_functions() {
+    let file = std::fs::File::open("fixtures/self/foo.pdb").expect("opening file");
+    let mut pdb = pdb::PDB::open(file).expect("opening pdb");
+    let address_map = pdb.address_map().expect("address map");
+
+    let globals = pdb.global_symbols().expect("global symbols");
+    let publics: Vec<_> = globals
+        .iter()
+        .filter_map(|symbol| match symbol.parse() {
+            Ok(pdb::SymbolData::Public(public)) => Ok(Some(public)),
+            _ => Ok(None),
+        })
+        .collect()
+        .expect("collect publics");
+
+    let procedures: Vec<_> = pdb
+        .all_procedures()
+        .expect("procedures")
+        .map(|(_, procedure)| Ok(procedure))
+        .collect()
+        .expect("collect procedures");
+
+    let functions = pdb::merge_functions(&publics, &procedures, &address_map);
+    assert!(functions.len() >= procedures.len() / 2);
+    assert!(functions.windows(2).all(|pair| pair[0].rva < pair[1].rva));
+
+    let function = functions
+        .iter()
+        .find(|function| function.rva == pdb::Rva(0x64f0))
+        .expect("merged function");
+    assert_eq!(
+        *function,
+        pdb::MergedFunction {
+            rva: pdb::Rva(0x64f0),
+            size: Some(14),
+            name: "Baz::f_public".into(),
+            mangled_name: Some("?f_public@Baz@@QEAAMXZ".into()),
+        }
+    );
+}
+
+#[test]
+fn to_rva_map() {
+    let file = std::fs::File::open("fixtures/self/foo.pdb").expect("opening file");
+    let mut pdb = pdb::PDB::open(file).expect("opening pdb");
+    let address_map = pdb.address_map().expect("address map");
+    let globals = pdb.global_symbols().expect("global symbols");
+
+    let symbols = globals.to_rva_map(&address_map).expect("rva map");
+    assert!(!symbols.is_empty());
+
+    match symbols.get(&pdb::Rva(0x64f0)) {
+        Some(pdb::SymbolData::Public(public)) => {
+            assert_eq!(public.name, "?f_public@Baz@@QEAAMXZ");
+            assert!(public.function);
+        }
+        other => panic!("unexpected symbol {:?}", other),
+    }
+
+    let (rva, symbol) = symbols
+        .range(..=pdb::Rva(0x64f4))
+        .next_back()
+        .expect("preceding symbol");
+    assert_eq!(*rva, pdb::Rva(0x64f0));
+    assert_eq!(symbol.name(), Some("?f_public@Baz@@QEAAMXZ"));
+
+    let symbols = pdb.global_symbol_rva_map().expect("rva map");
+    match symbols.get(&pdb::Rva(0x64f0)) {
+        Some(pdb::SymbolData::Procedure(procedure)) => assert_eq!(procedure.name, "Baz::f_public"),
+        other => panic!("unexpected symbol {:?}", other),
+    }
+}
+
+#[test]
+fn unique_udts() {
+    let file = std::fs::File::open("fixtures/self/foo.pdb").expect("opening file");
+    let mut pdb = pdb::PDB::open(file).expect("opening pdb");
+
+    let is_udt = |symbol: &pdb::Symbol<'_>| {
+        Ok(matches!(
+            symbol.parse_lenient(),
+            pdb::SymbolData::UserDefinedType(_)
+        ))
+    };
+
+    let mut raw_count = pdb
+        .global_symbols()
+        .expect("global symbols")
+        .iter()
+        .filter(is_udt)
+        .count()
+        .expect("count global udts");
+
+    let dbi = pdb.debug_information().expect("dbi");
+    let mut modules = dbi.modules().expect("modules");
+    while let Some(module) = modules.next().expect("parse module") {
+        if let Some(info) = pdb.module_info(&module).expect("module info") {
+            raw_count += info
+                .symbols()
+                .expect("symbols")
+                .filter(is_udt)
+                .count()
+                .expect("count module udts");
+        }
+    }
+
+    let udts = pdb.unique_udts().expect("unique udts");
+    assert!(!udts.is_empty());
+    assert!(udts.len() < raw_count, "{} >= {}", udts.len(), raw_count);
+}
@@ -1,4 +1,5 @@
 use std::collections::HashMap;
+use std::convert::TryInto;
 
 use pdb2 as pdb;
 
@@ -21,6 +22,24 @@ where
     func(&type_information);
 }
 
+/// Opens the fixture and builds a type finder over all of its types.
+fn setup_with_finder<F>(func: F)
+where
+    F: FnOnce(&mut pdb::PDB<'_, std::fs::File>, &pdb::TypeInformation<'_>, &pdb::TypeFinder<'_>),
+{
+    let file = std::fs::File::open("fixtures/self/foo.pdb").expect("opening file");
+    let mut pdb = pdb::PDB::open(file).expect("opening pdb");
+    let type_information = pdb.type_information().expect("type information");
+
+    let mut type_finder = type_information.finder();
+    let mut iter = type_information.iter();
+    while iter.next().expect("next type").is_some() {
+        type_finder.update(&iter);
+    }
+
+    func(&mut pdb, &type_information, &type_finder);
+}
+
 #[test]
 fn iteration() {
     setup(|type_information| {
@@ -67,6 +86,45 @@ fn type_finder() {
     })
 }
 
+#[test]
+fn iter_at() {
+    setup(|type_information| {
+        let mut type_finder = type_information.finder();
+        let mut types = Vec::new();
+
+        let mut iter = type_information.iter();
+        while let Some(typ) = iter.next().expect("next type") {
+            type_finder.update(&iter);
+            types.push(typ);
+        }
+
+        // start in the middle of a finder bucket, so some records have to be skipped
+        let start = types.len() / 2 + 3;
+        let mut iter = type_information
+            .iter_at(types[start].index(), &type_finder)
+            .expect("iter at");
+
+        for expected in &types[start..start + 16] {
+            let typ = iter.next().expect("next type").expect("type");
+            assert_eq!(typ.index(), expected.index());
+            assert_eq!(typ, *expected);
+        }
+
+        let from_start = type_information
+            .iter_at(pdb::TypeIndex(0x74), &type_finder)
+            .expect("iter at primitive")
+            .next()
+            .expect("next type");
+        assert_eq!(from_start, Some(types[0]));
+
+        let last = types.last().expect("last type").index();
+        assert!(matches!(
+            type_information.iter_at(pdb::TypeIndex(last.0 + 2), &type_finder),
+            Err(pdb::Error::TypeNotFound(_))
+        ));
+    })
+}
+
 #[test]
 fn find_classes() {
     setup(|type_information| {
@@ -158,6 +216,144 @@ fn find_classes() {
     })
 }
 
+#[test]
+fn call_site_signature() {
+    setup_with_finder(|_pdb, _type_information, type_finder| {
+        // The first indirect call site in `exe_main.obj` calls through a pointer to
+        // `void (__cdecl *)(void *, unsigned long, void *)`.
+        let call_site = pdb::CallSiteInfoSymbol {
+            offset: pdb::PdbInternalSectionOffset {
+                offset: 0x58b3,
+                section: 0x1,
+            },
+            type_index: pdb::TypeIndex(0x10f3),
+        };
+
+        let (parameters, return_type, calling_convention) =
+            call_site.signature(type_finder).expect("resolve signature");
+        assert_eq!(
+            parameters,
+            [
+                pdb::TypeIndex(0x603),
+                pdb::TypeIndex(0x22),
+                pdb::TypeIndex(0x603)
+            ]
+        );
+        assert_eq!(return_type, pdb::TypeIndex(0x3));
+        assert_eq!(calling_convention, pdb::CallingConvention::NearC);
+
+        // A heap allocation site referring to a class is not a function signature.
+        let heap_site = pdb::HeapAllocationSiteSymbol {
+            offset: pdb::PdbInternalSectionOffset::default(),
+            instr_length: 5,
+            type_index: pdb::TypeIndex(0x1311),
+        };
+        assert!(matches!(
+            heap_site.signature(type_finder),
+            Err(pdb::Error::NotAFunctionType(0x1311))
+        ));
+    })
+}
+
+#[test]
+fn group_heap_allocation_sites() {
+    setup_with_finder(|pdb, _type_information, type_finder| {
+        let dbi = pdb.debug_information().expect("debug information");
+        let mut modules = dbi.modules().expect("modules");
+        let mut sites = Vec::new();
+        while let Some(module) = modules.next().expect("next module") {
+            if let Some(info) = pdb.module_info(&module).expect("module info") {
+                let symbols = info.symbols().expect("symbols");
+                sites.extend(pdb::heap_allocation_sites(symbols).expect("heap allocation sites"));
+            }
+        }
+
+        let groups = pdb::group_heap_allocation_sites(&sites, type_finder).expect("group");
+        let total: usize = groups.values().map(Vec::len).sum();
+        assert_eq!(total, sites.len());
+        assert!(groups.contains_key("__vcrt_ptd"));
+    })
+}
+
+#[test]
+fn constant_enum_member() {
+    setup_with_finder(|pdb, _type_information, type_finder| {
+        let global_symbols = pdb.global_symbols().expect("global symbols");
+        let mut symbols = global_symbols.iter();
+        let mut members = Vec::new();
+        while let Some(symbol) = symbols.next().expect("next symbol") {
+            if let Ok(pdb::SymbolData::Constant(constant)) = symbol.parse() {
+                if let Some(member) = constant.enum_member(type_finder).expect("enum member") {
+                    members.push((constant, member));
+                }
+            }
+        }
+
+        assert_eq!(members.len(), 548);
+
+        let (constant, member) = members
+            .iter()
+            .find(|(constant, _)| constant.name == "__ISA_AVAILABLE_AVX2")
+            .expect("find constant");
+        assert_eq!(constant.value, pdb::Variant::U16(5));
+        assert_eq!(
+            *member,
+            pdb::EnumConstant {
+                enum_name: "ISA_AVAILABILITY".to_string(),
+                member_name: "__ISA_AVAILABLE_AVX2".to_string(),
+                underlying_type: pdb::TypeIndex(0x74),
+                value: pdb::Variant::I32(5),
+            }
+        );
+    })
+}
+
+#[test]
+fn constant_display_value() {
+    setup_with_finder(|pdb, _type_information, type_finder| {
+        let global_symbols = pdb.global_symbols().expect("global symbols");
+        let mut symbols = global_symbols.iter();
+        let mut constants = HashMap::new();
+        while let Some(symbol) = symbols.next().expect("next symbol") {
+            if let Ok(pdb::SymbolData::Constant(constant)) = symbol.parse() {
+                constants.insert(constant.name.clone(), constant);
+            }
+        }
+
+        let display = |constant: &pdb::ConstantSymbol| {
+            constant.display_value(type_finder).expect("display value")
+        };
+
+        let avx2 = &constants["__ISA_AVAILABLE_AVX2"];
+        assert_eq!(display(avx2), "ISA_AVAILABILITY::__ISA_AVAILABLE_AVX2");
+
+        // an enum-typed value without a matching enumerator
+        let mut flags = avx2.clone();
+        flags.value = pdb::Variant::U16(0x100);
+        assert_eq!(display(&flags), "256");
+
+        // `const int` stored as a signed byte, and `const unsigned short`
+        assert_eq!(
+            display(&constants["__acrt_stdio_char_traits<char>::eof"]),
+            "-1"
+        );
+        assert_eq!(
+            display(&constants["__acrt_stdio_char_traits<wchar_t>::eof"]),
+            "65535"
+        );
+
+        let mut boolean = pdb::ConstantSymbol {
+            managed: false,
+            type_index: pdb::TypeIndex(0x30), // bool
+            value: pdb::Variant::U8(1),
+            name: "enabled".to_string(),
+        };
+        assert_eq!(display(&boolean), "true");
+        boolean.value = pdb::Variant::U8(0);
+        assert_eq!(display(&boolean), "false");
+    })
+}
+
 /*
 #[bench]
 fn bench_type_finder(b: &mut test::Bencher) {
@@ -212,3 +408,384 @@ fn type_length_histogram() {
     })
 }
 */
+
+/// Rewrites the stream directory of a big MSF so that `stream` becomes the nil stream.
+fn remove_stream(data: &mut [u8], stream: usize) {
+    let read_u32 = |data: &[u8], offset: usize| {
+        u32::from_le_bytes(data[offset..offset + 4].try_into().unwrap()) as usize
+    };
+
+    let page_size = read_u32(data, 32);
+    let directory_size = read_u32(data, 44);
+    let block_map = read_u32(data, 52) * page_size;
+
+    // gather the pages that make up the stream directory
+    let directory_pages: Vec<usize> = (0..directory_size.div_ceil(page_size))
+        .map(|i| read_u32(data, block_map + i * 4))
+        .collect();
+    let directory: Vec<u8> = directory_pages
+        .iter()
+        .flat_map(|&page| data[page * page_size..(page + 1) * page_size].to_vec())
+        .take(directory_size)
+        .collect();
+
+    // drop the stream's size and page numbers from the directory
+    let stream_count = read_u32(&directory, 0);
+    let sizes: Vec<usize> = (0..stream_count)
+        .map(|i| read_u32(&directory, 4 + i * 4))
+        .collect();
+    let pages = |size: usize| {
+        if size == u32::MAX as usize {
+            0
+        } else {
+            size.div_ceil(page_size)
+        }
+    };
+    let skip: usize = sizes[..stream].iter().map(|&size| pages(size)).sum();
+    let page_list = 4 + stream_count * 4 + skip * 4;
+
+    let mut new_directory = directory.clone();
+    new_directory[4 + stream * 4..8 + stream * 4].copy_from_slice(&u32::MAX.to_le_bytes());
+    new_directory.drain(page_list..page_list + pages(sizes[stream]) * 4);
+
+    // the directory only shrinks, so it fits into the pages it occupied before
+    for (chunk, &page) in new_directory.chunks(page_size).zip(&directory_pages) {
+        data[page * page_size..page * page_size + chunk.len()].copy_from_slice(chunk);
+    }
+    data[44..48].copy_from_slice(&(new_directory.len() as u32).to_le_bytes());
+}
+
+#[test]
+fn missing_tpi() {
+    let mut data = std::fs::read("fixtures/self/foo.pdb").expect("reading file");
+    remove_stream(&mut data, 2);
+
+    let mut pdb = pdb::PDB::open(std::io::Cursor::new(data)).expect("opening pdb");
+    let type_information = pdb.type_information().expect("type information");
+    assert!(type_information.is_empty());
+    assert!(type_information.iter().next().expect("iter").is_none());
+
+    let finder = type_information.finder();
+    finder.find(pdb::TypeIndex(0x1000)).expect_err("find index");
+
+    // symbol-only tooling is unaffected by the missing types
+    let symbol_table = pdb.global_symbols().expect("global symbols");
+    assert_eq!(symbol_table.iter().count().expect("count"), 7436);
+    assert!(!pdb.id_information().expect("id information").is_empty());
+}
+
+#[test]
+fn classify_user_defined_types() {
+    let file = std::fs::File::open("fixtures/self/foo.pdb").expect("opening file");
+    let mut pdb = pdb::PDB::open(file).expect("opening pdb");
+    let type_information = pdb.type_information().expect("type information");
+
+    let mut type_finder = type_information.finder();
+    let mut iter = type_information.iter();
+    while iter.next().expect("next type").is_some() {
+        type_finder.update(&iter);
+    }
+
+    let global_symbols = pdb.global_symbols().expect("global symbols");
+    let mut symbols = global_symbols.iter();
+    let mut kinds = HashMap::new();
+    while let Some(symbol) = symbols.next().expect("next symbol") {
+        if let Ok(pdb::SymbolData::UserDefinedType(udt)) = symbol.parse() {
+            let kind = udt.classify(&type_finder).expect("classify");
+            kinds.insert(udt.name, kind);
+        }
+    }
+
+    // typedef unsigned __int64 size_t;
+    assert_eq!(kinds["size_t"], pdb::UserDefinedTypeKind::Typedef);
+    // typedef struct _iobuf FILE;
+    assert_eq!(kinds["FILE"], pdb::UserDefinedTypeKind::Alias);
+    // a struct in an anonymous namespace, spelled `anonymous-namespace'::module_id in the TPI
+    assert_eq!(
+        kinds["?A0x679b24ab::module_id"],
+        pdb::UserDefinedTypeKind::Definition
+    );
+}
+
+#[test]
+fn struct_dependencies() {
+    setup(|type_information| {
+        let mut type_finder = type_information.finder();
+        let mut guid = None;
+        let mut iter = type_information.iter();
+        while let Some(typ) = iter.next().expect("next type") {
+            type_finder.update(&iter);
+            if let Ok(pdb::TypeData::Class(class)) = typ.parse() {
+                if class.name == "_GUID" && class.fields.is_some() {
+                    guid = Some((typ.index(), class));
+                }
+            }
+        }
+
+        let (index, class) = guid.expect("struct _GUID");
+        let fields = class.fields.expect("fields");
+        let dependencies = type_information
+            .dependencies(&[index])
+            .expect("dependencies");
+        assert!(dependencies.contains(&index));
+        assert!(dependencies.contains(&fields));
+
+        let field_list = match type_finder.find(fields).expect("find").parse() {
+            Ok(pdb::TypeData::FieldList(field_list)) => field_list,
+            other => panic!("unexpected type {:?}", other),
+        };
+        assert_eq!(field_list.fields.len(), 4);
+        for field in &field_list.fields {
+            match field {
+                pdb::TypeData::Member(member) => {
+                    assert!(dependencies.contains(&member.field_type), "{}", member.name);
+                }
+                other => panic!("unexpected field {:?}", other),
+            }
+        }
+
+        // `Data4` is an array of `unsigned char`.
+        assert!(dependencies.contains(&pdb::TypeIndex(0x20)));
+        assert!(dependencies.contains(&pdb::TypeIndex(0x22)));
+        assert!(dependencies.len() < type_information.len());
+    });
+}
+
+#[test]
+fn calling_conventions() {
+    setup_with_finder(|_pdb, _type_information, type_finder| {
+        let calling_convention = |index| match type_finder.find(pdb::TypeIndex(index)) {
+            Ok(item) => match item.parse() {
+                Ok(pdb::TypeData::Procedure(data)) => data.calling_convention(),
+                Ok(pdb::TypeData::MemberFunction(data)) => data.calling_convention(),
+                other => panic!("unexpected type {:?}", other),
+            },
+            Err(e) => panic!("type not found: {:?}", e),
+        };
+
+        assert_eq!(calling_convention(0x1003), pdb::CallingConvention::NearC);
+        assert_eq!(
+            calling_convention(0x1388),
+            pdb::CallingConvention::NearVector
+        );
+        assert_eq!(calling_convention(0x1388).to_string(), "__vectorcall");
+    })
+}
+
+#[test]
+fn array_element_counts() {
+    setup_with_finder(|_pdb, _type_information, type_finder| {
+        let array = |index| match type_finder.find(pdb::TypeIndex(index)) {
+            Ok(item) => match item.parse() {
+                Ok(pdb::TypeData::Array(data)) => data,
+                other => panic!("unexpected type {:?}", other),
+            },
+            Err(e) => panic!("type not found: {:?}", e),
+        };
+
+        // `unsigned char Data4[8]` of `_GUID`
+        let data4 = array(0x109b);
+        assert_eq!(data4.element_type, pdb::TypeIndex(0x20));
+        assert_eq!(data4.indexing_type, pdb::TypeIndex(0x23));
+        assert_eq!(data4.len_bytes(), 8);
+        assert_eq!(data4.name, None);
+        assert_eq!(data4.element_count(type_finder).expect("count"), Some(8));
+
+        // the element type `_M128A` is a forward reference without a size
+        let forward = array(0x108c);
+        assert_eq!(forward.len_bytes(), 128);
+        assert_eq!(forward.element_count(type_finder).expect("count"), None);
+    })
+}
+
+#[test]
+fn bitfield_members() {
+    setup_with_finder(|_pdb, _type_information, type_finder| {
+        // struct { ULONGLONG Depth : 16; ULONGLONG Sequence : 48; ULONGLONG Reserved : 4;
+        //          ULONGLONG NextEntry : 60; } HeaderX64 of _SLIST_HEADER
+        let class = match type_finder
+            .find(pdb::TypeIndex(0x11be))
+            .expect("find")
+            .parse()
+        {
+            Ok(pdb::TypeData::Class(class)) => class,
+            other => panic!("unexpected type {:?}", other),
+        };
+        assert_eq!(class.name, "_SLIST_HEADER::<unnamed-type-HeaderX64>");
+
+        let mut members = Vec::new();
+        let mut fields = type_finder.fields(class.fields.expect("fields"));
+        while let Some(field) = fields.next().expect("next field") {
+            if let pdb::TypeData::Member(member) = field {
+                let bitfield = member.bitfield(type_finder).expect("bitfield");
+                let bitfield = bitfield.expect("bitfield member");
+                assert_eq!(bitfield.underlying_type, pdb::TypeIndex(0x23));
+                members.push((
+                    member.name,
+                    member.offset,
+                    bitfield.position,
+                    bitfield.length,
+                ));
+            }
+        }
+
+        assert_eq!(
+            members,
+            vec![
+                ("Depth".to_owned(), 0, 0, 16),
+                ("Sequence".to_owned(), 0, 16, 48),
+                ("Reserved".to_owned(), 8, 0, 4),
+                ("NextEntry".to_owned(), 8, 4, 60),
+            ]
+        );
+
+        let sequence = match type_finder
+            .find(pdb::TypeIndex(0x11ba))
+            .expect("find")
+            .parse()
+        {
+            Ok(pdb::TypeData::Bitfield(bitfield)) => bitfield,
+            other => panic!("unexpected type {:?}", other),
+        };
+        assert_eq!(sequence.mask(), 0xffff_ffff_ffff_0000);
+
+        // a class is not a field list
+        let mut fields = type_finder.fields(pdb::TypeIndex(0x11be));
+        assert!(matches!(
+            fields.next(),
+            Err(pdb::Error::NotAFieldList(0x11be))
+        ));
+    })
+}
+
+#[test]
+fn type_sizes() {
+    let file = std::fs::File::open("fixtures/self/foo.pdb").expect("opening file");
+    let mut pdb = pdb::PDB::open(file).expect("opening pdb");
+    let type_information = pdb.type_information().expect("type information");
+    let size_of = |index| {
+        type_information
+            .size_of(pdb::TypeIndex(index))
+            .expect("size of")
+    };
+
+    // primitives
+    assert_eq!(size_of(0x74), Some(4)); // int
+    assert_eq!(size_of(0x603), Some(8)); // void *
+    assert_eq!(size_of(0x3), None); // void
+
+    // struct _GUID
+    assert_eq!(size_of(0x109d), Some(16));
+    // pointer
+    assert_eq!(size_of(0x1081), Some(8));
+    // forward reference to struct _M128A
+    assert_eq!(size_of(0x108b), Some(16));
+    // _M128A[8]
+    assert_eq!(size_of(0x108c), Some(128));
+}
+
+#[test]
+fn virtual_methods() {
+    setup_with_finder(|_pdb, _type_information, type_finder| {
+        // class std::exception { virtual ~exception(); virtual char const* what() const; }
+        let class = match type_finder
+            .find(pdb::TypeIndex(0x1212))
+            .expect("find")
+            .parse()
+        {
+            Ok(pdb::TypeData::Class(class)) => class,
+            other => panic!("unexpected type {:?}", other),
+        };
+        assert_eq!(class.name, "std::exception");
+
+        let methods = type_finder
+            .virtual_methods(class.fields.expect("fields"))
+            .expect("virtual methods");
+        let slots: Vec<_> = methods
+            .iter()
+            .map(|method| (method.name.as_str(), method.vtable_offset))
+            .collect();
+        assert_eq!(
+            slots,
+            vec![("~exception", 0), ("__vecDelDtor", 0), ("what", 8)]
+        );
+
+        let shape = class.vtable_shape.expect("vtable shape");
+        match type_finder.find(shape).expect("find").parse() {
+            Ok(pdb::TypeData::VirtualTableShape(shape)) => {
+                // one descriptor per slot
+                assert_eq!(
+                    shape.descriptors,
+                    vec![pdb::VirtualTableShapeDescriptor::Near32; 2]
+                );
+            }
+            other => panic!("unexpected type {:?}", other),
+        }
+    })
+}
+
+#[test]
+fn base_classes() {
+    setup_with_finder(|_pdb, _type_information, type_finder| {
+        // class std::bad_cast : public std::exception
+        let class = match type_finder
+            .find(pdb::TypeIndex(0x11d7))
+            .expect("find")
+            .parse()
+        {
+            Ok(pdb::TypeData::Class(class)) => class,
+            other => panic!("unexpected type {:?}", other),
+        };
+        assert_eq!(class.name, "std::bad_cast");
+
+        let bases = type_finder
+            .base_classes(class.fields.expect("fields"))
+            .expect("base classes");
+        assert_eq!(bases.len(), 1);
+        assert_eq!(bases[0].base_class(), pdb::TypeIndex(0x11bf));
+        assert_eq!(bases[0].offset(), Some(0));
+        assert!(matches!(bases[0], pdb::BaseClass::Direct(_)));
+    })
+}
+
+#[test]
+fn function_parameters() {
+    setup_with_finder(|_pdb, type_information, type_finder| {
+        let mut procedures = Vec::new();
+        let mut member_functions = Vec::new();
+        let mut iter = type_information.iter();
+        while let Some(typ) = iter.next().expect("next type") {
+            match typ.parse() {
+                Ok(pdb::TypeData::Procedure(data)) if data.parameter_count == 2 => {
+                    procedures.push((typ.index(), data));
+                }
+                Ok(pdb::TypeData::MemberFunction(data)) if data.this_pointer_type.is_some() => {
+                    member_functions.push((typ.index(), data));
+                }
+                _ => (),
+            }
+        }
+
+        assert!(!procedures.is_empty());
+        for (index, data) in procedures {
+            let parameters = type_finder.function_parameters(index).expect("parameters");
+            assert_eq!(parameters.len(), 2);
+            match type_finder.find(data.argument_list).expect("find").parse() {
+                Ok(pdb::TypeData::ArgumentList(list)) => assert_eq!(parameters, list.arguments),
+                other => panic!("unexpected type {:?}", other),
+            }
+        }
+
+        assert!(!member_functions.is_empty());
+        for (index, data) in member_functions {
+            let parameters = type_finder.function_parameters(index).expect("parameters");
+            assert_eq!(parameters.len(), usize::from(data.parameter_count) + 1);
+            assert_eq!(Some(parameters[0]), data.this_pointer_type);
+        }
+
+        assert!(matches!(
+            type_finder.function_parameters(pdb::TypeIndex(0x74)),
+            Err(pdb::Error::NotAFunctionType(0x74))
+        ));
+    })
+}
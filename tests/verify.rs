@@ -0,0 +1,35 @@
+use std::io::Cursor;
+
+use pdb2 as pdb;
+
+use pdb::{VerifyProblem, PDB};
+
+fn read_fixture() -> Vec<u8> {
+    std::fs::read("fixtures/self/foo.pdb").expect("reading file")
+}
+
+#[test]
+fn verify_intact() {
+    let mut pdb = PDB::open(Cursor::new(read_fixture())).expect("opening pdb");
+    let report = pdb.verify();
+    assert!(report.is_ok(), "unexpected problems: {:?}", report.problems);
+}
+
+#[test]
+fn verify_truncated() {
+    let mut data = read_fixture();
+    data.truncate(data.len() / 2);
+
+    // Opening only reads the MSF header, so truncation is not noticed yet.
+    let mut pdb = PDB::open(Cursor::new(data)).expect("opening pdb");
+    let report = pdb.verify();
+
+    assert!(!report.is_ok());
+    assert_eq!(
+        report.problems,
+        [
+            VerifyProblem::Truncated { pages_used: 1107 },
+            VerifyProblem::UnreadableStreamDirectory,
+        ]
+    );
+}